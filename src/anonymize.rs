@@ -0,0 +1,416 @@
+//! Scrubbing account-identifying data from a save before it is shared.
+//!
+//! Every SteamID-shaped digit run becomes a placeholder SteamID, so the owner
+//! property stays valid and distinct IDs stay distinct. Names such as the
+//! persona and login of the accounts on this machine, and other SteamID
+//! notations, are overwritten with a placeholder of the same length. When the
+//! save parses, only string values are searched for them, in properties and
+//! in arrays, sets and maps, so property and type names are never touched.
+//! Nothing changes size, so the save still parses.
+
+use crate::backup::BackupStore;
+use crate::error::{Error, Result};
+use crate::manifest::{Change, Edit, Operation};
+use crate::steam::{self, SteamAccount};
+use crate::gvas::SaveGame;
+use crate::steamid::SteamId;
+use crate::{files, replace, transaction};
+use std::fmt;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+/// Shorter names match too much unrelated data.
+const MIN_NAME_LEN: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdentifierKind {
+    SteamId,
+    /// A SteamID written as `STEAM_0:Y:Z` or `[U:1:N]`.
+    SteamIdText,
+    PersonaName,
+    AccountName,
+    /// The operating system user, e.g. inside a stored path.
+    UserName,
+    Custom,
+}
+
+impl fmt::Display for IdentifierKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IdentifierKind::SteamId => write!(f, "SteamID"),
+            IdentifierKind::SteamIdText => write!(f, "SteamID text"),
+            IdentifierKind::PersonaName => write!(f, "persona name"),
+            IdentifierKind::AccountName => write!(f, "account name"),
+            IdentifierKind::UserName => write!(f, "user name"),
+            IdentifierKind::Custom => write!(f, "custom"),
+        }
+    }
+}
+
+/// What to look for besides SteamID64 digit runs, which are always scrubbed.
+#[derive(Debug, Clone, Default)]
+pub struct Identifiers {
+    /// Accounts whose other SteamID notations are searched for.
+    pub steamids: Vec<SteamId>,
+    pub names: Vec<(IdentifierKind, String)>,
+}
+
+impl Identifiers {
+    /// The accounts that logged into Steam on this machine, the operating
+    /// system user name and `extra`.
+    pub fn local(extra: &[String]) -> Self {
+        let mut identifiers = Self::default();
+        for steam_dir in steam::installed_steam_dirs() {
+            if let Ok(accounts) = steam::load_accounts(&steam_dir) {
+                identifiers.add_accounts(&accounts);
+            }
+        }
+        for var in ["USERNAME", "USER"] {
+            if let Ok(user) = std::env::var(var) {
+                identifiers.add(IdentifierKind::UserName, &user);
+            }
+        }
+        for text in extra {
+            identifiers.add(IdentifierKind::Custom, text);
+        }
+        identifiers
+    }
+
+    pub fn add_accounts(&mut self, accounts: &[SteamAccount]) {
+        for account in accounts {
+            if !self.steamids.contains(&account.steamid) {
+                self.steamids.push(account.steamid);
+            }
+            self.add(IdentifierKind::PersonaName, &account.persona_name);
+            self.add(IdentifierKind::AccountName, &account.account_name);
+        }
+    }
+
+    /// Adds a name to search for, ignoring very short and duplicate ones.
+    pub fn add(&mut self, kind: IdentifierKind, name: &str) {
+        let name = name.trim();
+        let is_known = self.names.iter().any(|(_, known)| known.eq_ignore_ascii_case(name));
+        if name.chars().count() >= MIN_NAME_LEN && !is_known {
+            self.names.push((kind, name.to_string()));
+        }
+    }
+}
+
+/// One identifier that was overwritten.
+#[derive(Debug, Clone)]
+pub struct Scrubbed {
+    pub offset: usize,
+    pub kind: IdentifierKind,
+    pub original: String,
+    pub placeholder: String,
+    /// Stored as UTF-16 rather than Latin-1.
+    pub wide: bool,
+    /// Property path of a SteamID when the save could be parsed.
+    pub path: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AnonymizeOptions {
+    pub identifiers: Identifiers,
+    /// Write the anonymized save here and leave the original untouched.
+    pub output: Option<PathBuf>,
+    /// Let `output` replace an existing file, which is backed up first.
+    pub overwrite: bool,
+    /// Where to back up the file being replaced; `None` skips the backup.
+    pub backup: Option<BackupStore>,
+    pub dry_run: bool,
+}
+
+impl Default for AnonymizeOptions {
+    fn default() -> Self {
+        Self {
+            identifiers: Identifiers::default(),
+            output: None,
+            overwrite: false,
+            backup: Some(BackupStore::default()),
+            dry_run: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AnonymizeReport {
+    pub scrubbed: Vec<Scrubbed>,
+    pub backup_path: Option<PathBuf>,
+    /// The anonymized file.
+    pub final_path: PathBuf,
+}
+
+/// Overwrites every identifier in place and returns what was scrubbed,
+/// ordered by offset.
+pub fn anonymize(data: &mut [u8], identifiers: &Identifiers) -> Vec<Scrubbed> {
+    let mut scrubbed: Vec<Scrubbed> = Vec::new();
+    let strings = string_ranges(data);
+
+    // Number the property values first, so the owner gets the first placeholder.
+    let matches = replace::find_steamid_matches(data);
+    let mut steamids: Vec<String> = Vec::new();
+    for m in matches.iter().filter(|m| m.is_property).chain(matches.iter().filter(|m| !m.is_property)) {
+        if !steamids.contains(&m.steamid) {
+            steamids.push(m.steamid.clone());
+        }
+    }
+
+    for m in matches {
+        let index = steamids.iter().position(|known| *known == m.steamid).unwrap_or_default();
+        let placeholder = placeholder_steamid(index);
+        if placeholder == m.steamid {
+            continue;
+        }
+        data[m.offset..m.offset + placeholder.len()].copy_from_slice(placeholder.as_bytes());
+        scrubbed.push(Scrubbed {
+            offset: m.offset,
+            kind: IdentifierKind::SteamId,
+            original: m.steamid,
+            placeholder,
+            wide: false,
+            path: m.path,
+        });
+    }
+
+    let mut texts: Vec<(IdentifierKind, String)> = Vec::new();
+    let found = steamids.iter().filter_map(|s| s.parse().ok().and_then(|v| SteamId::from_steamid64(v).ok()));
+    for steamid in identifiers.steamids.iter().copied().chain(found) {
+        for text in [steamid.steam2(), steamid.steam3()] {
+            if !texts.iter().any(|(_, known)| *known == text) {
+                texts.push((IdentifierKind::SteamIdText, text));
+            }
+        }
+    }
+    let first_name = texts.len();
+    texts.extend(identifiers.names.iter().cloned());
+    let wide_strings = if strings.is_none() { scan_wide_strings(data) } else { Vec::new() };
+
+    for (index, (kind, text)) in texts.iter().enumerate() {
+        for wide in [false, true] {
+            let Some(length) = encode(text, wide).map(|needle| needle.len()) else {
+                continue;
+            };
+            // The match must lie within the string it starts in, on a character
+            // boundary for wide text. Of the scanned strings, the one starting
+            // last is taken, as a bogus length further back can span the real one.
+            let ranges = match &strings {
+                Some(ranges) => Some(ranges.as_slice()),
+                None if wide => Some(wide_strings.as_slice()),
+                None => None,
+            };
+            let in_string = |offset: usize| {
+                ranges.is_none_or(|ranges| {
+                    ranges
+                        .iter()
+                        .filter(|r| r.contains(&offset))
+                        .max_by_key(|r| r.start)
+                        .is_some_and(|r| offset + length <= r.end && (!wide || (offset - r.start).is_multiple_of(2)))
+                })
+            };
+            for offset in find_text(data, text, wide, in_string) {
+                let overlaps = scrubbed
+                    .iter()
+                    .any(|s| offset < s.offset + encoded_len(s) && s.offset < offset + length);
+                if overlaps {
+                    continue;
+                }
+
+                let original = decode(&data[offset..offset + length], wide);
+                let placeholder = match kind {
+                    IdentifierKind::SteamIdText => placeholder_steam_text(&original, index),
+                    _ => placeholder_name(length / if wide { 2 } else { 1 }, index - first_name),
+                };
+                let Some(encoded) = encode(&placeholder, wide).filter(|_| placeholder != original) else {
+                    continue;
+                };
+                data[offset..offset + length].copy_from_slice(&encoded);
+                scrubbed.push(Scrubbed {
+                    offset,
+                    kind: *kind,
+                    original,
+                    placeholder,
+                    wide,
+                    path: None,
+                });
+            }
+        }
+    }
+
+    scrubbed.sort_by_key(|s| s.offset);
+    scrubbed
+}
+
+/// Anonymizes `path` in place, or into `options.output` when given.
+pub fn anonymize_file(path: &Path, options: &AnonymizeOptions) -> Result<AnonymizeReport> {
+    let original = files::read_save(path)?;
+    let mut data = original.clone();
+    let scrubbed = anonymize(&mut data, &options.identifiers);
+    if scrubbed.is_empty() {
+        return Err(Error::NoIdentifiersFound);
+    }
+
+    let final_path = options.output.clone().unwrap_or_else(|| path.to_path_buf());
+    if let Some(output) = &options.output {
+        if output == path {
+            return Err(Error::SameSlot(output.clone()));
+        }
+        if output.exists() && !options.overwrite {
+            return Err(Error::SlotExists(output.clone()));
+        }
+    }
+
+    if options.dry_run {
+        return Ok(AnonymizeReport {
+            scrubbed,
+            backup_path: None,
+            final_path,
+        });
+    }
+
+    // A copy goes through the same checks and history as an in-place edit.
+    let plan = transaction::Plan {
+        backup: options.backup.as_ref(),
+        rename_to: options.output.clone(),
+        overwrite_target: options.overwrite,
+        keep_source: options.output.is_some(),
+        record: Some(Change {
+            operation: Operation::Anonymize,
+            new_steamid: scrubbed
+                .iter()
+                .find(|s| s.kind == IdentifierKind::SteamId)
+                .map(|s| s.placeholder.clone()),
+            edits: scrubbed
+                .iter()
+                .filter(|s| s.kind == IdentifierKind::SteamId)
+                .map(|s| Edit {
+                    offset: s.offset,
+                    path: s.path.clone(),
+                    old: Some(s.original.clone()),
+                    new: Some(s.placeholder.clone()),
+                })
+                .collect(),
+        }),
+    };
+    let committed = transaction::commit(path, &original, &data, &plan)?;
+    let backup_path = committed.backup_path.or(committed.replaced_backup);
+
+    Ok(AnonymizeReport {
+        scrubbed,
+        backup_path,
+        final_path,
+    })
+}
+
+/// Placeholder SteamIDs are the first public individual accounts: valid, and
+/// easy to recognise.
+fn placeholder_steamid(index: usize) -> String {
+    SteamId::from_account_id(index as u32 + 1).to_string()
+}
+
+/// A valid `STEAM_0:1:Z` or `[U:1:N]` of the same length as `original`: the
+/// lowest account ID with as many digits, offset by `index` to keep distinct
+/// IDs distinct.
+fn placeholder_steam_text(original: &str, index: usize) -> String {
+    let digits = original
+        .rsplit(|c: char| !c.is_ascii_digit())
+        .find(|run| !run.is_empty())
+        .map_or(1, str::len) as u32;
+    let lowest = if digits == 1 { 1 } else { 10u64.pow(digits - 1) };
+    let number = (lowest + index as u64).min(10u64.pow(digits) - 1);
+    if original.starts_with('[') {
+        SteamId::from_account_id(number as u32).steam3()
+    } else {
+        SteamId::from_account_id((number * 2 + 1) as u32).steam2()
+    }
+}
+
+/// `Player2___`, or `XXX` when that does not fit, in exactly `length` code
+/// units (UTF-16 units for wide strings, bytes otherwise) so the stored length
+/// stays the same.
+fn placeholder_name(length: usize, index: usize) -> String {
+    let base = format!("Player{}", index + 1);
+    if length >= base.len() {
+        format!("{:_<length$}", base)
+    } else {
+        "X".repeat(length)
+    }
+}
+
+/// Byte ranges of the characters of every string value, in properties and
+/// containers alike, or `None` when the save does not round-trip and the
+/// whole file has to be searched.
+fn string_ranges(data: &[u8]) -> Option<Vec<Range<usize>>> {
+    SaveGame::parse_lossless(data).ok().map(|save| save.string_ranges())
+}
+
+/// Character ranges of everything shaped like a wide FString: a negative
+/// length followed by that many UTF-16 units, the last of them zero.
+fn scan_wide_strings(data: &[u8]) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    for pos in 0..data.len().saturating_sub(4) {
+        let length = i32::from_le_bytes([data[pos], data[pos + 1], data[pos + 2], data[pos + 3]]);
+        let Some(units) = length.checked_neg().and_then(|units| usize::try_from(units).ok()) else {
+            continue;
+        };
+        let start = pos + 4;
+        if units == 0 || units > (data.len() - start) / 2 {
+            continue;
+        }
+        let end = start + (units - 1) * 2;
+        if data[end..end + 2] == [0, 0] {
+            ranges.push(start..end);
+        }
+    }
+    ranges
+}
+
+/// Offsets of `text` as a whole word, ASCII case-insensitively, where
+/// `accept` allows a match there.
+fn find_text(data: &[u8], text: &str, wide: bool, accept: impl Fn(usize) -> bool) -> Vec<usize> {
+    let Some(needle) = encode(text, wide) else {
+        return Vec::new();
+    };
+    if needle.is_empty() || needle.len() > data.len() {
+        return Vec::new();
+    }
+    let unit = if wide { 2 } else { 1 };
+    let is_word_byte = |pos: usize| data.get(pos).is_some_and(|b| b.is_ascii_alphanumeric());
+
+    let mut offsets = Vec::new();
+    let mut i = 0;
+    while i + needle.len() <= data.len() {
+        let end = i + needle.len();
+        let starts_word = i < unit || !is_word_byte(i - unit);
+        if data[i..end].eq_ignore_ascii_case(&needle) && starts_word && !is_word_byte(end) && accept(i) {
+            offsets.push(i);
+            i = end;
+        } else {
+            i += 1;
+        }
+    }
+    offsets
+}
+
+/// `text` as a wide (UTF-16) or narrow (Latin-1) FString stores it; `None`
+/// if it has characters Latin-1 cannot hold.
+fn encode(text: &str, wide: bool) -> Option<Vec<u8>> {
+    if wide {
+        Some(text.encode_utf16().flat_map(u16::to_le_bytes).collect())
+    } else {
+        text.chars().map(|c| u8::try_from(c).ok()).collect()
+    }
+}
+
+fn decode(bytes: &[u8], wide: bool) -> String {
+    if wide {
+        let units: Vec<u16> = bytes.chunks_exact(2).map(|c| u16::from_le_bytes([c[0], c[1]])).collect();
+        String::from_utf16_lossy(&units)
+    } else {
+        bytes.iter().map(|&b| char::from(b)).collect()
+    }
+}
+
+fn encoded_len(scrubbed: &Scrubbed) -> usize {
+    encode(&scrubbed.placeholder, scrubbed.wide).map_or(0, |bytes| bytes.len())
+}
@@ -0,0 +1,108 @@
+use std::path::{Path, PathBuf};
+use steamid_replacer::anonymize::{self, AnonymizeOptions, Identifiers, Scrubbed};
+use steamid_replacer::backup::BackupStore;
+use steamid_replacer::{Error, files};
+
+#[derive(Default)]
+pub struct AnonymizeTab {
+    pub file_path: String,
+    /// Extra names to scrub, comma separated.
+    pub extra: String,
+    /// Write a copy to `output_path` instead of editing the save.
+    pub save_copy: bool,
+    pub output_path: String,
+    pub overwrite: bool,
+    /// What would be, or was, scrubbed.
+    pub scrubbed: Vec<Scrubbed>,
+    pub status: String,
+    pub backup_filename: String,
+}
+
+impl AnonymizeTab {
+    /// Loads `file_path`, suggests a copy next to it and previews what would be scrubbed.
+    pub fn load(&mut self) {
+        self.output_path = default_output(Path::new(&self.file_path))
+            .map(|path| path.to_string_lossy().to_string())
+            .unwrap_or_default();
+        self.save_copy = true;
+        self.overwrite = false;
+        self.preview();
+    }
+
+    pub fn preview(&mut self) {
+        self.scrubbed.clear();
+        if self.file_path.is_empty() {
+            return;
+        }
+        let options = AnonymizeOptions {
+            dry_run: true,
+            ..self.options(None)
+        };
+        match anonymize::anonymize_file(Path::new(&self.file_path), &options) {
+            Ok(report) => self.scrubbed = report.scrubbed,
+            Err(Error::NoIdentifiersFound) => {}
+            Err(e) => self.status = format!("❌ Error: {}", e),
+        }
+    }
+
+    pub fn handle_anonymize(&mut self, backup: BackupStore) {
+        if self.file_path.is_empty() {
+            self.status = "(!) Please select a file first".to_string();
+            self.backup_filename.clear();
+            return;
+        }
+        if self.save_copy && self.output_path.trim().is_empty() {
+            self.status = "(!) Please choose where to save the copy".to_string();
+            self.backup_filename.clear();
+            return;
+        }
+
+        match anonymize::anonymize_file(Path::new(&self.file_path), &self.options(Some(backup))) {
+            Ok(report) => {
+                self.status = format!(
+                    "✅ Successfully scrubbed {} identifier(s)!\nAnonymized save: {}",
+                    report.scrubbed.len(),
+                    report.final_path.display()
+                );
+                self.backup_filename = report
+                    .backup_path
+                    .as_deref()
+                    .map(files::backup_file_name)
+                    .unwrap_or_default();
+                self.scrubbed = report.scrubbed;
+                self.overwrite = false;
+            }
+            Err(Error::NoIdentifiersFound) => {
+                self.status = "(!) No SteamIDs or account names found in file".to_string();
+                self.backup_filename.clear();
+            }
+            Err(e) => {
+                self.status = format!("❌ Error: {}", e);
+                self.backup_filename.clear();
+            }
+        }
+    }
+
+    fn options(&self, backup: Option<BackupStore>) -> AnonymizeOptions {
+        let extra: Vec<String> = self
+            .extra
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(str::to_string)
+            .collect();
+        AnonymizeOptions {
+            identifiers: Identifiers::local(&extra),
+            output: self.save_copy.then(|| PathBuf::from(self.output_path.trim())),
+            overwrite: self.overwrite,
+            backup,
+            dry_run: false,
+        }
+    }
+}
+
+/// `SBSave0001.anonymized.sav` next to the save.
+fn default_output(path: &Path) -> Option<PathBuf> {
+    let stem = path.file_stem()?.to_string_lossy();
+    Some(path.with_file_name(format!("{}.anonymized.sav", stem)))
+}
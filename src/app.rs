@@ -1,5 +1,16 @@
-use crate::{replacer::ReplacerTab, remover::RemoverTab, utils};
+use crate::{anonymizer::AnonymizeTab, backups::BackupsTab, batch_view::BatchView, diff_view::{self, DiffView}, editor::{self, EditorTab}, hex_viewer::{self, HexViewTab}, replacer::ReplacerTab, remover::RemoverTab, slot_manager::SlotsTab, utils};
 use eframe::egui;
+use steamid_replacer::occurrences::{self, Occurrence};
+use std::path::{Path, PathBuf};
+use steamid_replacer::batch::{BatchOperation, BatchOptions, FileOutcome};
+use steamid_replacer::cloud::CloudStatus;
+use steamid_replacer::demo::TransferTarget;
+use steamid_replacer::diff::{Cell, CellKind, Row};
+use steamid_replacer::edit::{self, ScalarKind};
+use steamid_replacer::files;
+use steamid_replacer::gvas::Property;
+use steamid_replacer::hex::{Marker, SearchKind};
+use steamid_replacer::steamid;
 
 #[derive(Default)]
 pub struct SteamIDApp {
@@ -10,13 +21,27 @@ pub struct SteamIDApp {
     drag_hover: bool,
     pub replacer: ReplacerTab,
     pub remover: RemoverTab,
+    pub editor: EditorTab,
+    pub backups: BackupsTab,
+    pub slots: SlotsTab,
+    pub anonymizer: AnonymizeTab,
+    pub hex: HexViewTab,
+    pub batch: BatchView,
+    /// The tab that started the running batch, which gets its status.
+    batch_tab: AppTab,
+    pub diff: DiffView,
 }
 
-#[derive(Default, PartialEq)]
+#[derive(Default, Clone, Copy, PartialEq)]
 enum AppTab {
     #[default]
     Replacer,
     Remover,
+    Editor,
+    Backups,
+    Slots,
+    Anonymize,
+    Hex,
 }
 
 impl eframe::App for SteamIDApp {
@@ -24,6 +49,7 @@ impl eframe::App for SteamIDApp {
         self.handle_keyboard_input(ctx);
         self.load_icon_if_needed(ctx);
         self.handle_drag_and_drop(ctx);
+        self.poll_batch(ctx);
         self.show_menu_bar(ctx);
         self.show_main_content(ctx);
         self.show_dialogs(ctx);
@@ -31,6 +57,12 @@ impl eframe::App for SteamIDApp {
 }
 
 impl SteamIDApp {
+    pub fn new() -> Self {
+        let mut app = Self::default();
+        app.replacer.load_accounts();
+        app
+    }
+
     fn handle_keyboard_input(&mut self, ctx: &egui::Context) {
         ctx.input(|i| {
             if i.key_pressed(egui::Key::Escape) {
@@ -38,6 +70,13 @@ impl SteamIDApp {
                     self.show_about = false;
                 } else if self.show_help {
                     self.show_help = false;
+                } else if self.batch.show {
+                    self.batch.show = false;
+                } else if self.diff.show {
+                    self.diff.show = false;
+                } else {
+                    self.replacer.show_occurrences = false;
+                    self.remover.show_occurrences = false;
                 }
             }
         });
@@ -55,34 +94,127 @@ impl SteamIDApp {
     }
 
     fn handle_drag_and_drop(&mut self, ctx: &egui::Context) {
-        ctx.input(|i| {
-            if !i.raw.hovered_files.is_empty() {
-                self.drag_hover = true;
-            } else {
-                self.drag_hover = false;
-            }
-            if !i.raw.dropped_files.is_empty() {
-                if let Some(file) = i.raw.dropped_files.first() {
-                    if let Some(path) = &file.path {
-                        let file_path = path.to_string_lossy().to_string();
-                        match self.current_tab {
-                            AppTab::Replacer => {
-                                self.replacer.file_path = file_path;
-                                self.replacer.status = "File loaded via drag & drop".to_string();
-                                self.replacer.backup_filename.clear();
-                                self.replacer.read_current_steamid();
-                            }
-                            AppTab::Remover => {
-                                self.remover.file_path = file_path;
-                                self.remover.status = "File loaded via drag & drop".to_string();
-                                self.remover.backup_filename.clear();
-                                self.remover.read_current_steamid();
-                            }
-                        }
-                    }
+        let dropped: Vec<PathBuf> = ctx.input(|i| {
+            self.drag_hover = !i.raw.hovered_files.is_empty();
+            i.raw.dropped_files.iter().filter_map(|file| file.path.clone()).collect()
+        });
+
+        let is_edit_tab = matches!(self.current_tab, AppTab::Replacer | AppTab::Remover);
+        if is_edit_tab && (dropped.len() > 1 || dropped.first().is_some_and(|path| path.is_dir())) {
+            self.run_batch(&dropped);
+            return;
+        }
+
+        let Some(path) = dropped.first() else {
+            return;
+        };
+        let file_path = path.to_string_lossy().to_string();
+        match self.current_tab {
+            AppTab::Replacer => {
+                self.replacer.file_path = file_path;
+                self.replacer.status = "File loaded via drag & drop".to_string();
+                self.replacer.backup_filename.clear();
+                self.replacer.read_current_steamid();
+            }
+            AppTab::Remover => {
+                self.remover.file_path = file_path;
+                self.remover.status = "File loaded via drag & drop".to_string();
+                self.remover.backup_filename.clear();
+                self.remover.read_current_steamid();
+            }
+            AppTab::Editor => {
+                self.editor.file_path = file_path;
+                self.editor.backup_filename.clear();
+                self.editor.load();
+            }
+            AppTab::Backups => {
+                self.backups.file_path = file_path;
+                self.backups.status = "File loaded via drag & drop".to_string();
+                self.backups.backup_filename.clear();
+                self.backups.refresh();
+            }
+            AppTab::Slots => {
+                self.slots.status = "Folder loaded via drag & drop".to_string();
+                self.slots.backup_filename.clear();
+                self.slots.open(&file_path);
+            }
+            AppTab::Anonymize => {
+                self.anonymizer.file_path = file_path;
+                self.anonymizer.status = "File loaded via drag & drop".to_string();
+                self.anonymizer.backup_filename.clear();
+                self.anonymizer.load();
+            }
+            AppTab::Hex => {
+                self.hex.file_path = file_path;
+                self.hex.load();
+            }
+        }
+    }
+
+    /// Replaces or removes the SteamID in every dropped save, depending on the
+    /// tab. The batch runs in the background; see [`Self::poll_batch`].
+    fn run_batch(&mut self, paths: &[PathBuf]) {
+        let operation = match self.current_tab {
+            AppTab::Replacer => match steamid::parse(&self.replacer.new_steamid) {
+                Ok(id) => BatchOperation::Replace {
+                    steamid: id.to_string(),
+                    include_lookalikes: self.replacer.include_lookalikes,
+                },
+                Err(_) => {
+                    self.replacer.status = "(!) Enter a valid new SteamID before processing several saves".to_string();
+                    self.replacer.backup_filename.clear();
+                    return;
                 }
+            },
+            AppTab::Remover => BatchOperation::Remove,
+            AppTab::Editor | AppTab::Backups | AppTab::Slots | AppTab::Anonymize | AppTab::Hex => return,
+        };
+        let (tab_status, backup_filename) = match self.current_tab {
+            AppTab::Replacer => (&mut self.replacer.status, &mut self.replacer.backup_filename),
+            _ => (&mut self.remover.status, &mut self.remover.backup_filename),
+        };
+        backup_filename.clear();
+        if self.batch.is_running() {
+            *tab_status = "(!) Wait for the running batch to finish".to_string();
+            return;
+        }
+        *tab_status = "Processing saves...".to_string();
+
+        let options = BatchOptions {
+            operation,
+            backup: Some(self.backups.store()),
+            dry_run: false,
+            jobs: None,
+        };
+        self.batch_tab = self.current_tab;
+        self.batch.start(paths, options);
+    }
+
+    /// Picks up progress of the running batch and, once it is done, reports
+    /// the outcome on the tab that started it.
+    fn poll_batch(&mut self, ctx: &egui::Context) {
+        if !self.batch.is_running() {
+            return;
+        }
+        let Some(outcome) = self.batch.poll() else {
+            ctx.request_repaint_after(std::time::Duration::from_millis(100));
+            return;
+        };
+
+        let status = match outcome {
+            Ok(summary) => BatchView::status(&summary),
+            Err(e) => format!("❌ Error: {}", e),
+        };
+        match self.batch_tab {
+            AppTab::Replacer => {
+                self.replacer.status = status;
+                self.replacer.read_current_steamid();
             }
-        });
+            _ => {
+                self.remover.status = status;
+                self.remover.read_current_steamid();
+            }
+        }
     }
 
     fn show_menu_bar(&mut self, ctx: &egui::Context) {
@@ -104,10 +236,47 @@ impl SteamIDApp {
                                     self.remover.backup_filename.clear();
                                     self.remover.read_current_steamid();
                                 }
+                                AppTab::Editor => {
+                                    self.editor.file_path = file_path;
+                                    self.editor.backup_filename.clear();
+                                    self.editor.load();
+                                }
+                                AppTab::Backups => {
+                                    self.backups.file_path = file_path;
+                                    self.backups.status = status;
+                                    self.backups.backup_filename.clear();
+                                    self.backups.refresh();
+                                }
+                                AppTab::Slots => {
+                                    self.slots.status.clear();
+                                    self.slots.backup_filename.clear();
+                                    self.slots.open(&file_path);
+                                }
+                                AppTab::Anonymize => {
+                                    self.anonymizer.file_path = file_path;
+                                    self.anonymizer.status = status;
+                                    self.anonymizer.backup_filename.clear();
+                                    self.anonymizer.load();
+                                }
+                                AppTab::Hex => {
+                                    self.hex.file_path = file_path;
+                                    self.hex.load();
+                                }
                             }
                         }
                         ui.close_menu();
                     }
+                    let is_edit_tab = matches!(self.current_tab, AppTab::Replacer | AppTab::Remover);
+                    if ui
+                        .add_enabled(is_edit_tab, egui::Button::new("📚 Process Folder..."))
+                        .on_hover_text("Replace or remove the SteamID in every save in a folder")
+                        .clicked()
+                    {
+                        if let Some(dir) = utils::browse_folder() {
+                            self.run_batch(&[PathBuf::from(dir)]);
+                        }
+                        ui.close_menu();
+                    }
                     ui.separator();
                     if ui.button("❌ Exit").clicked() {
                         ctx.send_viewport_cmd(egui::ViewportCommand::Close);
@@ -125,67 +294,817 @@ impl SteamIDApp {
                 });
             });
         });
-    }
+    }
+
+    fn show_main_content(&mut self, ctx: &egui::Context) {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            self.show_header(ui);
+            self.show_tab_selector(ui);
+            
+            match self.current_tab {
+                AppTab::Replacer => self.show_replacer_tab(ui),
+                AppTab::Remover => self.show_remover_tab(ui),
+                AppTab::Editor => self.show_editor_tab(ui),
+                AppTab::Backups => self.show_backups_tab(ui),
+                AppTab::Slots => self.show_slots_tab(ui),
+                AppTab::Anonymize => self.show_anonymize_tab(ui),
+                AppTab::Hex => self.show_hex_tab(ui),
+            }
+        });
+    }
+
+    fn show_header(&self, ui: &mut egui::Ui) {
+        ui.vertical_centered(|ui| {
+            ui.add_space(15.0);
+            ui.horizontal(|ui| {
+                ui.add_space(ui.available_width() / 2.0 - 140.0);
+                if let Some(tex) = &self.icon_texture {
+                    ui.image((tex.id(), egui::vec2(24.0, 24.0)));
+                    ui.add_space(8.0);
+                }
+                ui.heading("Stellar Blade SteamID Tool");
+            });
+            ui.add_space(8.0);
+            ui.label("Replace or remove SteamID in Stellar Blade save files");
+            ui.add_space(15.0);
+        });
+    }
+
+    fn show_tab_selector(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal_wrapped(|ui| {
+            let previous_tab = self.current_tab;
+            ui.selectable_value(&mut self.current_tab, AppTab::Replacer, "🔄 Replacer");
+            ui.selectable_value(&mut self.current_tab, AppTab::Remover, "❌ Remover");
+            ui.selectable_value(&mut self.current_tab, AppTab::Editor, "✏ Editor");
+            ui.selectable_value(&mut self.current_tab, AppTab::Backups, "🗄 Backups");
+            ui.selectable_value(&mut self.current_tab, AppTab::Slots, "💾 Slots");
+            ui.selectable_value(&mut self.current_tab, AppTab::Anonymize, "🕶 Anonymize");
+            ui.selectable_value(&mut self.current_tab, AppTab::Hex, "🔢 Hex");
+            if self.current_tab != previous_tab {
+                self.on_tab_switched(previous_tab);
+            }
+        });
+        ui.add_space(10.0);
+    }
+
+    /// Keeps the tabs in sync: the Backups and Slots tabs follow the file of
+    /// the tab the user came from, and the edit tabs re-read their file in case
+    /// a backup was restored or a slot changed.
+    fn on_tab_switched(&mut self, previous_tab: AppTab) {
+        let file_path = match previous_tab {
+            AppTab::Replacer => self.replacer.file_path.clone(),
+            AppTab::Remover => self.remover.file_path.clone(),
+            AppTab::Editor => self.editor.file_path.clone(),
+            AppTab::Backups => self.backups.file_path.clone(),
+            AppTab::Slots => self
+                .slots
+                .selected_slot()
+                .map(|slot| slot.path.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            AppTab::Anonymize => self.anonymizer.file_path.clone(),
+            AppTab::Hex => self.hex.file_path.clone(),
+        };
+
+        match self.current_tab {
+            AppTab::Backups => {
+                if !file_path.is_empty() {
+                    self.backups.file_path = file_path;
+                }
+                self.backups.refresh();
+            }
+            AppTab::Slots => {
+                self.slots.detect_dir();
+                if file_path.is_empty() {
+                    self.slots.refresh();
+                } else {
+                    self.slots.open(&file_path);
+                }
+            }
+            AppTab::Anonymize => {
+                if !file_path.is_empty() && file_path != self.anonymizer.file_path {
+                    self.anonymizer.file_path = file_path;
+                    self.anonymizer.load();
+                } else {
+                    self.anonymizer.preview();
+                }
+            }
+            AppTab::Editor => {
+                if !file_path.is_empty() && file_path != self.editor.file_path {
+                    self.editor.file_path = file_path;
+                    self.editor.load();
+                } else if self.editor.staged.is_empty() {
+                    self.editor.load();
+                }
+            }
+            AppTab::Hex => {
+                if !file_path.is_empty() && file_path != self.hex.file_path {
+                    self.hex.file_path = file_path;
+                    self.hex.load();
+                } else if self.hex.staged.edits.is_empty() {
+                    self.hex.load();
+                }
+            }
+            AppTab::Replacer => self.replacer.read_current_steamid(),
+            AppTab::Remover => self.remover.read_current_steamid(),
+        }
+    }
+
+    fn show_replacer_tab(&mut self, ui: &mut egui::Ui) {
+        self.show_file_selection(ui, true);
+        ui.add_space(8.0);
+        self.show_new_steamid_input(ui);
+        ui.add_space(8.0);
+        self.show_demo_transfer_option(ui, true);
+        ui.add_space(12.0);
+        self.show_replace_button(ui);
+        ui.add_space(12.0);
+        let warning = utils::inconsistency_warning(&self.replacer.steamid_groups);
+        let saved_file = Some(self.replacer.file_path.as_str());
+        if self.show_status_section(ui, &self.replacer.status, &self.replacer.backup_filename, saved_file, warning.as_deref()) {
+            self.compare_with_backup(&self.replacer.file_path.clone(), &self.replacer.backup_filename.clone());
+        }
+    }
+
+    fn show_remover_tab(&mut self, ui: &mut egui::Ui) {
+        self.show_file_selection(ui, false);
+        ui.add_space(8.0);
+        self.show_remover_info(ui);
+        ui.add_space(8.0);
+        self.show_demo_transfer_option(ui, false);
+        ui.add_space(12.0);
+        self.show_remove_button(ui);
+        ui.add_space(12.0);
+        let warning = utils::inconsistency_warning(&self.remover.steamid_groups);
+        let saved_file = Some(self.remover.file_path.as_str());
+        if self.show_status_section(ui, &self.remover.status, &self.remover.backup_filename, saved_file, warning.as_deref()) {
+            self.compare_with_backup(&self.remover.file_path.clone(), &self.remover.backup_filename.clone());
+        }
+    }
+
+    fn show_editor_tab(&mut self, ui: &mut egui::Ui) {
+        ui.group(|ui| {
+            ui.vertical(|ui| {
+                ui.horizontal(|ui| {
+                    ui.label("File Path:");
+                    let text_width = ui.available_width() - 80.0;
+                    let response = ui.add_sized(
+                        [text_width, 20.0],
+                        egui::TextEdit::singleline(&mut self.editor.file_path)
+                            .hint_text("Drag & drop a file or click Browse..."),
+                    );
+                    if response.lost_focus() {
+                        self.editor.load();
+                    }
+                    if ui.button("📂 Browse").clicked()
+                        && let Some((file_path, _)) = utils::browse_file()
+                    {
+                        self.editor.file_path = file_path;
+                        self.editor.load();
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label("🔎 Filter:");
+                    let response = ui.add_sized(
+                        [ui.available_width() - 190.0, 20.0],
+                        egui::TextEdit::singleline(&mut self.editor.filter).hint_text("Property name"),
+                    );
+                    if response.changed() {
+                        self.editor.apply_filter();
+                    }
+                    ui.add_enabled_ui(self.editor.save.is_some(), |ui| {
+                        if ui.button("📤 Export JSON...").on_hover_text("Write the whole property tree as JSON").clicked()
+                            && let Some(path) = rfd::FileDialog::new()
+                                .add_filter("JSON", &["json"])
+                                .set_file_name(json_file_name(&self.editor.file_path))
+                                .save_file()
+                        {
+                            self.editor.export_json(&path);
+                        }
+                        if ui
+                            .button("📥 Import...")
+                            .on_hover_text("Replace the save with an edited JSON export, after backing it up")
+                            .clicked()
+                            && let Some(path) = rfd::FileDialog::new().add_filter("JSON", &["json"]).pick_file()
+                        {
+                            self.editor.import_json(&path, self.backups.store());
+                        }
+                    });
+                });
+                let staged = self.editor.staged.len();
+                let invalid = self.editor.staged.keys().any(|path| self.editor.check(path).is_some());
+                ui.add_enabled_ui(staged > 0, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(format!("{} staged value(s):", staged));
+                        if ui
+                            .add_enabled(!invalid, egui::Button::new("💾 Apply"))
+                            .on_hover_text("Write the values after backing up the save")
+                            .on_disabled_hover_text("Fix the values marked ⚠ first")
+                            .clicked()
+                        {
+                            self.editor.handle_apply(self.backups.store());
+                        }
+                        if ui.button("↺ Discard").clicked() {
+                            self.editor.discard();
+                        }
+                    });
+                });
+            });
+        });
+        ui.add_space(4.0);
+
+        let mut changes = Vec::new();
+        let expand = std::mem::take(&mut self.editor.expand);
+        ui.group(|ui| {
+            egui::ScrollArea::vertical()
+                .id_source("property_tree")
+                .max_height(ui.available_height() - 24.0)
+                .auto_shrink([false, false])
+                .show(ui, |ui| {
+                    if let Some(save) = &self.editor.save {
+                        property_tree(ui, &self.editor, &save.properties, "", expand, &mut changes);
+                    }
+                });
+        });
+        for (path, text) in changes {
+            self.editor.stage(&path, text);
+        }
+
+        let color = if self.editor.status.contains("❌") {
+            egui::Color32::from_rgb(220, 80, 80)
+        } else if self.editor.status.contains("✅") {
+            egui::Color32::from_rgb(80, 200, 120)
+        } else if self.editor.status.contains("(!)") {
+            egui::Color32::from_rgb(255, 165, 0)
+        } else {
+            egui::Color32::LIGHT_GRAY
+        };
+        if self.editor.backup_filename.is_empty() {
+            ui.colored_label(color, &self.editor.status);
+        } else {
+            ui.colored_label(color, format!("{}  Backup saved as: {}", self.editor.status, self.editor.backup_filename));
+        }
+    }
+
+    fn show_backups_tab(&mut self, ui: &mut egui::Ui) {
+        ui.group(|ui| {
+            ui.vertical(|ui| {
+                ui.label("📁 Save File");
+                ui.horizontal(|ui| {
+                    ui.label("File Path:");
+                    let text_width = ui.available_width() - 80.0;
+                    let response = ui.add_sized(
+                        [text_width, 20.0],
+                        egui::TextEdit::singleline(&mut self.backups.file_path)
+                            .hint_text("Drag & drop a file or click Browse..."),
+                    );
+                    if response.changed() {
+                        self.backups.status.clear();
+                        self.backups.backup_filename.clear();
+                        self.backups.refresh();
+                    }
+                    if ui.button("📂 Browse").clicked()
+                        && let Some((file_path, status)) = utils::browse_file()
+                    {
+                        self.backups.file_path = file_path;
+                        self.backups.status = status;
+                        self.backups.backup_filename.clear();
+                        self.backups.refresh();
+                    }
+                });
+            });
+        });
+        ui.add_space(8.0);
+
+        ui.group(|ui| {
+            ui.vertical(|ui| {
+                ui.label("🗄 Backup Settings");
+                ui.horizontal(|ui| {
+                    ui.label("Folder:");
+                    let text_width = ui.available_width() - 80.0;
+                    let response = ui.add_sized(
+                        [text_width, 20.0],
+                        egui::TextEdit::singleline(&mut self.backups.backup_dir)
+                            .hint_text("Default: SteamIDBackups next to the save"),
+                    );
+                    if response.changed() {
+                        self.backups.refresh();
+                    }
+                    if ui.button("📂 Browse").clicked()
+                        && let Some(dir) = rfd::FileDialog::new().pick_folder()
+                    {
+                        self.backups.backup_dir = dir.to_string_lossy().to_string();
+                        self.backups.refresh();
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Keep last");
+                    ui.add(egui::DragValue::new(&mut self.backups.keep_last).range(0..=999));
+                    ui.label("backups,");
+                    ui.add(egui::DragValue::new(&mut self.backups.max_age_days).range(0..=3650));
+                    ui.label("days (0 = no limit)");
+                    if ui.button("🧹 Apply now").clicked() {
+                        self.backups.handle_prune();
+                    }
+                });
+            });
+        });
+        ui.add_space(8.0);
+
+        ui.group(|ui| {
+            ui.set_width(ui.available_width());
+            ui.vertical(|ui| {
+                ui.label(format!("🕑 History ({})", self.backups.entries.len()));
+                if self.backups.entries.is_empty() {
+                    ui.colored_label(egui::Color32::LIGHT_GRAY, "No backups of this save yet");
+                }
+
+                let mut restore = None;
+                let mut compare = None;
+                egui::ScrollArea::vertical().max_height(130.0).show(ui, |ui| {
+                    for (index, (entry, steamid)) in self.backups.entries.iter().enumerate() {
+                        ui.horizontal(|ui| {
+                            if ui.small_button("↩ Restore").clicked() {
+                                restore = Some(index);
+                            }
+                            if ui.small_button("🔍").on_hover_text("Compare with the current save").clicked() {
+                                compare = Some(entry.path.clone());
+                            }
+                            ui.label(steamid_replacer::backup::format_time(entry.timestamp));
+                            match steamid {
+                                Some(steamid) => ui.colored_label(egui::Color32::LIGHT_BLUE, steamid),
+                                None => ui.colored_label(egui::Color32::LIGHT_GRAY, "universal"),
+                            };
+                            ui.colored_label(egui::Color32::LIGHT_GRAY, &entry.hash);
+                        });
+                    }
+                });
+                if let Some(index) = restore {
+                    self.backups.handle_restore(index);
+                }
+                if let Some(backup) = compare {
+                    self.diff.open(&backup, Path::new(&self.backups.file_path));
+                }
+            });
+        });
+        ui.add_space(8.0);
+
+        ui.group(|ui| {
+            ui.set_width(ui.available_width());
+            ui.vertical(|ui| {
+                ui.horizontal(|ui| {
+                    ui.label(format!("📝 Recorded Operations ({})", self.backups.operations.len()));
+                    if !self.backups.operations.is_empty() && ui.small_button("↶ Undo Last").clicked() {
+                        self.backups.handle_undo();
+                    }
+                });
+                if self.backups.operations.is_empty() {
+                    ui.colored_label(egui::Color32::LIGHT_GRAY, "No operations recorded for this save");
+                }
+
+                egui::ScrollArea::vertical()
+                    .id_source("recorded_operations")
+                    .max_height(90.0)
+                    .show(ui, |ui| {
+                        for entry in self.backups.operations.iter().rev() {
+                            ui.horizontal(|ui| {
+                                ui.label(steamid_replacer::backup::format_time(entry.timestamp));
+                                ui.colored_label(egui::Color32::LIGHT_BLUE, entry.summary());
+                                ui.colored_label(egui::Color32::LIGHT_GRAY, entry.sha256_after.get(..12).unwrap_or_default());
+                            });
+                        }
+                    });
+            });
+        });
+        ui.add_space(12.0);
+        let saved_file = Some(self.backups.file_path.as_str());
+        if self.show_status_section(ui, &self.backups.status, &self.backups.backup_filename, saved_file, None) {
+            self.compare_with_backup(&self.backups.file_path.clone(), &self.backups.backup_filename.clone());
+        }
+    }
+
+    fn show_slots_tab(&mut self, ui: &mut egui::Ui) {
+        ui.group(|ui| {
+            ui.vertical(|ui| {
+                ui.label("📁 SaveGames Folder");
+                ui.horizontal(|ui| {
+                    let text_width = ui.available_width() - 110.0;
+                    let response = ui.add_sized(
+                        [text_width, 20.0],
+                        egui::TextEdit::singleline(&mut self.slots.dir)
+                            .hint_text("Drag & drop a save or click Browse..."),
+                    );
+                    if response.changed() {
+                        self.slots.status.clear();
+                        self.slots.backup_filename.clear();
+                        self.slots.refresh();
+                    }
+                    if ui.button("📂 Browse").clicked()
+                        && let Some(dir) = utils::browse_folder()
+                    {
+                        self.slots.dir = dir;
+                        self.slots.status.clear();
+                        self.slots.backup_filename.clear();
+                        self.slots.refresh();
+                    }
+                    if ui.button("⟳").on_hover_text("Reload the folder").clicked() {
+                        self.slots.refresh();
+                    }
+                });
+                if self.slots.detected.len() > 1 {
+                    let mut chosen = None;
+                    ui.horizontal(|ui| {
+                        ui.label("Detected:");
+                        egui::ComboBox::from_id_source("detected_save_dirs")
+                            .selected_text("Choose a SaveGames folder")
+                            .width(ui.available_width())
+                            .show_ui(ui, |ui| {
+                                for dir in &self.slots.detected {
+                                    if ui.selectable_label(false, dir.to_string_lossy()).clicked() {
+                                        chosen = Some(dir.to_string_lossy().to_string());
+                                    }
+                                }
+                            });
+                    });
+                    if let Some(dir) = chosen {
+                        self.slots.dir = dir;
+                        self.slots.status.clear();
+                        self.slots.backup_filename.clear();
+                        self.slots.refresh();
+                    }
+                }
+            });
+        });
+        ui.add_space(8.0);
+
+        ui.group(|ui| {
+            ui.set_width(ui.available_width());
+            ui.vertical(|ui| {
+                ui.label(format!("💾 Slots ({})", self.slots.slots.len()));
+                if self.slots.slots.is_empty() {
+                    ui.colored_label(egui::Color32::LIGHT_GRAY, "No .sav files in this folder");
+                }
+
+                let mut clicked = None;
+                egui::ScrollArea::vertical().max_height(120.0).show(ui, |ui| {
+                    egui::Grid::new("slots_grid").striped(true).show(ui, |ui| {
+                        for (index, slot) in self.slots.slots.iter().enumerate() {
+                            if ui.selectable_label(self.slots.selected == Some(index), slot.name()).clicked() {
+                                clicked = Some(index);
+                            }
+                            match &slot.steamid {
+                                Some(steamid) => ui.colored_label(egui::Color32::LIGHT_BLUE, steamid),
+                                None => ui.colored_label(egui::Color32::LIGHT_GRAY, "universal"),
+                            };
+                            ui.label(format!("{} KB", slot.size.div_ceil(1024)));
+                            ui.label(slot.modified.map(steamid_replacer::backup::format_time).unwrap_or_default());
+                            match &slot.demo {
+                                Some(evidence) => ui.colored_label(egui::Color32::from_rgb(255, 165, 0), "demo")
+                                    .on_hover_text(evidence.to_string()),
+                                None => ui.label("full"),
+                            };
+                            match self.slots.cloud.get(index) {
+                                Some(status @ CloudStatus::InSync) => ui.label("☁").on_hover_text(status.to_string()),
+                                Some(status @ CloudStatus::Changed(_)) => ui
+                                    .colored_label(egui::Color32::from_rgb(255, 165, 0), "☁ ⚠")
+                                    .on_hover_text(status.to_string()),
+                                _ => ui.label(""),
+                            };
+                            ui.end_row();
+                        }
+                    });
+                });
+                if let Some(index) = clicked {
+                    self.slots.select(index);
+                }
+            });
+        });
+        ui.add_space(8.0);
+
+        ui.group(|ui| {
+            ui.set_width(ui.available_width());
+            let Some(selected) = self.slots.selected_slot().map(|slot| slot.name()) else {
+                ui.colored_label(egui::Color32::LIGHT_GRAY, "Select a slot to copy, rename, swap or delete it");
+                return;
+            };
+            ui.vertical(|ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Name:");
+                    ui.add_sized(
+                        [ui.available_width() - 190.0, 20.0],
+                        egui::TextEdit::singleline(&mut self.slots.new_name),
+                    );
+                    if ui.button("📄 Duplicate").clicked() {
+                        self.slots.handle_duplicate(self.backups.store());
+                    }
+                    if ui.button("✏ Rename").clicked() {
+                        self.slots.handle_rename();
+                    }
+                });
+                ui.checkbox(&mut self.slots.overwrite, "⚠ Duplicate may replace an existing slot (it is backed up first)");
+
+                ui.horizontal(|ui| {
+                    ui.label(format!("Swap {} with", selected));
+                    let selected_text = self
+                        .slots
+                        .swap_with
+                        .and_then(|i| self.slots.slots.get(i))
+                        .map(|slot| slot.name())
+                        .unwrap_or_default();
+                    egui::ComboBox::from_id_source("swap_with")
+                        .selected_text(selected_text)
+                        .show_ui(ui, |ui| {
+                            for (index, slot) in self.slots.slots.iter().enumerate() {
+                                if Some(index) != self.slots.selected {
+                                    ui.selectable_value(&mut self.slots.swap_with, Some(index), slot.name());
+                                }
+                            }
+                        });
+                    if ui.add_enabled(self.slots.swap_with.is_some(), egui::Button::new("🔀 Swap")).clicked() {
+                        self.slots.handle_swap(self.backups.store());
+                    }
+                    if ui.button("🗑 Delete").on_hover_text("Moves the slot into the backup folder").clicked() {
+                        self.slots.handle_delete(self.backups.store());
+                    }
+                });
+
+                let cloud_changed = self
+                    .slots
+                    .selected
+                    .and_then(|i| self.slots.cloud.get(i))
+                    .is_some_and(|status| matches!(status, CloudStatus::Changed(_)));
+                if cloud_changed {
+                    ui.horizontal(|ui| {
+                        ui.colored_label(
+                            egui::Color32::from_rgb(255, 165, 0),
+                            "(!) Steam Cloud may restore its older copy",
+                        );
+                        if ui
+                            .button("☁ Update Cloud Record")
+                            .on_hover_text("Close Steam first. Makes Steam upload this save on next launch.")
+                            .clicked()
+                        {
+                            self.slots.handle_update_cloud(self.backups.store());
+                        }
+                    });
+                }
+            });
+        });
+        ui.add_space(12.0);
+        self.show_status_section(ui, &self.slots.status, &self.slots.backup_filename, None, None);
+    }
+
+    fn show_anonymize_tab(&mut self, ui: &mut egui::Ui) {
+        ui.group(|ui| {
+            ui.vertical(|ui| {
+                ui.label("📁 File Selection");
+                ui.horizontal(|ui| {
+                    ui.label("File Path:");
+                    let text_width = ui.available_width() - 80.0;
+                    let response = ui.add_sized(
+                        [text_width, 20.0],
+                        egui::TextEdit::singleline(&mut self.anonymizer.file_path)
+                            .hint_text("Drag & drop a file or click Browse..."),
+                    );
+                    if response.changed() {
+                        self.anonymizer.status.clear();
+                        self.anonymizer.backup_filename.clear();
+                        self.anonymizer.load();
+                    }
+                    if ui.button("📂 Browse").clicked()
+                        && let Some((file_path, status)) = utils::browse_file()
+                    {
+                        self.anonymizer.file_path = file_path;
+                        self.anonymizer.status = status;
+                        self.anonymizer.backup_filename.clear();
+                        self.anonymizer.load();
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Also scrub:");
+                    let response = ui.add_sized(
+                        [ui.available_width(), 20.0],
+                        egui::TextEdit::singleline(&mut self.anonymizer.extra)
+                            .hint_text("Other names, comma separated"),
+                    );
+                    if response.lost_focus() {
+                        self.anonymizer.preview();
+                    }
+                });
+            });
+        });
+        ui.add_space(8.0);
+
+        ui.group(|ui| {
+            ui.set_width(ui.available_width());
+            ui.vertical(|ui| {
+                ui.label(format!("🕶 Identifiers Found ({})", self.anonymizer.scrubbed.len()));
+                if self.anonymizer.scrubbed.is_empty() {
+                    ui.colored_label(egui::Color32::LIGHT_GRAY, "Nothing account-identifying found");
+                }
+                egui::ScrollArea::vertical().max_height(120.0).show(ui, |ui| {
+                    egui::Grid::new("anonymize_grid").striped(true).show(ui, |ui| {
+                        for scrubbed in &self.anonymizer.scrubbed {
+                            ui.monospace(format!("0x{:08X}", scrubbed.offset));
+                            ui.label(scrubbed.kind.to_string());
+                            ui.colored_label(egui::Color32::LIGHT_BLUE, &scrubbed.original)
+                                .on_hover_text(scrubbed.path.as_deref().unwrap_or(if scrubbed.wide { "UTF-16" } else { "UTF-8" }));
+                            ui.label(format!("→ {}", scrubbed.placeholder));
+                            ui.end_row();
+                        }
+                    });
+                });
+            });
+        });
+        ui.add_space(8.0);
+
+        ui.group(|ui| {
+            ui.set_width(ui.available_width());
+            ui.vertical(|ui| {
+                ui.checkbox(&mut self.anonymizer.save_copy, "Save an anonymized copy (keep this save as it is)");
+                ui.add_enabled_ui(self.anonymizer.save_copy, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Copy:");
+                        ui.add_sized(
+                            [ui.available_width() - 80.0, 20.0],
+                            egui::TextEdit::singleline(&mut self.anonymizer.output_path),
+                        );
+                        if ui.button("📂 Browse").clicked()
+                            && let Some(path) = rfd::FileDialog::new().add_filter("Save Files", &["sav"]).save_file()
+                        {
+                            self.anonymizer.output_path = path.to_string_lossy().to_string();
+                        }
+                    });
+                    ui.checkbox(&mut self.anonymizer.overwrite, "⚠ Replace the copy if it exists (it is backed up first)");
+                });
+            });
+        });
+        ui.add_space(12.0);
+
+        ui.vertical_centered(|ui| {
+            let button = egui::Button::new("🕶 Anonymize").min_size(egui::vec2(160.0, 32.0));
+            if ui.add_enabled(!self.anonymizer.file_path.is_empty(), button).clicked() {
+                self.anonymizer.handle_anonymize(self.backups.store());
+            }
+        });
+        ui.add_space(12.0);
+        // With a copy, the backup is of the copy it replaced.
+        let saved_file = if self.anonymizer.save_copy {
+            self.anonymizer.output_path.clone()
+        } else {
+            self.anonymizer.file_path.clone()
+        };
+        if self.show_status_section(ui, &self.anonymizer.status, &self.anonymizer.backup_filename, Some(&saved_file), None) {
+            self.compare_with_backup(&saved_file, &self.anonymizer.backup_filename.clone());
+        }
+    }
+
+    fn show_hex_tab(&mut self, ui: &mut egui::Ui) {
+        ui.group(|ui| {
+            ui.vertical(|ui| {
+                ui.horizontal(|ui| {
+                    ui.label("File Path:");
+                    let text_width = ui.available_width() - 80.0;
+                    let response = ui.add_sized(
+                        [text_width, 20.0],
+                        egui::TextEdit::singleline(&mut self.hex.file_path)
+                            .hint_text("Drag & drop a file or click Browse..."),
+                    );
+                    if response.lost_focus() {
+                        self.hex.load();
+                    }
+                    if ui.button("📂 Browse").clicked()
+                        && let Some((file_path, _)) = utils::browse_file()
+                    {
+                        self.hex.file_path = file_path;
+                        self.hex.load();
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Go to:");
+                    let response = ui.add_sized(
+                        [80.0, 20.0],
+                        egui::TextEdit::singleline(&mut self.hex.goto).hint_text("0xC34"),
+                    );
+                    if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                        self.hex.handle_goto();
+                    }
+                    ui.separator();
+                    egui::ComboBox::from_id_source("hex_search_kind")
+                        .width(80.0)
+                        .selected_text(self.hex.search_kind.to_string())
+                        .show_ui(ui, |ui| {
+                            for kind in SearchKind::ALL {
+                                ui.selectable_value(&mut self.hex.search_kind, kind, kind.to_string());
+                            }
+                        });
+                    let response = ui.add_sized(
+                        [ui.available_width() - 60.0, 20.0],
+                        egui::TextEdit::singleline(&mut self.hex.search).hint_text("Search"),
+                    );
+                    if response.changed() {
+                        self.hex.matches.clear();
+                    }
+                    if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                        self.hex.step_match(true);
+                    }
+                    if ui.small_button("◀").on_hover_text("Previous match").clicked() {
+                        self.hex.step_match(false);
+                    }
+                    if ui.small_button("▶").on_hover_text("Next match").clicked() {
+                        self.hex.step_match(true);
+                    }
+                });
+                ui.horizontal(|ui| {
+                    for marker in [Marker::SteamId, Marker::StrProperty, Marker::NoneTag] {
+                        ui.label(egui::RichText::new(marker.to_string()).small().background_color(marker_color(marker)));
+                    }
+                    ui.label(egui::RichText::new("match").small().background_color(MATCH_COLOR));
+                    ui.label(egui::RichText::new("edited").small().background_color(EDIT_COLOR));
+                    ui.label(egui::RichText::new("selected").small().background_color(CURSOR_COLOR));
+                });
+                ui.horizontal(|ui| {
+                    ui.label("✏ Write at cursor:");
+                    let response = ui.add_sized(
+                        [110.0, 20.0],
+                        egui::TextEdit::singleline(&mut self.hex.edit_bytes).hint_text("Hex, e.g. 16 00"),
+                    );
+                    let entered = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                    if ui.add_enabled(self.hex.cursor.is_some(), egui::Button::new("➕ Stage")).clicked() || entered {
+                        self.hex.stage_edit();
+                    }
+                    if ui.button("📥 Load Patch...").clicked()
+                        && let Some(path) = rfd::FileDialog::new().add_filter("Patch", &["json"]).pick_file()
+                    {
+                        self.hex.load_patch(&path);
+                    }
+                });
+                let staged = self.hex.staged.edits.len();
+                ui.add_enabled_ui(staged > 0, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(format!("{} staged edit(s):", staged));
+                        if ui.button("💾 Apply").on_hover_text("Write the edits after backing up the save").clicked() {
+                            self.hex.handle_apply(self.backups.store());
+                        }
+                        if ui.button("↺ Discard").clicked() {
+                            self.hex.discard_edits();
+                        }
+                        if ui.button("📤 Save Patch...").clicked()
+                            && let Some(path) = rfd::FileDialog::new()
+                                .add_filter("Patch", &["json"])
+                                .set_file_name("patch.json")
+                                .save_file()
+                        {
+                            self.hex.save_patch(&path);
+                        }
+                    });
+                });
+            });
+        });
+        ui.add_space(4.0);
 
-    fn show_main_content(&mut self, ctx: &egui::Context) {
-        egui::CentralPanel::default().show(ctx, |ui| {
-            self.show_header(ui);
-            self.show_tab_selector(ui);
-            
-            match self.current_tab {
-                AppTab::Replacer => self.show_replacer_tab(ui),
-                AppTab::Remover => self.show_remover_tab(ui),
-            }
-        });
-    }
+        let font = egui::FontId::monospace(11.0);
+        let (row_height, char_width) = ui.fonts(|fonts| (fonts.row_height(&font), fonts.glyph_width(&font, '0')));
+        let row_count = self.hex.edited.len().div_ceil(hex_viewer::ROW_WIDTH);
+        let mut scroll = egui::ScrollArea::vertical()
+            .id_source("hex_view")
+            .max_height(ui.available_height() - 24.0)
+            .auto_shrink([false, false]);
+        if let Some(row) = self.hex.scroll_to_row.take() {
+            scroll = scroll.vertical_scroll_offset(row as f32 * (row_height + ui.spacing().item_spacing.y));
+        }
 
-    fn show_header(&self, ui: &mut egui::Ui) {
-        ui.vertical_centered(|ui| {
-            ui.add_space(15.0);
-            ui.horizontal(|ui| {
-                ui.add_space(ui.available_width() / 2.0 - 140.0);
-                if let Some(tex) = &self.icon_texture {
-                    ui.image((tex.id(), egui::vec2(24.0, 24.0)));
-                    ui.add_space(8.0);
+        let mut clicked = None;
+        ui.group(|ui| {
+            scroll.show_rows(ui, row_height, row_count, |ui, rows| {
+                for row in rows {
+                    let job = hex_row_job(&self.hex, row, &font);
+                    let response = ui.add(egui::Label::new(job).extend().sense(egui::Sense::click()));
+                    if response.clicked()
+                        && let Some(pos) = response.interact_pointer_pos()
+                    {
+                        let column = ((pos.x - response.rect.left()) / char_width) as usize;
+                        clicked = hex_column_byte(column).map(|byte| row * hex_viewer::ROW_WIDTH + byte);
+                    }
                 }
-                ui.heading("Stellar Blade SteamID Tool");
             });
-            ui.add_space(8.0);
-            ui.label("Replace or remove SteamID in Stellar Blade save files");
-            ui.add_space(15.0);
-        });
-    }
-
-    fn show_tab_selector(&mut self, ui: &mut egui::Ui) {
-        ui.horizontal(|ui| {
-            ui.selectable_value(&mut self.current_tab, AppTab::Replacer, "🔄 Replacer");
-            ui.selectable_value(&mut self.current_tab, AppTab::Remover, "❌ Remover");
         });
-        ui.add_space(10.0);
-    }
-
-    fn show_replacer_tab(&mut self, ui: &mut egui::Ui) {
-        self.show_file_selection(ui, true);
-        ui.add_space(8.0);
-        self.show_new_steamid_input(ui);
-        ui.add_space(8.0);
-        self.show_demo_transfer_option(ui, true);
-        ui.add_space(12.0);
-        self.show_replace_button(ui);
-        ui.add_space(12.0);
-        self.show_status_section(ui, &self.replacer.status, &self.replacer.backup_filename);
-    }
+        if let Some(offset) = clicked.filter(|&offset| offset < self.hex.edited.len()) {
+            self.hex.cursor = Some(offset);
+            self.hex.status = self.hex.describe(offset);
+        }
 
-    fn show_remover_tab(&mut self, ui: &mut egui::Ui) {
-        self.show_file_selection(ui, false);
-        ui.add_space(8.0);
-        self.show_remover_info(ui);
-        ui.add_space(8.0);
-        self.show_demo_transfer_option(ui, false);
-        ui.add_space(12.0);
-        self.show_remove_button(ui);
-        ui.add_space(12.0);
-        self.show_status_section(ui, &self.remover.status, &self.remover.backup_filename);
+        let color = if self.hex.status.contains("❌") {
+            egui::Color32::from_rgb(220, 80, 80)
+        } else if self.hex.status.contains("(!)") {
+            egui::Color32::from_rgb(255, 165, 0)
+        } else {
+            egui::Color32::LIGHT_GRAY
+        };
+        if self.hex.backup_filename.is_empty() {
+            ui.colored_label(color, &self.hex.status);
+        } else {
+            ui.colored_label(color, format!("{}  Backup saved as: {}", self.hex.status, self.hex.backup_filename));
+        }
     }
 
     fn show_file_selection(&mut self, ui: &mut egui::Ui, is_replacer: bool) {
@@ -220,19 +1139,19 @@ impl SteamIDApp {
                         }
                     }
                     
-                    if ui.button("📂 Browse").clicked() {
-                        if let Some((file_path_new, status)) = utils::browse_file() {
-                            if is_replacer {
-                                self.replacer.file_path = file_path_new;
-                                self.replacer.status = status;
-                                self.replacer.backup_filename.clear();
-                                self.replacer.read_current_steamid();
-                            } else {
-                                self.remover.file_path = file_path_new;
-                                self.remover.status = status;
-                                self.remover.backup_filename.clear();
-                                self.remover.read_current_steamid();
-                            }
+                    if ui.button("📂 Browse").clicked()
+                        && let Some((file_path_new, status)) = utils::browse_file()
+                    {
+                        if is_replacer {
+                            self.replacer.file_path = file_path_new;
+                            self.replacer.status = status;
+                            self.replacer.backup_filename.clear();
+                            self.replacer.read_current_steamid();
+                        } else {
+                            self.remover.file_path = file_path_new;
+                            self.remover.status = status;
+                            self.remover.backup_filename.clear();
+                            self.remover.read_current_steamid();
                         }
                     }
                 });
@@ -249,7 +1168,9 @@ impl SteamIDApp {
                         &self.remover.current_steamid
                     };
                     
-                    if current_steamid.is_empty() {
+                    if is_replacer && self.replacer.universal {
+                        ui.colored_label(egui::Color32::LIGHT_GREEN, "Universal save (the new SteamID will be written back)");
+                    } else if current_steamid.is_empty() {
                         let label = if is_replacer {
                             "No valid file selected"
                         } else {
@@ -259,6 +1180,17 @@ impl SteamIDApp {
                     } else {
                         ui.colored_label(egui::Color32::LIGHT_BLUE, current_steamid);
                     }
+
+                    let (occurrences, show_occurrences) = if is_replacer {
+                        (&self.replacer.occurrences, &mut self.replacer.show_occurrences)
+                    } else {
+                        (&self.remover.occurrences, &mut self.remover.show_occurrences)
+                    };
+                    if !occurrences.is_empty()
+                        && ui.small_button(format!("🔎 Occurrences ({})", occurrences.len())).clicked()
+                    {
+                        *show_occurrences = true;
+                    }
                 });
             });
         });
@@ -274,25 +1206,97 @@ impl SteamIDApp {
                     let response = ui.add_sized(
                         [text_width, 20.0],
                         egui::TextEdit::singleline(&mut self.replacer.new_steamid)
-                            .hint_text("Enter 17-digit SteamID (e.g., 76561198123456789)"),
+                            .hint_text("SteamID64, STEAM_0:1:12345, [U:1:24691] or profile URL"),
                     );
                     if response.changed() {
                         self.replacer.status.clear();
                         self.replacer.backup_filename.clear();
                     }
                 });
-                ui.label("💡 SteamID must be 17 digits starting with '7656'");
+                self.show_account_picker(ui);
+
+                if self.replacer.new_steamid.trim().is_empty() {
+                    ui.label("💡 SteamID64, STEAM_0:Y:Z, [U:1:N], account ID or profile URL");
+                } else {
+                    match steamid::parse(&self.replacer.new_steamid) {
+                        Ok(id) => {
+                            ui.colored_label(
+                                egui::Color32::from_rgb(80, 200, 120),
+                                format!("➡ {}   {}   {}", id, id.steam2(), id.steam3()),
+                            );
+                        }
+                        Err(e) => {
+                            ui.colored_label(egui::Color32::from_rgb(220, 80, 80), format!("❌ {}", e));
+                        }
+                    }
+                }
+
+                if !occurrences::is_consistent(&self.replacer.steamid_groups) {
+                    ui.horizontal_wrapped(|ui| {
+                        ui.label("Normalise all to:");
+                        for group in &self.replacer.steamid_groups {
+                            if ui.small_button(&group.steamid).clicked() {
+                                self.replacer.new_steamid = group.steamid.clone();
+                                self.replacer.status.clear();
+                                self.replacer.backup_filename.clear();
+                            }
+                        }
+                    });
+                }
             });
         });
     }
 
+    fn show_account_picker(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Steam account:");
+            if self.replacer.accounts.is_empty() {
+                ui.colored_label(egui::Color32::LIGHT_GRAY, &self.replacer.accounts_status);
+            } else {
+                let chosen = steamid::parse(&self.replacer.new_steamid).ok();
+                let selected_text = self
+                    .replacer
+                    .accounts
+                    .iter()
+                    .find(|a| Some(a.steamid) == chosen)
+                    .map(|a| a.label())
+                    .unwrap_or_else(|| "Choose a local account...".to_string());
+
+                egui::ComboBox::from_id_source("steam_account")
+                    .selected_text(selected_text)
+                    .width(ui.available_width() - 40.0)
+                    .show_ui(ui, |ui| {
+                        for account in &self.replacer.accounts {
+                            let label = format!("{}  {}", account.label(), account.steamid);
+                            if ui.selectable_label(Some(account.steamid) == chosen, label).clicked() {
+                                self.replacer.new_steamid = account.steamid.to_string();
+                                self.replacer.status.clear();
+                                self.replacer.backup_filename.clear();
+                            }
+                        }
+                    });
+            }
+
+            if ui
+                .small_button("📂")
+                .on_hover_text("Choose the Steam installation folder")
+                .clicked()
+                && let Some(dir) = rfd::FileDialog::new().pick_folder()
+            {
+                self.replacer.steam_dir = Some(dir);
+                self.replacer.load_accounts();
+            }
+        });
+    }
+
     fn show_remover_info(&self, ui: &mut egui::Ui) {
         ui.group(|ui| {
             ui.vertical(|ui| {
                 ui.label("(i) Universal Save Creation");
                 ui.label("This will remove SteamID from the save file, making it work for any Steam account.");
                 ui.add_space(5.0);
-                ui.label("(!) Note: Once removed, the SteamID cannot be recovered from the save file.");
+                ui.label("(!) Note: The old SteamID is not kept in the save file. To bind the save to an");
+                ui.label("account again, open it in the Replacer tab and enter that account's SteamID.");
             });
         });
     }
@@ -300,23 +1304,78 @@ impl SteamIDApp {
     fn show_demo_transfer_option(&mut self, ui: &mut egui::Ui, is_replacer: bool) {
         ui.group(|ui| {
             ui.horizontal_wrapped(|ui| {
-                let transfer_demo_save = if is_replacer {
-                    &mut self.replacer.transfer_demo_save
+                let transfer = if is_replacer {
+                    &mut self.replacer.transfer
                 } else {
-                    &mut self.remover.transfer_demo_save
+                    &mut self.remover.transfer
                 };
-                
-                let response = ui.checkbox(transfer_demo_save, "📋 Transfer Demo Save (removes 'Demo00' from filename)");
-                
-                if is_replacer && response.changed() && *transfer_demo_save {
-                    if !self.replacer.file_path.contains("Demo00") {
-                        self.replacer.status = "❌ Error: File does not contain 'Demo00'. Cannot transfer demo save.".to_string();
-                        self.replacer.show_demo_error = true;
-                    } else {
-                        self.replacer.show_demo_error = false;
+
+                let response = ui.checkbox(&mut transfer.enabled, "📋 Transfer Demo Save to a full-game slot");
+
+                if response.changed() && transfer.enabled {
+                    let is_demo = transfer.is_demo();
+                    let status = if is_replacer { &mut self.replacer.status } else { &mut self.remover.status };
+                    if !is_demo {
+                        *status = "❌ Error: File is not a demo save. Cannot transfer demo save.".to_string();
                     }
+                    if is_replacer {
+                        self.replacer.show_demo_error = !is_demo;
+                    }
+                }
+
+                if is_replacer
+                    && ui
+                        .checkbox(&mut self.replacer.include_lookalikes, "🔢 Also replace look-alike digit runs")
+                        .on_hover_text("Also rewrite 17-digit numbers starting with 7656 that are not a SteamID property")
+                        .changed()
+                {
+                    self.replacer.select_default();
+                }
+            });
+
+            let transfer = if is_replacer { &mut self.replacer.transfer } else { &mut self.remover.transfer };
+            if !transfer.enabled || !transfer.is_demo() {
+                return;
+            }
+
+            if let Some(evidence) = &transfer.evidence {
+                ui.label(format!("Detected demo save: {}", evidence));
+            }
+
+            if transfer.targets.is_empty() {
+                ui.label("(!) No full-game slot found next to this save");
+                return;
+            }
+
+            let describe = |target: &TransferTarget| {
+                let name = files::backup_file_name(&target.path);
+                match (&target.steamid, target.exists) {
+                    (Some(steamid), _) => format!("{} (exists, SteamID {})", name, steamid),
+                    (None, true) => format!("{} (exists)", name),
+                    (None, false) => name,
                 }
+            };
+            let selected_text = transfer.target().map(describe).unwrap_or_default();
+            let mut selected = transfer.selected;
+            ui.horizontal(|ui| {
+                ui.label("Move to:");
+                egui::ComboBox::from_id_source(("transfer_target", is_replacer))
+                    .selected_text(selected_text)
+                    .width(ui.available_width())
+                    .show_ui(ui, |ui| {
+                        for (i, target) in transfer.targets.iter().enumerate() {
+                            ui.selectable_value(&mut selected, i, describe(target));
+                        }
+                    });
             });
+            if selected != transfer.selected {
+                transfer.selected = selected;
+                transfer.overwrite_existing = false;
+            }
+
+            if transfer.target().is_some_and(|target| target.exists) {
+                ui.checkbox(&mut transfer.overwrite_existing, "⚠ Replace existing file (it will be backed up first)");
+            }
         });
     }
 
@@ -324,12 +1383,12 @@ impl SteamIDApp {
         ui.vertical_centered(|ui| {
             let button_enabled = !self.replacer.file_path.is_empty()
                 && !self.replacer.new_steamid.is_empty()
-                && (!self.replacer.transfer_demo_save || self.replacer.file_path.contains("Demo00"));
+                && self.replacer.transfer.is_ready();
             let button = egui::Button::new("🔄 Replace SteamID")
                 .min_size(egui::vec2(160.0, 32.0));
             let response = ui.add_enabled(button_enabled, button);
             if response.clicked() {
-                self.replacer.handle_replace();
+                self.replacer.handle_replace(self.backups.store());
             }
         });
     }
@@ -337,30 +1396,36 @@ impl SteamIDApp {
     fn show_remove_button(&mut self, ui: &mut egui::Ui) {
         ui.vertical_centered(|ui| {
             let button_enabled = !self.remover.file_path.is_empty()
-                && (!self.remover.transfer_demo_save || self.remover.file_path.contains("Demo00"));
+                && self.remover.transfer.is_ready();
             let button = egui::Button::new("❌ Remove SteamID")
                 .min_size(egui::vec2(160.0, 32.0));
             let response = ui.add_enabled(button_enabled, button);
             if response.clicked() {
-                self.remover.handle_remove();
+                self.remover.handle_remove(self.backups.store());
             }
         });
     }
 
-    fn show_status_section(&self, ui: &mut egui::Ui, status: &str, backup_filename: &str) {
+    /// Shows the tab's status and the backup it took. Returns `true` when
+    /// "Compare" is clicked to diff `saved_file` against that backup.
+    fn show_status_section(
+        &self,
+        ui: &mut egui::Ui,
+        status: &str,
+        backup_filename: &str,
+        saved_file: Option<&str>,
+        warning: Option<&str>,
+    ) -> bool {
+        let mut compare = false;
         ui.group(|ui| {
             ui.set_min_height(60.0);
             ui.set_width(ui.available_width());
             ui.vertical_centered(|ui| {
                 ui.heading("🍕 Status");
-                if status.is_empty() {
-                    if !backup_filename.is_empty() {
-                        ui.colored_label(
-                            egui::Color32::LIGHT_GRAY,
-                            format!("Backup saved as: {}", backup_filename),
-                        );
-                    }
-                } else {
+                if let Some(warning) = warning {
+                    ui.colored_label(egui::Color32::from_rgb(255, 165, 0), warning);
+                }
+                if !status.is_empty() {
                     if status.contains("Error") || status.contains("❌") {
                         ui.colored_label(egui::Color32::from_rgb(220, 80, 80), status);
                     } else if status.contains("Successfully") || status.contains("✅") {
@@ -370,15 +1435,29 @@ impl SteamIDApp {
                     } else {
                         ui.colored_label(egui::Color32::LIGHT_BLUE, status);
                     }
-                    if !backup_filename.is_empty() {
-                        ui.colored_label(
-                            egui::Color32::LIGHT_GRAY,
-                            format!("Backup saved as: {}", backup_filename),
-                        );
+                }
+                if !backup_filename.is_empty() {
+                    ui.colored_label(
+                        egui::Color32::LIGHT_GRAY,
+                        format!("Backup saved as: {}", backup_filename),
+                    );
+                    if saved_file.is_some_and(|file| !file.is_empty()) {
+                        compare = ui
+                            .small_button("🔍 Compare")
+                            .on_hover_text("Show what changed since this backup")
+                            .clicked();
                     }
                 }
             });
         });
+        compare
+    }
+
+    /// Opens the compare window for `saved_file` against one of its backups.
+    fn compare_with_backup(&mut self, saved_file: &str, backup_filename: &str) {
+        let saved_file = Path::new(saved_file);
+        let backup = self.backups.store().dir_for(saved_file).join(backup_filename);
+        self.diff.open(&backup, saved_file);
     }
 
     fn show_dialogs(&mut self, ctx: &egui::Context) {
@@ -389,6 +1468,26 @@ impl SteamIDApp {
         if self.show_help {
             self.show_help_dialog(ctx);
         }
+
+        if self.batch.show {
+            show_batch_window(ctx, &mut self.batch);
+        }
+
+        if self.diff.show {
+            show_diff_window(ctx, &mut self.diff);
+        }
+
+        match self.current_tab {
+            AppTab::Replacer if self.replacer.show_occurrences => {
+                let tab = &mut self.replacer;
+                show_occurrences_window(ctx, &mut tab.show_occurrences, &tab.occurrences, &mut tab.selected, true);
+            }
+            AppTab::Remover if self.remover.show_occurrences => {
+                let tab = &mut self.remover;
+                show_occurrences_window(ctx, &mut tab.show_occurrences, &tab.occurrences, &mut tab.selected, false);
+            }
+            _ => {}
+        }
     }
 
     fn show_about_dialog(&mut self, ctx: &egui::Context) {
@@ -472,9 +1571,11 @@ impl SteamIDApp {
                             ui.add_space(5.0);
 
                             ui.label("3a. For Replacer:");
-                            ui.label("   • Enter the new 17-digit SteamID");
-                            ui.label("   • Must start with '7656'");
-                            ui.label("   • Example: 76561198123456789");
+                            ui.label("   • Enter the new SteamID in any common notation");
+                            ui.label("   • e.g. 76561198123456789, STEAM_0:1:81595530,");
+                            ui.label("     [U:1:163191061] or a /profiles/ URL");
+                            ui.label("   • The converted SteamID64 is shown below the field");
+                            ui.label("   • Universal saves get the SteamID written back in");
                             ui.add_space(5.0);
 
                             ui.label("3b. For Remover:");
@@ -484,25 +1585,65 @@ impl SteamIDApp {
 
                             ui.label("4. 📋 Transfer Demo Save (Optional):");
                             ui.label("   • Check this for demo save files");
-                            ui.label("   • Removes 'Demo00' from filename");
-                            ui.label("   • Keeps backup as demo file");
+                            ui.label("   • Demo saves are recognised by their contents");
+                            ui.label("   • Pick the full-game slot to move the save to");
+                            ui.label("   • An existing slot is only replaced after you confirm,");
+                            ui.label("     and it is backed up first");
                             ui.add_space(5.0);
 
                             ui.label("5. 🔄 Click 'Replace SteamID' or 'Remove SteamID'");
-                            ui.label("   • A backup (.bak) will be created automatically");
+                            ui.label("   • A timestamped backup is created automatically");
+                            ui.label("   • Restore any earlier version from the 🗄 Backups tab,");
+                            ui.label("     or undo the last operation, even after a removal");
+                            ui.label("   • 🔍 Compare shows the bytes an edit changed, side by side");
+                            ui.label("   • Copy, swap, rename or delete saves in the 💾 Slots tab");
+                            ui.label("   • Save folders are found on Windows and in Proton prefixes");
+                            ui.label("     on Linux and Steam Deck, in every Steam library");
+                            ui.label("   • ☁ ⚠ marks saves Steam Cloud may overwrite with an older");
+                            ui.label("     copy; close Steam and click 'Update Cloud Record'");
+                            ui.add_space(5.0);
+
+                            ui.label("🕶 Sharing a save:");
+                            ui.label("   • The Anonymize tab replaces every SteamID with a placeholder");
+                            ui.label("     and scrubs the names of the Steam accounts on this PC");
+                            ui.label("   • By default an anonymized copy is written next to the save");
+                            ui.add_space(5.0);
+
+                            ui.label("🔢 Hex tab:");
+                            ui.label("   • Shows the raw bytes with SteamIDs, StrProperty and None");
+                            ui.label("     markers highlighted");
+                            ui.label("   • Go to an offset or search for hex bytes, ASCII or UTF-16");
+                            ui.label("   • Stage raw byte edits at the cursor, then Apply them (after");
+                            ui.label("     a backup) or save them as a reusable patch file");
+                            ui.add_space(5.0);
+
+                            ui.label("✏ Editor tab:");
+                            ui.label("   • Browse every property of the save as a tree; type in the");
+                            ui.label("     filter to find one by name");
+                            ui.label("   • Numbers, booleans, strings and enum labels can be edited;");
+                            ui.label("     values that do not fit the property's type are marked ⚠");
+                            ui.label("   • Apply writes the staged values after a backup");
+                            ui.label("   • Export the save as JSON to edit elsewhere, and Import it back");
+                            ui.add_space(5.0);
+
+                            ui.label("📚 Several saves at once:");
+                            ui.label("   • Drop several saves or a folder on the Replacer or Remover tab,");
+                            ui.label("     or use File > Process Folder...");
+                            ui.label("   • Every save is backed up; results are listed per file");
                             ui.add_space(10.0);
 
                             ui.separator();
                             ui.label("🔍 Finding your SteamID:");
-                            ui.label("• Visit steamid.io or steamidfinder.com");
-                            ui.label("• Enter your Steam profile URL");
-                            ui.label("• Copy the 'SteamID64' number");
+                            ui.label("• Pick an account that has logged into Steam on this PC");
+                            ui.label("  from the 'Steam account' dropdown, or");
+                            ui.label("• Paste your Steam profile URL (steamcommunity.com/profiles/...)");
 
                             ui.add_space(5.0);
 
                             ui.separator();
                             ui.colored_label(egui::Color32::from_rgb(80, 200, 120), "✅ Safety:");
-                            ui.label("• Automatic backup creation (.bak extension)");
+                            ui.label("• Timestamped backups in SteamIDBackups next to the save");
+                            ui.label("• Older backups are never overwritten");
                             ui.label("• Original file is preserved before changes");
                             ui.label("• Prevents replacing with same SteamID");
                             ui.add_space(5.0);
@@ -517,4 +1658,424 @@ impl SteamIDApp {
                 });
             });
     }
-}
\ No newline at end of file
+}
+
+/// Lists every SteamID occurrence with a tick box deciding whether it is edited.
+/// The remover can only empty `StrProperty` matches, so other rows are disabled there.
+fn show_occurrences_window(
+    ctx: &egui::Context,
+    open: &mut bool,
+    occurrences: &[Occurrence],
+    selected: &mut [bool],
+    is_replacer: bool,
+) {
+    let mut close = false;
+    egui::Window::new("Occurrences")
+        .open(open)
+        .collapsible(false)
+        .default_width(500.0)
+        .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+        .show(ctx, |ui| {
+            let action = if is_replacer { "replace" } else { "remove" };
+            ui.label(format!("Tick the occurrences to {}:", action));
+            ui.add_space(5.0);
+
+            egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                for (occurrence, selected) in occurrences.iter().zip(selected.iter_mut()) {
+                    let editable = is_replacer || occurrence.found_by.is_owner_property();
+                    ui.horizontal(|ui| {
+                        ui.add_enabled(
+                            editable,
+                            egui::Checkbox::new(selected, format!("0x{:08X}", occurrence.offset)),
+                        );
+                        ui.colored_label(egui::Color32::LIGHT_BLUE, &occurrence.steamid);
+                        ui.label(format!("({})", occurrence.found_by));
+                    });
+                    let property = match &occurrence.path {
+                        Some(path) => path.as_str(),
+                        None if occurrence.is_property => "unknown property",
+                        None => "look-alike, not a property",
+                    };
+                    ui.colored_label(egui::Color32::LIGHT_GRAY, format!("    {}", property));
+                    ui.label(egui::RichText::new(format!("    {}", occurrence.context)).monospace().small());
+                    ui.add_space(4.0);
+                }
+            });
+
+            ui.add_space(8.0);
+            if ui.button("✅ Close").clicked() {
+                close = true;
+            }
+        });
+    if close {
+        *open = false;
+    }
+}
+
+fn show_batch_window(ctx: &egui::Context, batch: &mut BatchView) {
+    let mut close = false;
+    let running = batch.is_running();
+    egui::Window::new(batch.title.as_str())
+        .open(&mut batch.show)
+        .collapsible(false)
+        .default_width(500.0)
+        .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+        .show(ctx, |ui| {
+            if running {
+                let fraction = if batch.total == 0 { 0.0 } else { batch.done as f32 / batch.total as f32 };
+                ui.add(
+                    egui::ProgressBar::new(fraction)
+                        .text(format!("{} / {} save(s)", batch.done, batch.total))
+                        .animate(true),
+                );
+            } else {
+                ui.label(batch.summary.to_string());
+            }
+            ui.add_space(5.0);
+
+            egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                egui::Grid::new("batch_results").striped(true).show(ui, |ui| {
+                    for result in &batch.results {
+                        ui.label(result.path.display().to_string());
+                        let color = match result.outcome {
+                            FileOutcome::Changed(_) => egui::Color32::from_rgb(80, 200, 120),
+                            FileOutcome::AlreadyUniversal | FileOutcome::NoSteamId => egui::Color32::LIGHT_GRAY,
+                            FileOutcome::Failed(_) => egui::Color32::from_rgb(220, 80, 80),
+                        };
+                        ui.colored_label(color, result.outcome.to_string());
+                        ui.end_row();
+                    }
+                });
+            });
+
+            ui.add_space(8.0);
+            if ui.button("✅ Close").clicked() {
+                close = true;
+            }
+        });
+    if close {
+        batch.show = false;
+    }
+}
+
+/// Side-by-side hex of a save against a backup or another save, with the
+/// changed bytes highlighted.
+fn show_diff_window(ctx: &egui::Context, view: &mut DiffView) {
+    let mut close = false;
+    let mut refilter = false;
+    let mut other = None;
+    egui::Window::new("🔍 Compare")
+        .open(&mut view.show)
+        .collapsible(false)
+        .default_width(720.0)
+        .resizable(true)
+        .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+        .show(ctx, |ui| {
+            ui.label(format!("Old: {} ({} bytes)", files::backup_file_name(&view.old_path), view.old_len));
+            ui.label(format!("New: {} ({} bytes)", files::backup_file_name(&view.new_path), view.new_len));
+            if view.error.is_empty() {
+                ui.colored_label(egui::Color32::LIGHT_BLUE, view.summary.to_string());
+            } else {
+                ui.colored_label(egui::Color32::from_rgb(220, 80, 80), &view.error);
+            }
+            ui.horizontal(|ui| {
+                refilter = ui.checkbox(&mut view.changes_only, "Only show changes").changed();
+                for kind in [CellKind::Changed, CellKind::Removed, CellKind::Added] {
+                    let label = match kind {
+                        CellKind::Changed => "changed",
+                        CellKind::Removed => "removed",
+                        _ => "added",
+                    };
+                    ui.label(egui::RichText::new(label).background_color(diff_color(kind)));
+                }
+            });
+            ui.add_space(5.0);
+
+            let font = egui::FontId::monospace(10.0);
+            let row_height = ui.fonts(|fonts| fonts.row_height(&font));
+            // Leave room for the header and buttons, growing with the window.
+            let max_height = (ctx.screen_rect().height() - 240.0).max(200.0);
+            egui::ScrollArea::both()
+                .max_height(max_height)
+                .auto_shrink([false, true])
+                .show_rows(ui, row_height, view.visible.len(), |ui, range| {
+                    for visible in &view.visible[range] {
+                        match visible.map(|index| &view.rows[index]) {
+                            Some(row) => ui.add(egui::Label::new(diff_row_job(row, &font)).extend()),
+                            None => ui.label(egui::RichText::new("  ...").font(font.clone()).color(egui::Color32::GRAY)),
+                        };
+                    }
+                });
+
+            ui.add_space(8.0);
+            ui.horizontal(|ui| {
+                if ui.button("📂 Compare With...").on_hover_text("Pick another save or backup as the old side").clicked() {
+                    other = utils::browse_file().map(|(path, _)| PathBuf::from(path));
+                }
+                if ui.button("✅ Close").clicked() {
+                    close = true;
+                }
+            });
+        });
+    if let Some(path) = other {
+        view.old_path = path;
+        view.compare();
+    } else if refilter {
+        view.update_visible();
+    }
+    if close {
+        view.show = false;
+    }
+}
+
+fn diff_color(kind: CellKind) -> egui::Color32 {
+    match kind {
+        CellKind::Same => egui::Color32::TRANSPARENT,
+        CellKind::Changed => egui::Color32::from_rgb(120, 95, 20),
+        CellKind::Removed => egui::Color32::from_rgb(125, 40, 40),
+        CellKind::Added => egui::Color32::from_rgb(30, 100, 50),
+    }
+}
+
+/// `00000C38 30 30 ... 30 0000..00 │ 00000C38 ...` with each differing byte
+/// on its highlight colour.
+fn diff_row_job(row: &Row, font: &egui::FontId) -> egui::text::LayoutJob {
+    let mut job = egui::text::LayoutJob::default();
+    let plain = egui::TextFormat::simple(font.clone(), egui::Color32::LIGHT_GRAY);
+    let offset_format = egui::TextFormat::simple(font.clone(), egui::Color32::GRAY);
+
+    for is_old in [true, false] {
+        let byte_of = |cell: &Cell| if is_old { cell.old } else { cell.new };
+        let offset = if is_old { row.old_offset } else { row.new_offset };
+        if !is_old {
+            job.append(" │ ", 0.0, offset_format.clone());
+        }
+        job.append(&format!("{:08X} ", offset), 0.0, offset_format.clone());
+        for (index, cell) in row.cells.iter().enumerate() {
+            let format = egui::TextFormat {
+                background: diff_color(cell.kind),
+                ..plain.clone()
+            };
+            let hex = byte_of(cell).map(|byte| format!("{:02X}", byte)).unwrap_or_else(|| "  ".to_string());
+            if index > 0 {
+                job.append(" ", 0.0, plain.clone());
+            }
+            job.append(&hex, 0.0, format);
+        }
+        job.append(&" ".repeat((diff_view::ROW_WIDTH - row.cells.len()) * 3 + 1), 0.0, plain.clone());
+        for cell in &row.cells {
+            let format = egui::TextFormat {
+                background: diff_color(cell.kind),
+                ..plain.clone()
+            };
+            let ascii = match byte_of(cell) {
+                Some(byte) if byte.is_ascii_graphic() || byte == b' ' => byte as char,
+                Some(_) => '.',
+                None => ' ',
+            };
+            job.append(&ascii.to_string(), 0.0, format);
+        }
+    }
+    job
+}
+
+/// Rows for `properties`, with structs and containers as collapsible headers.
+/// Values changed this frame are pushed to `changes` by path.
+fn property_tree(
+    ui: &mut egui::Ui,
+    editor: &EditorTab,
+    properties: &[Property],
+    prefix: &str,
+    expand: bool,
+    changes: &mut Vec<(String, String)>,
+) {
+    for property in properties {
+        let path = if prefix.is_empty() {
+            property.name.to_string()
+        } else {
+            format!("{}.{}", prefix, property.name)
+        };
+        if !editor.is_visible(&path) {
+            continue;
+        }
+
+        let children = property.value.children();
+        if children.is_empty() {
+            property_row(ui, editor, property, &path, changes);
+            continue;
+        }
+        let title = format!("{}  ({})  {}", property.name, editor::type_label(property), editor::summary(property));
+        egui::CollapsingHeader::new(title.trim_end())
+            .id_source(&path)
+            .open(expand.then_some(true))
+            .show(ui, |ui| {
+                for (index, children) in children {
+                    let Some(index) = index else {
+                        property_tree(ui, editor, children, &path, expand, changes);
+                        continue;
+                    };
+                    let element = format!("{}[{}]", path, index);
+                    if !editor.is_visible(&element) {
+                        continue;
+                    }
+                    egui::CollapsingHeader::new(format!("[{}]", index))
+                        .id_source(&element)
+                        .open(expand.then_some(true))
+                        .show(ui, |ui| property_tree(ui, editor, children, &element, expand, changes));
+                }
+            });
+    }
+}
+
+/// A property without children: its name, type and a widget for its value.
+fn property_row(
+    ui: &mut egui::Ui,
+    editor: &EditorTab,
+    property: &Property,
+    path: &str,
+    changes: &mut Vec<(String, String)>,
+) {
+    ui.horizontal(|ui| {
+        let mut name = egui::RichText::new(property.name.as_str());
+        if editor.staged.contains_key(path) {
+            name = name.background_color(EDIT_COLOR);
+        }
+        ui.label(name).on_hover_text(path);
+        ui.label(egui::RichText::new(editor::type_label(property)).small().weak());
+
+        let (Some(kind), Some(current)) = (ScalarKind::of(property), edit::scalar_text(property)) else {
+            ui.label(egui::RichText::new(editor::summary(property)).weak());
+            return;
+        };
+        let text = editor.staged.get(path).cloned().unwrap_or(current);
+        let ambiguous = editor.ambiguous.contains(path);
+        ui.add_enabled_ui(!ambiguous, |ui| {
+            if let Some(new) = scalar_widget(ui, editor, property, kind, &text) {
+                changes.push((path.to_string(), new));
+            }
+        })
+        .response
+        .on_disabled_hover_text("Several properties share this name and cannot be edited here");
+        if let Some(error) = editor.check(path) {
+            ui.colored_label(egui::Color32::from_rgb(255, 165, 0), "⚠").on_hover_text(error);
+        }
+    });
+}
+
+/// Edits `text` with a widget suited to `kind`; returns the new text if it changed.
+fn scalar_widget(ui: &mut egui::Ui, editor: &EditorTab, property: &Property, kind: ScalarKind, text: &str) -> Option<String> {
+    let width = ui.available_width().clamp(60.0, 220.0);
+    match kind {
+        ScalarKind::Bool => {
+            let mut value = text == "true";
+            ui.checkbox(&mut value, "").changed().then(|| value.to_string())
+        }
+        // Wider types do not fit a drag value's f64 exactly and are typed in.
+        ScalarKind::Integer { min, max } if max - min <= u32::MAX as i128 => {
+            let mut value: i64 = text.parse().unwrap_or_default();
+            ui.add(egui::DragValue::new(&mut value).range(min as i64..=max as i64))
+                .changed()
+                .then(|| value.to_string())
+        }
+        ScalarKind::Float => {
+            let mut value: f64 = text.parse().unwrap_or_default();
+            ui.add(egui::DragValue::new(&mut value).speed(0.1))
+                .changed()
+                .then(|| value.to_string())
+        }
+        ScalarKind::Label => {
+            let mut value = text.to_string();
+            let changed = ui.add_sized([width - 30.0, 18.0], egui::TextEdit::singleline(&mut value)).changed();
+            let enum_name = editor::type_label(property);
+            let mut picked = None;
+            if let Some(labels) = editor.enum_labels.get(&enum_name) {
+                ui.menu_button("⏷", |ui| {
+                    for label in labels {
+                        if ui.selectable_label(*label == value, label).clicked() {
+                            picked = Some(label.clone());
+                            ui.close_menu();
+                        }
+                    }
+                })
+                .response
+                .on_hover_text(format!("{} values used in this save", enum_name));
+            }
+            picked.or(changed.then_some(value))
+        }
+        ScalarKind::Integer { .. } | ScalarKind::Text => {
+            let mut value = text.to_string();
+            ui.add_sized([width, 18.0], egui::TextEdit::singleline(&mut value))
+                .changed()
+                .then_some(value)
+        }
+    }
+}
+
+/// `SBSave0001.json` for `SBSave0001.sav`.
+fn json_file_name(file_path: &str) -> String {
+    let stem = Path::new(file_path).file_stem().unwrap_or_default().to_string_lossy();
+    format!("{}.json", stem)
+}
+
+const MATCH_COLOR: egui::Color32 = egui::Color32::from_rgb(150, 110, 20);
+const CURSOR_COLOR: egui::Color32 = egui::Color32::from_rgb(70, 110, 200);
+const EDIT_COLOR: egui::Color32 = egui::Color32::from_rgb(150, 45, 45);
+
+fn marker_color(marker: Marker) -> egui::Color32 {
+    match marker {
+        Marker::SteamId => egui::Color32::from_rgb(30, 100, 50),
+        Marker::StrProperty => egui::Color32::from_rgb(95, 55, 125),
+        Marker::NoneTag => egui::Color32::from_rgb(30, 90, 105),
+    }
+}
+
+/// `00000C30  12 00 00 00 37 36 ...  ....7656...` with markers, search
+/// matches, staged edits and the selected byte highlighted.
+fn hex_row_job(view: &HexViewTab, row: usize, font: &egui::FontId) -> egui::text::LayoutJob {
+    let start = row * hex_viewer::ROW_WIDTH;
+    let bytes = &view.edited[start..(start + hex_viewer::ROW_WIDTH).min(view.edited.len())];
+    let plain = egui::TextFormat::simple(font.clone(), egui::Color32::LIGHT_GRAY);
+    let format_at = |offset: usize| {
+        let background = if view.cursor == Some(offset) {
+            CURSOR_COLOR
+        } else if view.edited.get(offset) != view.data.get(offset) {
+            EDIT_COLOR
+        } else if view.is_match(offset) {
+            MATCH_COLOR
+        } else {
+            view.highlight_at(offset)
+                .map(|h| marker_color(h.marker))
+                .unwrap_or(egui::Color32::TRANSPARENT)
+        };
+        egui::TextFormat {
+            background,
+            ..plain.clone()
+        }
+    };
+
+    let mut job = egui::text::LayoutJob::default();
+    job.append(&format!("{:08X} ", start), 0.0, egui::TextFormat::simple(font.clone(), egui::Color32::GRAY));
+    for (index, byte) in bytes.iter().enumerate() {
+        job.append(" ", 0.0, plain.clone());
+        job.append(&format!("{:02X}", byte), 0.0, format_at(start + index));
+    }
+    job.append(&" ".repeat((hex_viewer::ROW_WIDTH - bytes.len()) * 3 + 2), 0.0, plain.clone());
+    for (index, &byte) in bytes.iter().enumerate() {
+        let ascii = if byte.is_ascii_graphic() || byte == b' ' { byte as char } else { '.' };
+        job.append(&ascii.to_string(), 0.0, format_at(start + index));
+    }
+    job
+}
+
+/// The byte within a row under text column `column` of [`hex_row_job`].
+fn hex_column_byte(column: usize) -> Option<usize> {
+    let hex_start = 10;
+    let ascii_start = hex_start + hex_viewer::ROW_WIDTH * 3 + 1;
+    if (hex_start..ascii_start - 1).contains(&column) {
+        Some((column - hex_start) / 3)
+    } else {
+        (ascii_start..ascii_start + hex_viewer::ROW_WIDTH)
+            .contains(&column)
+            .then(|| column - ascii_start)
+    }
+}
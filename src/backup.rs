@@ -0,0 +1,291 @@
+//! Timestamped backup history for save files.
+//!
+//! Each backup is named `<save file>.<YYYYMMDD-HHMMSS>.<hash>.bak`, where the
+//! hash is the start of the SHA-256 of its contents, so repeated operations
+//! never overwrite an older copy and identical copies are stored once. In a
+//! backup directory shared by saves from several folders, the save file name
+//! is followed by `@<folder id>` so same-named saves keep separate histories.
+
+use crate::error::{Error, Result};
+use crate::files;
+use crate::replace;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Folder created next to a save when no backup directory is configured.
+pub const DEFAULT_DIR_NAME: &str = "SteamIDBackups";
+
+const BACKUP_EXTENSION: &str = "bak";
+const HASH_LEN: usize = 12;
+const FOLDER_ID_LEN: usize = 8;
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Which backups of a save to keep; `None` means no limit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Retention {
+    pub keep_last: Option<usize>,
+    pub max_age_days: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BackupStore {
+    /// Where backups go; `None` keeps them in `SteamIDBackups` next to each save.
+    pub dir: Option<PathBuf>,
+    pub retention: Retention,
+}
+
+#[derive(Debug, Clone)]
+pub struct BackupEntry {
+    pub path: PathBuf,
+    /// File name of the save this is a copy of.
+    pub save_name: String,
+    /// ID of the save's folder, for backups in a shared backup directory.
+    pub folder_id: Option<String>,
+    /// Unix time the backup was taken.
+    pub timestamp: u64,
+    pub hash: String,
+    pub size: u64,
+}
+
+impl BackupEntry {
+    /// The SteamID the backed-up save contained, if any.
+    pub fn steamid(&self) -> Option<String> {
+        let data = files::read_save(&self.path).ok()?;
+        replace::find_steamid(&data)
+    }
+}
+
+impl BackupStore {
+    /// Backup directory used for `save_path`.
+    pub fn dir_for(&self, save_path: &Path) -> PathBuf {
+        match &self.dir {
+            Some(dir) => dir.clone(),
+            None => save_path
+                .parent()
+                .unwrap_or_else(|| Path::new("."))
+                .join(DEFAULT_DIR_NAME),
+        }
+    }
+
+    /// The name the backups and history of `save_path` are filed under: its
+    /// file name, followed by `@<folder id>` in a shared backup directory.
+    pub fn source_name(&self, save_path: &Path) -> String {
+        match self.folder_id(save_path) {
+            Some(folder_id) => format!("{}@{}", file_name(save_path), folder_id),
+            None => file_name(save_path),
+        }
+    }
+
+    /// Start of the SHA-256 of the save's absolute folder path, when backups
+    /// do not go into a folder of their own next to the save.
+    fn folder_id(&self, save_path: &Path) -> Option<String> {
+        self.dir.as_ref()?;
+        let folder = save_path.parent().unwrap_or_else(|| Path::new("."));
+        let folder = folder
+            .canonicalize()
+            .or_else(|_| std::path::absolute(folder))
+            .unwrap_or_else(|_| folder.to_path_buf());
+        let mut id = hex::encode(Sha256::digest(folder.to_string_lossy().as_bytes()));
+        id.truncate(FOLDER_ID_LEN);
+        Some(id)
+    }
+
+    /// Copies the save into the store and applies the retention policy. If a
+    /// backup with identical contents exists, that backup is returned instead,
+    /// so paths already recorded in the history stay valid.
+    pub fn create(&self, save_path: &Path) -> Result<PathBuf> {
+        let data = files::read_save(save_path)?;
+        let hash = short_hash(&data);
+
+        let backup_path = match self.list(save_path)?.into_iter().find(|entry| entry.hash == hash) {
+            Some(entry) => entry.path,
+            None => {
+                let dir = self.dir_for(save_path);
+                std::fs::create_dir_all(&dir).map_err(Error::io("Failed to create backup folder"))?;
+                let name = format!("{}.{}.{}.{}", self.source_name(save_path), compact_time(now()), hash, BACKUP_EXTENSION);
+                let backup_path = dir.join(name);
+                std::fs::write(&backup_path, &data).map_err(Error::io("Failed to create backup"))?;
+                backup_path
+            }
+        };
+
+        self.prune(save_path, Some(&backup_path))?;
+        Ok(backup_path)
+    }
+
+    /// Backups of `save_path`, newest first. In a shared directory this
+    /// includes backups taken before folder IDs were added to the names.
+    pub fn list(&self, save_path: &Path) -> Result<Vec<BackupEntry>> {
+        let save_name = file_name(save_path);
+        let folder_id = self.folder_id(save_path);
+        let entries = self
+            .list_all(save_path)?
+            .into_iter()
+            .filter(|entry| entry.save_name == save_name)
+            .filter(|entry| entry.folder_id.is_none() || entry.folder_id == folder_id)
+            .collect();
+        Ok(entries)
+    }
+
+    /// Every backup in the directory used for `save_path`, for any save,
+    /// grouped by save and newest first.
+    pub fn list_all(&self, save_path: &Path) -> Result<Vec<BackupEntry>> {
+        let dir = self.dir_for(save_path);
+        let read_dir = match std::fs::read_dir(&dir) {
+            Ok(read_dir) => read_dir,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(Error::io("Failed to read backup folder")(e)),
+        };
+
+        let mut entries = Vec::new();
+        for dir_entry in read_dir.flatten() {
+            let path = dir_entry.path();
+            if let Some(mut entry) = parse_backup_name(&path) {
+                entry.size = dir_entry.metadata().map(|m| m.len()).unwrap_or(0);
+                entries.push(entry);
+            }
+        }
+        entries.sort_by(|a, b| a.save_name.cmp(&b.save_name).then(b.timestamp.cmp(&a.timestamp)));
+        Ok(entries)
+    }
+
+    /// Restores `backup` over `save_path`, backing up the current file first.
+    /// Returns the backup of the replaced file, if there was one.
+    pub fn restore(&self, backup: &Path, save_path: &Path) -> Result<Option<PathBuf>> {
+        let data = files::read_save(backup)?;
+        let previous = if save_path.exists() {
+            Some(self.create(save_path)?)
+        } else {
+            None
+        };
+
+        files::write_save(save_path, &data)?;
+        Ok(previous)
+    }
+
+    /// Deletes backups of `save_path` outside the retention policy, never
+    /// touching `keep`. Returns the deleted paths. Backups in a shared
+    /// directory that cannot be told apart from those of same-named saves in
+    /// other folders are kept.
+    pub fn prune(&self, save_path: &Path, keep: Option<&Path>) -> Result<Vec<PathBuf>> {
+        let Retention { keep_last, max_age_days } = self.retention;
+        let oldest_allowed = max_age_days.map(|days| now().saturating_sub(days * SECONDS_PER_DAY));
+        let folder_id = self.folder_id(save_path);
+
+        let mut deleted = Vec::new();
+        let mut entries: Vec<BackupEntry> = self
+            .list(save_path)?
+            .into_iter()
+            .filter(|entry| entry.folder_id == folder_id)
+            .collect();
+        // `keep` counts as the newest, even if an older copy shares its second.
+        entries.sort_by_key(|entry| Some(entry.path.as_path()) != keep);
+        for (index, entry) in entries.into_iter().enumerate() {
+            let too_many = keep_last.is_some_and(|n| index >= n);
+            let too_old = oldest_allowed.is_some_and(|oldest| entry.timestamp < oldest);
+            if (too_many || too_old) && Some(entry.path.as_path()) != keep {
+                std::fs::remove_file(&entry.path).map_err(Error::io("Failed to delete old backup"))?;
+                deleted.push(entry.path);
+            }
+        }
+        Ok(deleted)
+    }
+}
+
+/// `2026-10-17 14:03:09 UTC`
+pub fn format_time(timestamp: u64) -> String {
+    let (year, month, day, hour, minute, second) = civil_time(timestamp);
+    format!("{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC", year, month, day, hour, minute, second)
+}
+
+fn compact_time(timestamp: u64) -> String {
+    let (year, month, day, hour, minute, second) = civil_time(timestamp);
+    format!("{:04}{:02}{:02}-{:02}{:02}{:02}", year, month, day, hour, minute, second)
+}
+
+fn parse_compact_time(s: &str) -> Option<u64> {
+    let (date, time) = s.split_once('-')?;
+    if date.len() != 8 || time.len() != 6 || !date.chars().chain(time.chars()).all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let field = |s: &str, range: std::ops::Range<usize>| s[range].parse::<u64>().ok();
+
+    let days = days_from_civil(field(date, 0..4)? as i64, field(date, 4..6)?, field(date, 6..8)?);
+    let seconds = field(time, 0..2)? * 3600 + field(time, 2..4)? * 60 + field(time, 4..6)?;
+    u64::try_from(days).ok().map(|days| days * SECONDS_PER_DAY + seconds)
+}
+
+/// Splits `<save name>[@<folder id>].<time>.<hash>.bak` back into its parts.
+fn parse_backup_name(path: &Path) -> Option<BackupEntry> {
+    let name = path.file_name()?.to_str()?;
+    let rest = name.strip_suffix(BACKUP_EXTENSION)?.strip_suffix('.')?;
+    let (rest, hash) = rest.rsplit_once('.')?;
+    let (source_name, time) = rest.rsplit_once('.')?;
+
+    if hash.len() != HASH_LEN || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let (save_name, folder_id) = match source_name.rsplit_once('@') {
+        Some((save_name, id)) if id.len() == FOLDER_ID_LEN && id.chars().all(|c| c.is_ascii_hexdigit()) => {
+            (save_name, Some(id.to_string()))
+        }
+        _ => (source_name, None),
+    };
+
+    Some(BackupEntry {
+        path: path.to_path_buf(),
+        save_name: save_name.to_string(),
+        folder_id,
+        timestamp: parse_compact_time(time)?,
+        hash: hash.to_string(),
+        size: 0,
+    })
+}
+
+fn short_hash(data: &[u8]) -> String {
+    let mut hash = hex::encode(Sha256::digest(data));
+    hash.truncate(HASH_LEN);
+    hash
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+pub(crate) fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// UTC calendar fields for a Unix time (Howard Hinnant's civil_from_days).
+fn civil_time(timestamp: u64) -> (i64, u64, u64, u64, u64, u64) {
+    let days = (timestamp / SECONDS_PER_DAY) as i64;
+    let secs = timestamp % SECONDS_PER_DAY;
+
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u64;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u64;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    (year, month, day, secs / 3600, secs % 3600 / 60, secs % 60)
+}
+
+fn days_from_civil(year: i64, month: u64, day: u64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let mp = (month as i64 + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
@@ -0,0 +1,117 @@
+use std::path::{Path, PathBuf};
+use steamid_replacer::backup::{BackupEntry, BackupStore, Retention};
+use steamid_replacer::{files, manifest};
+
+#[derive(Default)]
+pub struct BackupsTab {
+    pub file_path: String,
+    /// Custom backup folder; empty keeps backups next to each save.
+    pub backup_dir: String,
+    /// 0 keeps every backup.
+    pub keep_last: usize,
+    /// 0 keeps backups forever.
+    pub max_age_days: u64,
+    /// Backups of `file_path` with the SteamID each contains, newest first.
+    pub entries: Vec<(BackupEntry, Option<String>)>,
+    /// Operations recorded in the save's manifest, oldest first.
+    pub operations: Vec<manifest::Entry>,
+    pub status: String,
+    pub backup_filename: String,
+}
+
+impl BackupsTab {
+    pub fn store(&self) -> BackupStore {
+        BackupStore {
+            dir: (!self.backup_dir.trim().is_empty()).then(|| PathBuf::from(self.backup_dir.trim())),
+            retention: Retention {
+                keep_last: (self.keep_last > 0).then_some(self.keep_last),
+                max_age_days: (self.max_age_days > 0).then_some(self.max_age_days),
+            },
+        }
+    }
+
+    pub fn refresh(&mut self) {
+        self.entries.clear();
+        self.operations.clear();
+        if self.file_path.is_empty() {
+            return;
+        }
+
+        match manifest::history(&self.store(), Path::new(&self.file_path)) {
+            Ok(operations) => self.operations = operations,
+            Err(e) => self.status = format!("❌ Error: {}", e),
+        }
+
+        match self.store().list(Path::new(&self.file_path)) {
+            Ok(entries) => {
+                self.entries = entries
+                    .into_iter()
+                    .map(|entry| {
+                        let steamid = entry.steamid();
+                        (entry, steamid)
+                    })
+                    .collect();
+            }
+            Err(e) => self.status = format!("❌ Error: {}", e),
+        }
+    }
+
+    pub fn handle_restore(&mut self, index: usize) {
+        let Some((entry, _)) = self.entries.get(index) else {
+            return;
+        };
+        let backup_path = entry.path.clone();
+
+        match self.store().restore(&backup_path, Path::new(&self.file_path)) {
+            Ok(previous) => {
+                self.status = format!("✅ Successfully restored {}", files::backup_file_name(&backup_path));
+                self.backup_filename = previous
+                    .as_deref()
+                    .map(files::backup_file_name)
+                    .unwrap_or_default();
+            }
+            Err(e) => {
+                self.status = format!("❌ Error: {}", e);
+                self.backup_filename.clear();
+            }
+        }
+        self.refresh();
+    }
+
+    /// Reverts the last recorded operation on the save.
+    pub fn handle_undo(&mut self) {
+        let options = manifest::UndoOptions {
+            backup: Some(self.store()),
+            dry_run: false,
+        };
+        match manifest::undo(Path::new(&self.file_path), &options) {
+            Ok(report) => {
+                self.status = format!("✅ Successfully undid {}", report.undone.summary());
+                self.backup_filename = report
+                    .backup_path
+                    .as_deref()
+                    .map(files::backup_file_name)
+                    .unwrap_or_default();
+            }
+            Err(e) => {
+                self.status = format!("❌ Error: {}", e);
+                self.backup_filename.clear();
+            }
+        }
+        self.refresh();
+    }
+
+    pub fn handle_prune(&mut self) {
+        if self.file_path.is_empty() {
+            self.status = "(!) Please select a file first".to_string();
+            return;
+        }
+
+        match self.store().prune(Path::new(&self.file_path), None) {
+            Ok(deleted) => self.status = format!("✅ Successfully deleted {} old backup(s)", deleted.len()),
+            Err(e) => self.status = format!("❌ Error: {}", e),
+        }
+        self.backup_filename.clear();
+        self.refresh();
+    }
+}
@@ -0,0 +1,213 @@
+//! Replacing or removing the SteamID in many saves at once.
+//!
+//! Files are processed on a pool of worker threads; each gets its own result
+//! so one bad save does not stop the rest.
+
+use crate::backup::{self, BackupStore};
+use crate::error::{Error, Result};
+use crate::{remove, replace};
+use std::collections::HashSet;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BatchOperation {
+    Replace { steamid: String, include_lookalikes: bool },
+    Remove,
+}
+
+#[derive(Debug, Clone)]
+pub struct BatchOptions {
+    pub operation: BatchOperation,
+    /// Where each save is backed up before writing; `None` skips backups.
+    pub backup: Option<BackupStore>,
+    pub dry_run: bool,
+    /// Worker threads; `None` uses one per CPU.
+    pub jobs: Option<usize>,
+}
+
+#[derive(Debug)]
+pub enum FileOutcome {
+    /// The save was (or in a dry run would be) written with this many edits.
+    Changed(usize),
+    AlreadyUniversal,
+    NoSteamId,
+    Failed(Error),
+}
+
+impl fmt::Display for FileOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FileOutcome::Changed(count) => write!(f, "changed ({} occurrence(s))", count),
+            FileOutcome::AlreadyUniversal => write!(f, "already universal"),
+            FileOutcome::NoSteamId => write!(f, "no SteamID"),
+            FileOutcome::Failed(e) => write!(f, "error: {}", e),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct FileResult {
+    pub path: PathBuf,
+    pub outcome: FileOutcome,
+    pub backup_path: Option<PathBuf>,
+}
+
+/// How many files ended in each outcome.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BatchSummary {
+    pub changed: usize,
+    pub already_universal: usize,
+    pub no_steamid: usize,
+    pub failed: usize,
+}
+
+impl BatchSummary {
+    pub fn of(results: &[FileResult]) -> Self {
+        let mut summary = Self::default();
+        for result in results {
+            match result.outcome {
+                FileOutcome::Changed(_) => summary.changed += 1,
+                FileOutcome::AlreadyUniversal => summary.already_universal += 1,
+                FileOutcome::NoSteamId => summary.no_steamid += 1,
+                FileOutcome::Failed(_) => summary.failed += 1,
+            }
+        }
+        summary
+    }
+
+    pub fn total(&self) -> usize {
+        self.changed + self.already_universal + self.no_steamid + self.failed
+    }
+}
+
+impl fmt::Display for BatchSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} file(s): {} changed, {} already universal, {} without SteamID, {} failed",
+            self.total(),
+            self.changed,
+            self.already_universal,
+            self.no_steamid,
+            self.failed
+        )
+    }
+}
+
+/// Expands `paths` into the save files to process: files are taken as given,
+/// folders are searched recursively for `.sav` files, skipping backup folders.
+/// Paths are canonicalized and each save is listed once, so no two workers
+/// write the same file.
+pub fn collect_saves(paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    let mut saves = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            collect_dir(path, &mut saves)?;
+        } else {
+            saves.push(path.clone());
+        }
+    }
+
+    let mut seen = HashSet::new();
+    Ok(saves
+        .into_iter()
+        .map(|save| save.canonicalize().unwrap_or(save))
+        .filter(|save| seen.insert(save.clone()))
+        .collect())
+}
+
+fn collect_dir(dir: &Path, saves: &mut Vec<PathBuf>) -> Result<()> {
+    let read_dir = std::fs::read_dir(dir).map_err(Error::io("Failed to read folder"))?;
+    let mut paths: Vec<PathBuf> = read_dir.flatten().map(|entry| entry.path()).collect();
+    paths.sort();
+
+    for path in paths {
+        if path.is_dir() {
+            if path.file_name().is_some_and(|name| name != backup::DEFAULT_DIR_NAME) {
+                collect_dir(&path, saves)?;
+            }
+        } else if path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("sav")) {
+            saves.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Runs the operation on every save in parallel. Results are in the order of `saves`.
+pub fn run(saves: &[PathBuf], options: &BatchOptions) -> Vec<FileResult> {
+    run_with_progress(saves, options, |_| {})
+}
+
+/// Like [`run`], calling `on_done` with the number of finished files each time
+/// one completes.
+pub fn run_with_progress(saves: &[PathBuf], options: &BatchOptions, on_done: impl Fn(usize) + Sync) -> Vec<FileResult> {
+    let jobs = options
+        .jobs
+        .or_else(|| std::thread::available_parallelism().ok().map(|n| n.get()))
+        .unwrap_or(1)
+        .clamp(1, saves.len().max(1));
+
+    let next = AtomicUsize::new(0);
+    let done = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<FileResult>>> = Mutex::new(saves.iter().map(|_| None).collect());
+    std::thread::scope(|scope| {
+        for _ in 0..jobs {
+            scope.spawn(|| {
+                loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(path) = saves.get(index) else {
+                        break;
+                    };
+                    let result = process(path, options);
+                    results.lock().unwrap_or_else(|e| e.into_inner())[index] = Some(result);
+                    on_done(done.fetch_add(1, Ordering::Relaxed) + 1);
+                }
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap_or_else(|e| e.into_inner())
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+fn process(path: &Path, options: &BatchOptions) -> FileResult {
+    let outcome = match &options.operation {
+        BatchOperation::Replace { steamid, include_lookalikes } => {
+            let replace_options = replace::ReplaceOptions {
+                backup: options.backup.clone(),
+                dry_run: options.dry_run,
+                include_lookalikes: *include_lookalikes,
+                ..Default::default()
+            };
+            replace::replace_steamid_in_file(path, steamid, &replace_options)
+                .map(|report| (report.replacements(), report.backup_path))
+        }
+        BatchOperation::Remove => {
+            let remove_options = remove::RemoveOptions {
+                backup: options.backup.clone(),
+                dry_run: options.dry_run,
+                ..Default::default()
+            };
+            remove::remove_steamid_in_file(path, &remove_options).map(|report| (report.removed.len(), report.backup_path))
+        }
+    };
+
+    let (outcome, backup_path) = match outcome {
+        Ok((count, backup_path)) => (FileOutcome::Changed(count), backup_path),
+        Err(Error::AlreadyUniversal) => (FileOutcome::AlreadyUniversal, None),
+        Err(Error::NoSteamIdFound) => (FileOutcome::NoSteamId, None),
+        Err(e) => (FileOutcome::Failed(e), None),
+    };
+    FileResult {
+        path: path.to_path_buf(),
+        outcome,
+        backup_path,
+    }
+}
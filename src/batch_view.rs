@@ -0,0 +1,104 @@
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use steamid_replacer::Error;
+use steamid_replacer::batch::{self, BatchOptions, BatchSummary, FileResult};
+
+/// Messages from the worker thread of a running batch.
+enum BatchMessage {
+    /// The saves to process have been collected.
+    Found(usize),
+    /// This many saves have finished.
+    Progress(usize),
+    Finished(Result<Vec<FileResult>, Error>),
+}
+
+/// Results of the last batch run, shown in their own window.
+#[derive(Default)]
+pub struct BatchView {
+    pub show: bool,
+    pub title: String,
+    pub results: Vec<FileResult>,
+    pub summary: BatchSummary,
+    /// Saves found and saves finished by the running batch.
+    pub total: usize,
+    pub done: usize,
+    receiver: Option<Receiver<BatchMessage>>,
+}
+
+impl BatchView {
+    /// Starts processing every save in `paths` (folders are searched
+    /// recursively) on a background thread and opens the result window.
+    /// Call [`BatchView::poll`] every frame until it returns the outcome.
+    pub fn start(&mut self, paths: &[PathBuf], options: BatchOptions) {
+        let (sender, receiver) = mpsc::channel();
+        let paths = paths.to_vec();
+        self.title = match options.operation {
+            batch::BatchOperation::Replace { .. } => "🔄 Batch Replace".to_string(),
+            batch::BatchOperation::Remove => "❌ Batch Remove".to_string(),
+        };
+        std::thread::spawn(move || {
+            let saves = match batch::collect_saves(&paths) {
+                Ok(saves) => saves,
+                Err(e) => {
+                    let _ = sender.send(BatchMessage::Finished(Err(e)));
+                    return;
+                }
+            };
+            let _ = sender.send(BatchMessage::Found(saves.len()));
+            let results = batch::run_with_progress(&saves, &options, |done| {
+                let _ = sender.send(BatchMessage::Progress(done));
+            });
+            let _ = sender.send(BatchMessage::Finished(Ok(results)));
+        });
+
+        self.results.clear();
+        self.summary = BatchSummary::default();
+        self.total = 0;
+        self.done = 0;
+        self.receiver = Some(receiver);
+        self.show = true;
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.receiver.is_some()
+    }
+
+    /// Takes the messages the worker has sent so far; returns the outcome
+    /// once the batch has finished.
+    pub fn poll(&mut self) -> Option<Result<BatchSummary, Error>> {
+        let receiver = self.receiver.as_ref()?;
+        loop {
+            match receiver.try_recv() {
+                Ok(BatchMessage::Found(total)) => self.total = total,
+                Ok(BatchMessage::Progress(done)) => self.done = self.done.max(done),
+                Ok(BatchMessage::Finished(outcome)) => {
+                    self.receiver = None;
+                    return Some(outcome.map(|results| {
+                        self.results = results;
+                        self.summary = BatchSummary::of(&self.results);
+                        self.summary
+                    }));
+                }
+                Err(TryRecvError::Empty) => return None,
+                Err(TryRecvError::Disconnected) => {
+                    self.receiver = None;
+                    return Some(Err(Error::Io {
+                        context: "Batch worker stopped",
+                        source: std::io::Error::other("the worker thread panicked"),
+                    }));
+                }
+            }
+        }
+    }
+
+    /// Status line for the tab that started the batch.
+    pub fn status(summary: &BatchSummary) -> String {
+        if summary.total() == 0 {
+            "(!) No .sav files found".to_string()
+        } else if summary.failed > 0 {
+            format!("❌ Error: batch finished with failures. {}", summary)
+        } else {
+            format!("✅ Successfully processed {}", summary)
+        }
+    }
+}
@@ -0,0 +1,1141 @@
+// Copyright (C) 2025 Dxian998
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use clap::{Args, Parser, Subcommand};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use steamid_replacer::backup::{self, BackupStore, Retention};
+use steamid_replacer::batch;
+use steamid_replacer::demo::{self, DemoTransfer};
+use steamid_replacer::{
+    Error, anonymize, cloud, diff, edit, export, files, gvas::SaveGame, hex, manifest, occurrences, patch, personalize, remove, replace, slots, steam, steamid,
+};
+
+/// Exit code when the save needed no change (no SteamID found, already universal).
+const EXIT_NOTHING_TO_DO: u8 = 3;
+
+#[derive(Parser)]
+#[command(name = "steamid-replacer-cli", version, about = "Replace or remove SteamID in Stellar Blade save files")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Show the SteamID embedded in a save
+    Inspect {
+        file: PathBuf,
+    },
+    /// List every SteamID occurrence with its offset, property and hex context
+    Occurrences {
+        file: PathBuf,
+    },
+    /// Replace the SteamID in a save with a new one
+    Replace {
+        file: PathBuf,
+        /// SteamID64, STEAM_0:Y:Z, [U:1:N], account ID or profile URL
+        steamid: String,
+        /// Also replace digit runs that are not a SteamID string property
+        #[arg(long)]
+        include_lookalikes: bool,
+        #[command(flatten)]
+        write: WriteArgs,
+    },
+    /// Remove the SteamID, making the save universal
+    Remove {
+        file: PathBuf,
+        #[command(flatten)]
+        write: WriteArgs,
+    },
+    /// Replace or remove the SteamID in many saves or whole folders at once
+    Batch {
+        #[command(subcommand)]
+        operation: BatchCommand,
+    },
+    /// Bind a universal save to an account by writing its SteamID back
+    Personalize {
+        file: PathBuf,
+        /// SteamID64, STEAM_0:Y:Z, [U:1:N], account ID or profile URL
+        steamid: String,
+        #[command(flatten)]
+        write: WriteArgs,
+    },
+    /// Replace every SteamID and local account name with placeholders before sharing a save
+    Anonymize {
+        file: PathBuf,
+        /// Write the anonymized save here and leave FILE untouched
+        #[arg(long, short = 'o', value_name = "FILE")]
+        output: Option<PathBuf>,
+        /// Allow --output to replace an existing file (it is backed up first)
+        #[arg(long)]
+        overwrite: bool,
+        /// Another name or string to scrub; repeatable
+        #[arg(long, value_name = "TEXT")]
+        also: Vec<String>,
+        /// Report what would be scrubbed without writing anything
+        #[arg(long)]
+        dry_run: bool,
+        /// Do not back up the file being replaced
+        #[arg(long)]
+        no_backup: bool,
+        #[command(flatten)]
+        store: BackupArgs,
+    },
+    /// Overwrite raw bytes by offset, or apply a patch file, checking the bytes being replaced
+    Patch {
+        file: PathBuf,
+        /// Write these bytes, e.g. `0xC27=16` or `3112="00 04"`; repeatable
+        #[arg(long = "set", value_name = "OFFSET=HEX", value_parser = parse_patch_edit)]
+        set: Vec<(usize, Vec<u8>)>,
+        /// Apply a patch file written by --save
+        #[arg(long, value_name = "PATCH")]
+        apply: Option<PathBuf>,
+        /// Write the edits, with the bytes they replace, to a patch file
+        #[arg(long, value_name = "PATCH")]
+        save: Option<PathBuf>,
+        /// Check the edits without touching the save (--save still writes the patch)
+        #[arg(long)]
+        dry_run: bool,
+        /// Do not back up the save before writing
+        #[arg(long)]
+        no_backup: bool,
+        #[command(flatten)]
+        store: BackupArgs,
+    },
+    /// Set property values by path, checking each fits the property's type
+    Set {
+        file: PathBuf,
+        /// e.g. `Level=43`, `Info.Name=Eve` or `Items[1].Label=sword`; repeatable
+        #[arg(required = true, value_name = "PATH=VALUE", value_parser = parse_value_edit)]
+        values: Vec<edit::ValueEdit>,
+        /// Check the values without touching the save
+        #[arg(long)]
+        dry_run: bool,
+        /// Do not back up the save before writing
+        #[arg(long)]
+        no_backup: bool,
+        #[command(flatten)]
+        store: BackupArgs,
+    },
+    /// Write a save's whole property tree as an editable JSON document
+    Export {
+        file: PathBuf,
+        /// Write the JSON to this file instead of printing it
+        #[arg(long, short = 'o', value_name = "JSON")]
+        output: Option<PathBuf>,
+    },
+    /// Rebuild a save from a JSON document written by `export`
+    Import {
+        json: PathBuf,
+        /// The save to write
+        file: PathBuf,
+        /// Allow replacing an existing save (it is backed up first)
+        #[arg(long)]
+        overwrite: bool,
+        /// Check that the document rebuilds a valid save without writing it
+        #[arg(long)]
+        dry_run: bool,
+        /// Do not back up the save being replaced
+        #[arg(long)]
+        no_backup: bool,
+        #[command(flatten)]
+        store: BackupArgs,
+    },
+    /// Print a SteamID in every supported notation
+    Convert {
+        steamid: String,
+    },
+    /// List the Steam accounts that have logged in on this machine
+    Accounts {
+        /// Steam installation folder (detected if omitted)
+        #[arg(long)]
+        steam_dir: Option<PathBuf>,
+    },
+    /// List the backups taken of a save, newest first
+    Backups {
+        file: PathBuf,
+        #[command(flatten)]
+        store: BackupArgs,
+    },
+    /// Restore a backup over a save, backing up the current file first
+    Restore {
+        file: PathBuf,
+        /// Backup file, or its number in the `backups` list (1 = newest)
+        backup: String,
+        #[command(flatten)]
+        store: BackupArgs,
+    },
+    /// Compare a save with one of its backups or another save, byte by byte
+    Diff {
+        file: PathBuf,
+        /// Backup file or other save, or a backup's number in the `backups` list (default: 1 = newest)
+        other: Option<String>,
+        /// Unchanged rows shown around each change
+        #[arg(long, value_name = "ROWS", default_value_t = 2)]
+        context: usize,
+        #[command(flatten)]
+        store: BackupArgs,
+    },
+    /// List the operations recorded for a save, oldest first
+    History {
+        file: PathBuf,
+        #[command(flatten)]
+        store: BackupArgs,
+    },
+    /// Revert the last recorded operation on a save
+    Undo {
+        file: PathBuf,
+        /// Check that the operation can be reverted without touching the file
+        #[arg(long)]
+        dry_run: bool,
+        /// Do not back up the save before writing
+        #[arg(long)]
+        no_backup: bool,
+        #[command(flatten)]
+        store: BackupArgs,
+    },
+    /// Show the Steam installs, libraries and SaveGames folders found on this machine
+    Locate {
+        /// Steam installation folder (detected if omitted)
+        #[arg(long)]
+        steam_dir: Option<PathBuf>,
+    },
+    /// Check a save against Steam Cloud's record of it, optionally updating the record
+    Cloud {
+        file: PathBuf,
+        /// Steam installation folder (detected if omitted)
+        #[arg(long)]
+        steam_dir: Option<PathBuf>,
+        /// Record the save's current size, hash and time so Steam uploads it
+        /// instead of restoring the cloud copy (close Steam first)
+        #[arg(long)]
+        update: bool,
+        #[command(flatten)]
+        store: BackupArgs,
+    },
+    /// List the saves in a SaveGames folder with their SteamID and demo status
+    Slots {
+        /// SaveGames folder (detected if omitted)
+        dir: Option<PathBuf>,
+    },
+    /// Copy, swap, rename or delete save slots
+    Slot {
+        #[command(subcommand)]
+        action: SlotAction,
+    },
+    /// Check that saves parse and re-serialize byte for byte
+    Verify {
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum BatchCommand {
+    /// Replace the SteamID in every save
+    Replace {
+        /// SteamID64, STEAM_0:Y:Z, [U:1:N], account ID or profile URL
+        steamid: String,
+        /// Saves or folders, searched recursively for .sav files (default: the detected SaveGames folder)
+        paths: Vec<PathBuf>,
+        /// Also replace digit runs that are not a SteamID string property
+        #[arg(long)]
+        include_lookalikes: bool,
+        #[command(flatten)]
+        batch: BatchArgs,
+    },
+    /// Remove the SteamID from every save
+    Remove {
+        /// Saves or folders, searched recursively for .sav files (default: the detected SaveGames folder)
+        paths: Vec<PathBuf>,
+        #[command(flatten)]
+        batch: BatchArgs,
+    },
+}
+
+#[derive(Args)]
+struct BatchArgs {
+    /// Report what would change without touching any file
+    #[arg(long)]
+    dry_run: bool,
+    /// Do not back up saves before writing
+    #[arg(long)]
+    no_backup: bool,
+    #[command(flatten)]
+    store: BackupArgs,
+    /// Number of files processed in parallel (default: one per CPU)
+    #[arg(long, short = 'j', value_name = "N")]
+    jobs: Option<usize>,
+}
+
+#[derive(Subcommand)]
+enum SlotAction {
+    /// Copy a save to another slot in the same folder
+    Copy {
+        file: PathBuf,
+        /// File name of the new slot (`.sav` is added if missing)
+        name: String,
+        /// Replace the slot if it exists (it is backed up first)
+        #[arg(long)]
+        overwrite: bool,
+        #[command(flatten)]
+        store: BackupArgs,
+    },
+    /// Exchange the contents of two saves, backing up both
+    Swap {
+        first: PathBuf,
+        second: PathBuf,
+        #[command(flatten)]
+        store: BackupArgs,
+    },
+    /// Rename a save within its folder
+    Rename {
+        file: PathBuf,
+        name: String,
+    },
+    /// Move a save into the backup folder
+    Delete {
+        file: PathBuf,
+        #[command(flatten)]
+        store: BackupArgs,
+    },
+}
+
+#[derive(Args)]
+struct WriteArgs {
+    /// Report what would change without touching the file
+    #[arg(long)]
+    dry_run: bool,
+    /// Do not back up the save before writing
+    #[arg(long)]
+    no_backup: bool,
+    #[command(flatten)]
+    store: BackupArgs,
+    /// Move a demo save into a full-game slot after editing
+    #[arg(long)]
+    transfer_demo: bool,
+    /// Slot to transfer the demo save to (implies --transfer-demo)
+    #[arg(long, value_name = "FILE")]
+    transfer_to: Option<PathBuf>,
+    /// Allow the transfer to replace an existing save (it is backed up first)
+    #[arg(long)]
+    overwrite: bool,
+    /// Only edit the occurrence at this offset (decimal or 0x hex); repeatable
+    #[arg(long = "offset", value_name = "OFFSET", value_parser = parse_offset)]
+    offsets: Vec<usize>,
+}
+
+#[derive(Args)]
+struct BackupArgs {
+    /// Backup folder (default: SteamIDBackups next to the save)
+    #[arg(long, value_name = "DIR")]
+    backup_dir: Option<PathBuf>,
+    /// Keep only the newest N backups of each save
+    #[arg(long, value_name = "N")]
+    keep: Option<usize>,
+    /// Delete backups older than this many days
+    #[arg(long, value_name = "DAYS")]
+    keep_days: Option<u64>,
+}
+
+impl BackupArgs {
+    fn store(&self) -> BackupStore {
+        BackupStore {
+            dir: self.backup_dir.clone(),
+            retention: Retention {
+                keep_last: self.keep,
+                max_age_days: self.keep_days,
+            },
+        }
+    }
+}
+
+impl WriteArgs {
+    fn only_offsets(&self) -> Option<Vec<usize>> {
+        (!self.offsets.is_empty()).then(|| self.offsets.clone())
+    }
+
+    fn demo_transfer(&self) -> Option<DemoTransfer> {
+        (self.transfer_demo || self.transfer_to.is_some()).then(|| DemoTransfer {
+            target: self.transfer_to.clone(),
+            overwrite_existing: self.overwrite,
+        })
+    }
+
+    fn backup(&self) -> Option<BackupStore> {
+        (!self.no_backup).then(|| self.store.store())
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let result = match cli.command {
+        Command::Inspect { file } => inspect(&file),
+        Command::Occurrences { file } => list_occurrences(&file),
+        Command::Replace { file, steamid, include_lookalikes, write } => {
+            replace(&file, &steamid, include_lookalikes, &write)
+        }
+        Command::Remove { file, write } => remove(&file, &write),
+        Command::Batch { operation } => run_batch(operation),
+        Command::Personalize { file, steamid, write } => personalize(&file, &steamid, &write),
+        Command::Anonymize { file, output, overwrite, also, dry_run, no_backup, store } => {
+            let options = anonymize::AnonymizeOptions {
+                identifiers: anonymize::Identifiers::local(&also),
+                output,
+                overwrite,
+                backup: (!no_backup).then(|| store.store()),
+                dry_run,
+            };
+            anonymize(&file, &options)
+        }
+        Command::Patch { file, set, apply, save, dry_run, no_backup, store } => {
+            let options = patch::PatchOptions {
+                backup: (!no_backup).then(|| store.store()),
+                dry_run,
+            };
+            patch_save(&file, &set, apply.as_deref(), save.as_deref(), &options)
+        }
+        Command::Set { file, values, dry_run, no_backup, store } => {
+            let options = edit::EditOptions {
+                backup: (!no_backup).then(|| store.store()),
+                dry_run,
+            };
+            set_values(&file, &values, &options)
+        }
+        Command::Export { file, output } => export_save(&file, output.as_deref()),
+        Command::Import { json, file, overwrite, dry_run, no_backup, store } => {
+            let options = export::ImportOptions {
+                backup: (!no_backup).then(|| store.store()),
+                overwrite,
+                dry_run,
+            };
+            import_save(&json, &file, &options)
+        }
+        Command::Convert { steamid } => convert(&steamid),
+        Command::Accounts { steam_dir } => accounts(steam_dir),
+        Command::Backups { file, store } => list_backups(&file, &store.store()),
+        Command::Restore { file, backup, store } => restore(&file, &backup, &store.store()),
+        Command::Diff { file, other, context, store } => {
+            compare(&file, other.as_deref().unwrap_or("1"), context, &store.store())
+        }
+        Command::History { file, store } => history(&file, &store.store()),
+        Command::Undo { file, dry_run, no_backup, store } => undo(&file, dry_run, no_backup, &store),
+        Command::Locate { steam_dir } => locate(steam_dir),
+        Command::Cloud { file, steam_dir, update, store } => cloud(&file, steam_dir, update, &store.store()),
+        Command::Slots { dir } => list_slots(dir),
+        Command::Slot { action } => slot(action),
+        Command::Verify { files } => verify(&files),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e @ (Error::NoSteamIdFound | Error::AlreadyUniversal | Error::NoIdentifiersFound)) => {
+            eprintln!("{}", e);
+            ExitCode::from(EXIT_NOTHING_TO_DO)
+        }
+        Err(e @ (Error::TransferTargetExists(_) | Error::SlotExists(_))) => {
+            eprintln!("Error: {} (pass --overwrite)", e);
+            ExitCode::FAILURE
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn inspect(file: &Path) -> Result<(), Error> {
+    let data = files::read_save(file)?;
+
+    let matches = replace::find_steamid_matches(&data);
+    let steamid = matches.iter().find(|m| m.is_property).map(|m| m.steamid.clone());
+    let owner = remove::find_owner_properties(&data).into_iter().next();
+
+    println!("File: {}", file.display());
+    println!("Size: {} bytes", data.len());
+    match demo::detect(file, &data) {
+        Some(evidence) => println!("Demo save: yes ({})", evidence),
+        None => println!("Demo save: no"),
+    }
+    match &steamid {
+        Some(steamid) => println!("SteamID: {}", steamid),
+        None => println!("SteamID: none"),
+    }
+    match &owner {
+        Some(owner) => println!("Owner property: {} ({})", owner.steamid, owner.location()),
+        None => println!("Owner property: none (universal save)"),
+    }
+
+    for m in &matches {
+        let kind = if m.is_property { "property" } else { "look-alike" };
+        println!("  0x{:08X}  {:<10}  {}  {}", m.offset, kind, m.steamid, m.path.as_deref().unwrap_or("-"));
+    }
+
+    let groups = occurrences::group_steamids(&occurrences::find_occurrences(&data));
+    if !occurrences::is_consistent(&groups) {
+        println!("Warning: save contains {} different SteamIDs:", groups.len());
+        for group in &groups {
+            println!("  {}  {} occurrence(s)", group.steamid, group.offsets.len());
+        }
+        println!("Run `replace` with the one to keep to normalise them.");
+    }
+
+    if steamid.is_none() && owner.is_none() {
+        return Err(Error::NoSteamIdFound);
+    }
+    Ok(())
+}
+
+fn list_occurrences(file: &Path) -> Result<(), Error> {
+    let data = files::read_save(file)?;
+    let occurrences = occurrences::find_occurrences(&data);
+    if occurrences.is_empty() {
+        return Err(Error::NoSteamIdFound);
+    }
+
+    println!("{:<10}  {:<11}  {:<17}  {:<24}  CONTEXT", "OFFSET", "FOUND BY", "STEAMID", "PROPERTY");
+    for o in &occurrences {
+        let property = match (&o.path, o.is_property) {
+            (Some(path), _) => path.as_str(),
+            (None, true) => "?",
+            (None, false) => "(look-alike)",
+        };
+        println!("0x{:08X}  {:<11}  {}  {:<24}  {}", o.offset, o.found_by.to_string(), o.steamid, property, o.context);
+    }
+    Ok(())
+}
+
+fn replace(file: &Path, input: &str, include_lookalikes: bool, write: &WriteArgs) -> Result<(), Error> {
+    let steamid = steamid::parse(input)?.to_string();
+    let steamid = steamid.as_str();
+    if input.trim() != steamid {
+        println!("Using SteamID64 {} for {}", steamid, input.trim());
+    }
+
+    let options = replace::ReplaceOptions {
+        demo_transfer: write.demo_transfer(),
+        backup: write.backup(),
+        dry_run: write.dry_run,
+        include_lookalikes,
+        only_offsets: write.only_offsets(),
+    };
+    let report = replace::replace_steamid_in_file(file, steamid, &options)?;
+
+    if report.previous_steamids == [steamid] {
+        println!("SteamID is already {}", steamid);
+    } else if report.previous_steamids.len() > 1 {
+        let verb = if write.dry_run { "Would normalise" } else { "Normalised" };
+        println!("{} {} different SteamIDs: {}", verb, report.previous_steamids.len(), report.previous_steamids.join(", "));
+    }
+
+    let verb = if write.dry_run { "Would replace" } else { "Replaced" };
+    println!("{} {} occurrence(s) with {}", verb, report.replacements(), steamid);
+    for offset in &report.offsets {
+        println!("  0x{:08X}", offset);
+    }
+    if !report.skipped.is_empty() {
+        println!("Left {} match(es) untouched (use --include-lookalikes or --offset):", report.skipped.len());
+        for skipped in &report.skipped {
+            println!("  0x{:08X}  {}", skipped.offset, skipped.steamid);
+        }
+    }
+    print_write_summary(file, &report.final_path, report.backup_path.as_deref(), report.replaced_backup.as_deref(), write);
+    Ok(())
+}
+
+fn remove(file: &Path, write: &WriteArgs) -> Result<(), Error> {
+    let options = remove::RemoveOptions {
+        demo_transfer: write.demo_transfer(),
+        backup: write.backup(),
+        dry_run: write.dry_run,
+        only_offsets: write.only_offsets(),
+    };
+    let report = remove::remove_steamid_in_file(file, &options)?;
+
+    let verb = if write.dry_run { "Would remove" } else { "Removed" };
+    for property in &report.removed {
+        println!("{} {} at {}", verb, property.steamid, property.location());
+    }
+    print_write_summary(file, &report.final_path, report.backup_path.as_deref(), report.replaced_backup.as_deref(), write);
+    Ok(())
+}
+
+fn personalize(file: &Path, input: &str, write: &WriteArgs) -> Result<(), Error> {
+    let steamid = steamid::parse(input)?.to_string();
+    if input.trim() != steamid {
+        println!("Using SteamID64 {} for {}", steamid, input.trim());
+    }
+
+    let options = personalize::PersonalizeOptions {
+        demo_transfer: write.demo_transfer(),
+        backup: write.backup(),
+        dry_run: write.dry_run,
+        only_offsets: write.only_offsets(),
+    };
+    let report = personalize::personalize_in_file(file, &steamid, &options)?;
+
+    let verb = if write.dry_run { "Would write" } else { "Wrote" };
+    println!("{} {} into {} owner propert(ies)", verb, steamid, report.offsets.len());
+    for offset in &report.offsets {
+        println!("  0x{:08X}", offset);
+    }
+    print_write_summary(file, &report.final_path, report.backup_path.as_deref(), report.replaced_backup.as_deref(), write);
+    Ok(())
+}
+
+fn anonymize(file: &Path, options: &anonymize::AnonymizeOptions) -> Result<(), Error> {
+    let report = anonymize::anonymize_file(file, options)?;
+
+    let verb = if options.dry_run { "Would scrub" } else { "Scrubbed" };
+    println!("{} {} identifier(s):", verb, report.scrubbed.len());
+    for scrubbed in &report.scrubbed {
+        let encoding = if scrubbed.wide { "UTF-16" } else { "UTF-8" };
+        println!(
+            "  0x{:08X}  {:<13} {:<6} {} -> {}{}",
+            scrubbed.offset,
+            scrubbed.kind.to_string(),
+            encoding,
+            scrubbed.original,
+            scrubbed.placeholder,
+            scrubbed.path.as_deref().map(|path| format!("  ({})", path)).unwrap_or_default()
+        );
+    }
+    if let Some(backup_path) = &report.backup_path {
+        println!("Backup saved as: {}", backup_path.display());
+    }
+    if options.dry_run {
+        println!("Dry run: no files were changed");
+    } else {
+        println!("Anonymized save: {}", report.final_path.display());
+    }
+    Ok(())
+}
+
+fn run_batch(command: BatchCommand) -> Result<(), Error> {
+    let (operation, paths, args) = match command {
+        BatchCommand::Replace { steamid, paths, include_lookalikes, batch } => {
+            let steamid = steamid::parse(&steamid)?.to_string();
+            (batch::BatchOperation::Replace { steamid, include_lookalikes }, paths, batch)
+        }
+        BatchCommand::Remove { paths, batch } => (batch::BatchOperation::Remove, paths, batch),
+    };
+    let options = batch::BatchOptions {
+        operation,
+        backup: (!args.no_backup).then(|| args.store.store()),
+        dry_run: args.dry_run,
+        jobs: args.jobs,
+    };
+
+    let paths = if paths.is_empty() {
+        vec![slots::find_save_dir().ok_or(Error::SaveDirNotFound)?]
+    } else {
+        paths
+    };
+    let saves = batch::collect_saves(&paths)?;
+    let results = batch::run(&saves, &options);
+    for result in &results {
+        println!("{}: {}", result.path.display(), result.outcome);
+    }
+
+    let summary = batch::BatchSummary::of(&results);
+    println!("{}", summary);
+    if args.dry_run {
+        println!("Dry run: no files were changed");
+    }
+
+    if summary.failed > 0 {
+        Err(Error::BatchFailed(summary.failed))
+    } else if summary.changed == 0 {
+        Err(match options.operation {
+            batch::BatchOperation::Remove => Error::AlreadyUniversal,
+            batch::BatchOperation::Replace { .. } => Error::NoSteamIdFound,
+        })
+    } else {
+        Ok(())
+    }
+}
+
+fn patch_save(
+    file: &Path,
+    set: &[(usize, Vec<u8>)],
+    apply: Option<&Path>,
+    save: Option<&Path>,
+    options: &patch::PatchOptions,
+) -> Result<(), Error> {
+    let mut patch = match apply {
+        Some(path) => patch::Patch::load(path)?,
+        None => patch::Patch::default(),
+    };
+    // Edits given here expect whatever the file holds after the loaded ones.
+    if !set.is_empty() {
+        let mut data = files::read_save(file)?;
+        if !patch.edits.is_empty() {
+            data = patch::apply(&data, &patch)?;
+        }
+        for (offset, bytes) in set {
+            let edit = patch::PatchEdit::at(&data, *offset, bytes.clone())?;
+            data[edit.offset..edit.offset + edit.new.len()].copy_from_slice(&edit.new);
+            patch.edits.push(edit);
+        }
+    }
+
+    let report = patch::patch_file(file, &patch, options)?;
+    let verb = if options.dry_run { "Would apply" } else { "Applied" };
+    println!("{} {} edit(s) to {}", verb, report.edits, file.display());
+    for edit in &patch.edits {
+        println!("  0x{:08X}  {} -> {}", edit.offset, hex::format_bytes(&edit.old), hex::format_bytes(&edit.new));
+    }
+    if let Some(backup_path) = &report.backup_path {
+        println!("Backup saved as: {}", backup_path.display());
+    }
+    if let Some(save) = save {
+        patch.save(save)?;
+        println!("Patch saved as: {}", save.display());
+    }
+    if options.dry_run {
+        println!("Dry run: the save was not changed");
+    }
+    Ok(())
+}
+
+fn set_values(file: &Path, values: &[edit::ValueEdit], options: &edit::EditOptions) -> Result<(), Error> {
+    let report = edit::edit_file(file, values, options)?;
+    if report.edits.is_empty() {
+        println!("{} already has these values; nothing to write", file.display());
+        return Ok(());
+    }
+    let verb = if options.dry_run { "Would set" } else { "Set" };
+    println!("{} {} value(s) in {}", verb, report.edits.len(), file.display());
+    for edit in &report.edits {
+        println!(
+            "  {}  {} -> {}",
+            edit.path.as_deref().unwrap_or_default(),
+            edit.old.as_deref().unwrap_or_default(),
+            edit.new.as_deref().unwrap_or_default()
+        );
+    }
+    if let Some(backup_path) = &report.backup_path {
+        println!("Backup saved as: {}", backup_path.display());
+    }
+    if options.dry_run {
+        println!("Dry run: the save was not changed");
+    }
+    Ok(())
+}
+
+fn export_save(file: &Path, output: Option<&Path>) -> Result<(), Error> {
+    match output {
+        Some(output) => {
+            export::export_file(file, output)?;
+            println!("Exported {} to {}", file.display(), output.display());
+        }
+        None => println!("{}", export::export(&files::read_save(file)?)?),
+    }
+    Ok(())
+}
+
+fn import_save(json: &Path, file: &Path, options: &export::ImportOptions) -> Result<(), Error> {
+    let report = export::import_file(json, file, options)?;
+    if report.unchanged {
+        println!("{} already matches {}; nothing to write", file.display(), json.display());
+        return Ok(());
+    }
+    let verb = match (options.dry_run, report.replaced) {
+        (true, true) => "Would replace",
+        (true, false) => "Would create",
+        (false, true) => "Replaced",
+        (false, false) => "Created",
+    };
+    println!("{} {} ({} bytes) from {}", verb, file.display(), report.size, json.display());
+    if let Some(backup_path) = &report.backup_path {
+        println!("Backup saved as: {}", backup_path.display());
+    }
+    if options.dry_run {
+        println!("Dry run: nothing was written");
+    }
+    Ok(())
+}
+
+fn convert(input: &str) -> Result<(), Error> {
+    let id = steamid::parse(input)?;
+    println!("SteamID64:  {}", id);
+    println!("SteamID2:   {}", id.steam2());
+    println!("SteamID3:   {}", id.steam3());
+    println!("Account ID: {}", id.account_id());
+    println!("Profile:    {}", id.profile_url());
+    Ok(())
+}
+
+fn accounts(steam_dir: Option<PathBuf>) -> Result<(), Error> {
+    let steam_dir = steam_dir.or_else(steam::find_steam_dir).ok_or(Error::SteamNotFound)?;
+
+    let accounts = steam::load_accounts(&steam_dir)?;
+    println!("Steam: {}", steam_dir.display());
+    for account in &accounts {
+        let recent = if account.most_recent { "  (most recent)" } else { "" };
+        println!("  {}  {}{}", account.steamid, account.label(), recent);
+    }
+    if accounts.is_empty() {
+        println!("  no accounts have logged in");
+    }
+    Ok(())
+}
+
+fn list_backups(file: &Path, store: &BackupStore) -> Result<(), Error> {
+    let entries = store.list(file)?;
+    println!("Backups in {}:", store.dir_for(file).display());
+    for (index, entry) in entries.iter().enumerate() {
+        let steamid = entry.steamid().unwrap_or_else(|| "universal".to_string());
+        println!(
+            "  {:>3}  {}  {:<17}  {:>8} bytes  {}",
+            index + 1,
+            backup::format_time(entry.timestamp),
+            steamid,
+            entry.size,
+            entry.hash
+        );
+    }
+    if entries.is_empty() {
+        println!("  none");
+    }
+    Ok(())
+}
+
+/// A backup given as a path or as its number in the `backups` list.
+fn backup_path(file: &Path, backup: &str, store: &BackupStore) -> Result<PathBuf, Error> {
+    match backup.parse::<usize>() {
+        Ok(number) => store
+            .list(file)?
+            .into_iter()
+            .nth(number.wrapping_sub(1))
+            .map(|entry| entry.path)
+            .ok_or(Error::BackupNotFound(backup.to_string())),
+        Err(_) => Ok(PathBuf::from(backup)),
+    }
+}
+
+fn restore(file: &Path, backup: &str, store: &BackupStore) -> Result<(), Error> {
+    let backup_path = backup_path(file, backup, store)?;
+    let previous = store.restore(&backup_path, file)?;
+    println!("Restored {} from {}", file.display(), backup_path.display());
+    if let Some(previous) = previous {
+        println!("Previous contents saved as: {}", previous.display());
+    }
+    Ok(())
+}
+
+/// Bytes per side of a `diff` row.
+const DIFF_WIDTH: usize = 8;
+
+fn compare(file: &Path, other: &str, context: usize, store: &BackupStore) -> Result<(), Error> {
+    let other = backup_path(file, other, store)?;
+    let old = files::read_save(&other)?;
+    let new = files::read_save(file)?;
+    let diff = diff::diff(&old, &new);
+
+    println!("--- {} ({} bytes)", other.display(), old.len());
+    println!("+++ {} ({} bytes)", file.display(), new.len());
+    println!("{}", diff.summary());
+    for hunk in &diff.hunks {
+        println!("  {}", hunk);
+    }
+    if diff.is_identical() {
+        return Ok(());
+    }
+
+    println!();
+    let rows = diff.rows(&old, &new, DIFF_WIDTH);
+    for visible in diff::visible_rows(&rows, context) {
+        let Some(row) = visible.map(|index| &rows[index]) else {
+            println!("  ...");
+            continue;
+        };
+        let marker = match row.cells.iter().map(|cell| cell.kind).find(|&kind| kind != diff::CellKind::Same) {
+            None => ' ',
+            Some(diff::CellKind::Removed) => '-',
+            Some(diff::CellKind::Added) => '+',
+            Some(_) => '~',
+        };
+        let (old_side, old_carets) = diff_side(row.old_offset, row.cells.iter().map(|cell| (cell.old, cell.kind)));
+        let (new_side, new_carets) = diff_side(row.new_offset, row.cells.iter().map(|cell| (cell.new, cell.kind)));
+        println!("{} {}   {}", marker, old_side, new_side);
+        if row.has_changes() {
+            println!("{}", format!("  {}   {}", old_carets, new_carets).trim_end());
+        }
+    }
+    Ok(())
+}
+
+/// `00000C38  30 30 -- ...  |00 ...|` for one side of a row, and a line with
+/// `^^` under the bytes that differ.
+fn diff_side(offset: usize, cells: impl Iterator<Item = (Option<u8>, diff::CellKind)>) -> (String, String) {
+    let mut hex = String::new();
+    let mut ascii = String::new();
+    let mut carets = String::new();
+    for (byte, kind) in cells {
+        match byte {
+            Some(byte) => {
+                hex.push_str(&format!(" {:02X}", byte));
+                ascii.push(if byte.is_ascii_graphic() || byte == b' ' { byte as char } else { '.' });
+            }
+            None => {
+                hex.push_str(" --");
+                ascii.push(' ');
+            }
+        }
+        carets.push_str(if kind == diff::CellKind::Same { "   " } else { " ^^" });
+    }
+    let side = format!("{:08X} {:<width$}  |{:<ascii_width$}|", offset, hex, ascii, width = DIFF_WIDTH * 3, ascii_width = DIFF_WIDTH);
+    let carets = format!("{:8} {:<width$}  {:ascii_width$}", "", carets, "", width = DIFF_WIDTH * 3, ascii_width = DIFF_WIDTH + 2);
+    (side, carets)
+}
+
+fn history(file: &Path, store: &BackupStore) -> Result<(), Error> {
+    let entries = manifest::history(store, file)?;
+    println!("Operations recorded in {}:", manifest::manifest_path(store, file).display());
+    for (index, entry) in entries.iter().enumerate() {
+        println!("  {:>3}  {}  {}", index + 1, backup::format_time(entry.timestamp), entry.summary());
+        for edit in &entry.edits {
+            let location = edit.path.as_deref().unwrap_or("-");
+            println!("         0x{:08X}  {}", edit.offset, location);
+        }
+        println!("         SHA-256 {} -> {}", entry.sha256_before, entry.sha256_after);
+        if let Some(transferred_to) = &entry.transferred_to {
+            println!("         transferred from {} to {}", entry.file.display(), transferred_to.display());
+        }
+        if let Some(backup_path) = &entry.backup {
+            println!("         backup {}", backup_path.display());
+        }
+    }
+    if entries.is_empty() {
+        println!("  none");
+    }
+    Ok(())
+}
+
+fn undo(file: &Path, dry_run: bool, no_backup: bool, store: &BackupArgs) -> Result<(), Error> {
+    let options = manifest::UndoOptions {
+        backup: (!no_backup).then(|| store.store()),
+        dry_run,
+    };
+    let report = manifest::undo(file, &options)?;
+
+    let verb = if dry_run { "Would undo" } else { "Undid" };
+    let source = if report.from_backup { "from its backup" } else { "from the recorded edits" };
+    println!("{} {} ({}), {}", verb, report.undone.summary(), backup::format_time(report.undone.timestamp), source);
+    if let Some(backup_path) = &report.backup_path {
+        println!("Backup saved as: {}", backup_path.display());
+    }
+    if dry_run {
+        println!("Dry run: no files were changed");
+    }
+    Ok(())
+}
+
+fn locate(steam_dir: Option<PathBuf>) -> Result<(), Error> {
+    let mut save_dirs = slots::default_save_dirs();
+    let steam_dirs = match steam_dir {
+        Some(steam_dir) => {
+            for dir in steam::proton_save_dirs(&steam_dir, steam::APP_ID) {
+                if !save_dirs.contains(&dir) {
+                    save_dirs.push(dir);
+                }
+            }
+            vec![steam_dir]
+        }
+        None => steam::installed_steam_dirs(),
+    };
+
+    for steam_dir in &steam_dirs {
+        println!("Steam: {}", steam_dir.display());
+        for library in steam::library_folders(steam_dir) {
+            println!("  Library: {}", library.display());
+        }
+    }
+    if steam_dirs.is_empty() {
+        println!("Steam: not found");
+    }
+
+    for dir in &save_dirs {
+        println!("SaveGames: {}", dir.display());
+    }
+    if save_dirs.is_empty() {
+        return Err(Error::SaveDirNotFound);
+    }
+    Ok(())
+}
+
+fn cloud(file: &Path, steam_dir: Option<PathBuf>, update: bool, store: &BackupStore) -> Result<(), Error> {
+    let steam_dirs = match steam_dir {
+        Some(steam_dir) => vec![steam_dir],
+        None => steam::installed_steam_dirs(),
+    };
+    if steam_dirs.is_empty() {
+        return Err(Error::SteamNotFound);
+    }
+
+    let caches = cloud::caches_tracking(file, &steam_dirs, steam::APP_ID);
+    if caches.is_empty() {
+        println!("{}: {}", file.display(), cloud::CloudStatus::Untracked);
+        return Ok(());
+    }
+
+    for mut cache in caches {
+        let account = cache.account_id.map(|id| id.to_string()).unwrap_or_else(|| "?".to_string());
+        println!("Account {}: {}", account, cache.path.display());
+        let status = cache.check(file)?;
+        println!("  {}", status);
+
+        if update && matches!(status, cloud::CloudStatus::Changed(_)) {
+            cache.update(file)?;
+            let backup_path = cache.save(file, Some(store))?;
+            println!("  Updated the record; Steam will upload this save on next launch");
+            if let Some(backup_path) = backup_path {
+                println!("  Backup saved as: {}", backup_path.display());
+            }
+        } else if matches!(status, cloud::CloudStatus::Changed(_)) {
+            println!("  Steam may restore the cloud copy; run with --update while Steam is closed");
+        }
+    }
+    Ok(())
+}
+
+fn list_slots(dir: Option<PathBuf>) -> Result<(), Error> {
+    let dir = dir.or_else(slots::find_save_dir).ok_or(Error::SaveDirNotFound)?;
+
+    let slots = slots::list(&dir)?;
+    println!("Slots in {}:", dir.display());
+    for slot in &slots {
+        let modified = slot.modified.map(backup::format_time).unwrap_or_else(|| "-".to_string());
+        let kind = match &slot.demo {
+            Some(evidence) => format!("demo ({})", evidence),
+            None => "full".to_string(),
+        };
+        println!(
+            "  {:<24}  {:<17}  {:>8} bytes  {}  {}",
+            slot.name(),
+            slot.steamid.as_deref().unwrap_or("universal"),
+            slot.size,
+            modified,
+            kind
+        );
+    }
+    if slots.is_empty() {
+        println!("  none");
+    }
+    Ok(())
+}
+
+fn slot(action: SlotAction) -> Result<(), Error> {
+    match action {
+        SlotAction::Copy { file, name, overwrite, store } => {
+            let (target, replaced) = slots::duplicate(&file, &name, &store.store(), overwrite)?;
+            println!("Copied {} to {}", file.display(), target.display());
+            if let Some(replaced) = replaced {
+                println!("Replaced save backed up as: {}", replaced.display());
+            }
+        }
+        SlotAction::Swap { first, second, store } => {
+            let backups = slots::swap(&first, &second, &store.store())?;
+            println!("Swapped {} and {}", first.display(), second.display());
+            for backup_path in &backups {
+                println!("Backup saved as: {}", backup_path.display());
+            }
+        }
+        SlotAction::Rename { file, name } => {
+            let target = slots::rename(&file, &name)?;
+            println!("Renamed {} to {}", file.display(), target.display());
+        }
+        SlotAction::Delete { file, store } => {
+            let backup_path = slots::delete(&file, &store.store())?;
+            println!("Deleted {}", file.display());
+            println!("Backup saved as: {}", backup_path.display());
+        }
+    }
+    Ok(())
+}
+
+fn verify(paths: &[PathBuf]) -> Result<(), Error> {
+    let mut first_error = None;
+
+    for path in paths {
+        let result = files::read_save(path).and_then(|data| SaveGame::parse_lossless(&data));
+        match result {
+            Ok(save) => println!("OK    {} ({} properties)", path.display(), save.walk().len()),
+            Err(e) => {
+                println!("FAIL  {}: {}", path.display(), e);
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+
+    match first_error {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
+fn parse_offset(s: &str) -> Result<usize, String> {
+    hex::parse_offset(s).ok_or_else(|| format!("invalid offset '{}': expected a decimal or 0x hex number", s))
+}
+
+/// `OFFSET=HEX` as given to `patch --set`.
+fn parse_patch_edit(s: &str) -> Result<(usize, Vec<u8>), String> {
+    let (offset, bytes) = s.split_once('=').ok_or_else(|| format!("expected OFFSET=HEX, got '{}'", s))?;
+    let offset = parse_offset(offset.trim())?;
+    let bytes = hex::parse_bytes(bytes.trim_matches(|c: char| c == '"' || c.is_whitespace())).map_err(|e| e.to_string())?;
+    Ok((offset, bytes))
+}
+
+fn print_write_summary(
+    file: &Path,
+    final_path: &Path,
+    backup_path: Option<&Path>,
+    replaced_backup: Option<&Path>,
+    write: &WriteArgs,
+) {
+    let dry_run = write.dry_run;
+    if let Some(backup_path) = backup_path {
+        println!("Backup saved as: {}", backup_path.display());
+    }
+    if final_path != file {
+        let verb = if dry_run { "Would transfer" } else { "Transferred" };
+        println!("{} demo save to: {}", verb, final_path.display());
+        if dry_run && final_path.exists() {
+            if write.overwrite {
+                println!("Would replace the existing {} after backing it up", final_path.display());
+            } else {
+                println!("{} already exists; pass --overwrite to replace it", final_path.display());
+            }
+        }
+    }
+    if let Some(replaced_backup) = replaced_backup {
+        println!("Replaced save backed up as: {}", replaced_backup.display());
+    }
+    if dry_run {
+        println!("Dry run: no files were changed");
+    }
+}
+
+/// `PATH=VALUE` as given to `set`.
+fn parse_value_edit(s: &str) -> Result<edit::ValueEdit, String> {
+    let (path, value) = s.split_once('=').ok_or_else(|| format!("expected PATH=VALUE, got '{}'", s))?;
+    Ok(edit::ValueEdit {
+        path: path.trim().to_string(),
+        value: value.to_string(),
+    })
+}
@@ -0,0 +1,275 @@
+//! Steam Cloud bookkeeping for the game's saves.
+//!
+//! Steam records every synced file in `userdata/<account id>/<app id>/remotecache.vdf`
+//! with its size, SHA-1 and timestamps. When an edited save no longer matches
+//! that record, Steam may decide the cloud copy wins and download it over the
+//! edit on the next launch. Updating the record makes Steam upload the edit instead.
+
+use crate::backup::BackupStore;
+use crate::error::{Error, Result};
+use crate::files;
+use crate::vdf::{self, Vdf};
+use sha1::{Digest, Sha1};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+const REMOTE_CACHE: &str = "remotecache.vdf";
+
+/// What `remotecache.vdf` records about one file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CloudEntry {
+    /// File name relative to the app's cloud root, as Steam stores it.
+    pub key: String,
+    pub size: Option<u64>,
+    /// Lowercase hex SHA-1.
+    pub sha: Option<String>,
+    /// Unix time of the local file when Steam last synced it.
+    pub local_time: Option<u64>,
+    pub remote_time: Option<u64>,
+}
+
+/// One way the local save differs from its cloud record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mismatch {
+    Size { recorded: u64, actual: u64 },
+    Hash { recorded: String, actual: String },
+    Time { recorded: u64, actual: u64 },
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Mismatch::Size { recorded, actual } => write!(f, "size {} recorded, {} on disk", recorded, actual),
+            Mismatch::Hash { recorded, actual } => write!(f, "SHA-1 {} recorded, {} on disk", recorded, actual),
+            Mismatch::Time { recorded, actual } => write!(f, "time {} recorded, {} on disk", recorded, actual),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CloudStatus {
+    /// Steam Cloud does not know this file.
+    Untracked,
+    InSync,
+    /// Steam may replace the local file with the cloud copy.
+    Changed(Vec<Mismatch>),
+}
+
+impl fmt::Display for CloudStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CloudStatus::Untracked => write!(f, "not tracked by Steam Cloud"),
+            CloudStatus::InSync => write!(f, "matches the Steam Cloud record"),
+            CloudStatus::Changed(mismatches) => {
+                let mismatches: Vec<String> = mismatches.iter().map(Mismatch::to_string).collect();
+                write!(f, "differs from the Steam Cloud record ({})", mismatches.join("; "))
+            }
+        }
+    }
+}
+
+/// A loaded `remotecache.vdf`.
+#[derive(Debug, Clone)]
+pub struct RemoteCache {
+    pub path: PathBuf,
+    /// Steam account the cache belongs to, from its `userdata` folder.
+    pub account_id: Option<u32>,
+    root: Vdf,
+}
+
+impl RemoteCache {
+    pub fn load(path: &Path) -> Result<Self> {
+        let root = vdf::read(path)?;
+        let account_id = path
+            .parent()
+            .and_then(Path::parent)
+            .and_then(Path::file_name)
+            .and_then(|name| name.to_str()?.parse().ok());
+        Ok(Self {
+            path: path.to_path_buf(),
+            account_id,
+            root,
+        })
+    }
+
+    /// Every file recorded in the cache.
+    pub fn entries(&self) -> Vec<CloudEntry> {
+        self.app()
+            .map(|app| {
+                app.entries()
+                    .iter()
+                    .filter(|(_, value)| matches!(value, Vdf::Object(_)))
+                    .map(|(key, value)| entry(key, value))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// The record for the file with the same name as `save`, if any.
+    pub fn entry_for(&self, save: &Path) -> Option<CloudEntry> {
+        let key = self.key_for(save)?;
+        self.entries().into_iter().find(|entry| entry.key == key)
+    }
+
+    /// Compares `save` on disk with its record.
+    pub fn check(&self, save: &Path) -> Result<CloudStatus> {
+        let Some(entry) = self.entry_for(save) else {
+            return Ok(CloudStatus::Untracked);
+        };
+        let actual = FileState::read(save)?;
+
+        let mut mismatches = Vec::new();
+        if let Some(recorded) = entry.size
+            && recorded != actual.size
+        {
+            mismatches.push(Mismatch::Size { recorded, actual: actual.size });
+        }
+        if let Some(recorded) = entry.sha
+            && !recorded.eq_ignore_ascii_case(&actual.sha)
+        {
+            mismatches.push(Mismatch::Hash { recorded, actual: actual.sha });
+        }
+        if let (Some(recorded), Some(modified)) = (entry.local_time, actual.modified)
+            && recorded != modified
+        {
+            mismatches.push(Mismatch::Time { recorded, actual: modified });
+        }
+
+        if mismatches.is_empty() {
+            Ok(CloudStatus::InSync)
+        } else {
+            Ok(CloudStatus::Changed(mismatches))
+        }
+    }
+
+    /// Records the current size, SHA-1 and time of `save`, so Steam treats
+    /// the local file as the newest version. Returns `false` if the save is
+    /// not tracked. Call [`RemoteCache::save`] to write the change.
+    pub fn update(&mut self, save: &Path) -> Result<bool> {
+        let Some(key) = self.key_for(save) else {
+            return Ok(false);
+        };
+        let actual = FileState::read(save)?;
+
+        let Some(record) = self.app_mut().and_then(|app| app.get_mut(&key)) else {
+            return Ok(false);
+        };
+        record.set("size", actual.size.to_string());
+        record.set("sha", actual.sha);
+        if let Some(modified) = actual.modified {
+            record.set("localtime", modified.to_string());
+            record.set("time", modified.to_string());
+        }
+        Ok(true)
+    }
+
+    /// Writes the cache back, backing up the old file first if a store is given.
+    /// The backup goes where the store keeps the backups of `save`, the file
+    /// whose record changed, so nothing is added to Steam's `userdata` tree.
+    /// Steam must not be running, or it will overwrite the file on exit.
+    pub fn save(&self, save: &Path, backup: Option<&BackupStore>) -> Result<Option<PathBuf>> {
+        let backup_path = match backup {
+            Some(store) if self.path.exists() => {
+                let store = BackupStore {
+                    dir: Some(store.dir_for(save)),
+                    ..store.clone()
+                };
+                Some(store.create(&self.path)?)
+            }
+            _ => None,
+        };
+        files::write_save(&self.path, vdf::to_text(&self.root).as_bytes())?;
+        Ok(backup_path)
+    }
+
+    /// The app node: the single top-level object, keyed by the app ID.
+    fn app(&self) -> Option<&Vdf> {
+        self.root.entries().first().map(|(_, app)| app)
+    }
+
+    fn app_mut(&mut self) -> Option<&mut Vdf> {
+        match &mut self.root {
+            Vdf::Object(entries) => entries.first_mut().map(|(_, app)| app),
+            Vdf::Value(_) => None,
+        }
+    }
+
+    /// Keys may contain a sub folder; match on the file name.
+    fn key_for(&self, save: &Path) -> Option<String> {
+        let name = save.file_name()?.to_str()?;
+        self.app()?
+            .entries()
+            .iter()
+            .filter(|(_, value)| matches!(value, Vdf::Object(_)))
+            .map(|(key, _)| key)
+            .find(|key| {
+                key.rsplit(['/', '\\'])
+                    .next()
+                    .is_some_and(|file| file.eq_ignore_ascii_case(name))
+            })
+            .cloned()
+    }
+}
+
+/// Every `userdata/<account id>/<app_id>/remotecache.vdf` under `steam_dir`.
+pub fn remote_caches(steam_dir: &Path, app_id: u32) -> Vec<PathBuf> {
+    let Ok(read_dir) = std::fs::read_dir(steam_dir.join("userdata")) else {
+        return Vec::new();
+    };
+    let mut caches: Vec<PathBuf> = read_dir
+        .flatten()
+        .map(|account| account.path().join(app_id.to_string()).join(REMOTE_CACHE))
+        .filter(|path| path.is_file())
+        .collect();
+    caches.sort();
+    caches
+}
+
+/// The caches under any of `steam_dirs` that record a file named like `save`.
+pub fn caches_tracking(save: &Path, steam_dirs: &[PathBuf], app_id: u32) -> Vec<RemoteCache> {
+    steam_dirs
+        .iter()
+        .flat_map(|steam_dir| remote_caches(steam_dir, app_id))
+        .filter_map(|path| RemoteCache::load(&path).ok())
+        .filter(|cache| cache.entry_for(save).is_some())
+        .collect()
+}
+
+pub fn sha1_hex(data: &[u8]) -> String {
+    hex::encode(Sha1::digest(data))
+}
+
+fn entry(key: &str, value: &Vdf) -> CloudEntry {
+    let number = |name: &str| value.get_str(name).and_then(|v| v.parse().ok());
+    CloudEntry {
+        key: key.to_string(),
+        size: number("size"),
+        sha: value.get_str("sha").map(str::to_lowercase),
+        local_time: number("localtime"),
+        remote_time: number("remotetime"),
+    }
+}
+
+struct FileState {
+    size: u64,
+    sha: String,
+    modified: Option<u64>,
+}
+
+impl FileState {
+    fn read(path: &Path) -> Result<Self> {
+        let data = files::read_save(path)?;
+        let modified = std::fs::metadata(path)
+            .and_then(|m| m.modified())
+            .map_err(Error::io("Failed to read file time"))?
+            .duration_since(UNIX_EPOCH)
+            .ok()
+            .map(|duration| duration.as_secs());
+        Ok(Self {
+            size: data.len() as u64,
+            sha: sha1_hex(&data),
+            modified,
+        })
+    }
+}
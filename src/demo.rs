@@ -0,0 +1,120 @@
+//! Recognising demo saves and choosing where a transferred demo save goes.
+
+use crate::gvas::SaveGame;
+use crate::replace;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+const DEMO_MARKER: &str = "Demo00";
+
+/// Why a save is considered a demo save.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DemoEvidence {
+    /// The file name contains `Demo00`.
+    FileName,
+    /// The save game class in the header names a demo class.
+    SaveClass(String),
+}
+
+impl fmt::Display for DemoEvidence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DemoEvidence::FileName => write!(f, "file name contains '{}'", DEMO_MARKER),
+            DemoEvidence::SaveClass(class) => write!(f, "save class {}", class),
+        }
+    }
+}
+
+/// How a demo save is moved into a full-game slot after editing.
+#[derive(Debug, Clone, Default)]
+pub struct DemoTransfer {
+    /// Slot to move to; `None` uses [`default_target`].
+    pub target: Option<PathBuf>,
+    /// Replace the target if it already exists. It is backed up first.
+    pub overwrite_existing: bool,
+}
+
+/// A possible destination for a demo transfer.
+#[derive(Debug, Clone)]
+pub struct TransferTarget {
+    pub path: PathBuf,
+    pub exists: bool,
+    /// SteamID held by the existing file, if any.
+    pub steamid: Option<String>,
+}
+
+/// Looks at the save game class in the header first, then at the file name.
+/// Property names are not evidence: a full-game save can carry progress
+/// properties named after the demo. Unparseable saves fall back to the name.
+pub fn detect(path: &Path, data: &[u8]) -> Option<DemoEvidence> {
+    if let Ok(save) = SaveGame::parse(data) {
+        let class = save.header.save_game_class_name.as_str();
+        // `/Script/SB.SBDemoSaveGame`: only the class itself, not its package.
+        let class_name = class.rsplit(['.', '/']).next().unwrap_or(class);
+        if class_name.contains("Demo") {
+            return Some(DemoEvidence::SaveClass(class.to_string()));
+        }
+    }
+
+    has_demo_name(path).then_some(DemoEvidence::FileName)
+}
+
+pub fn is_demo_save(path: &Path, data: &[u8]) -> bool {
+    detect(path, data).is_some()
+}
+
+pub fn has_demo_name(path: &Path) -> bool {
+    path.file_name()
+        .is_some_and(|name| name.to_string_lossy().contains(DEMO_MARKER))
+}
+
+/// Where a `Demo00` save is moved so the full game picks it up. The remover
+/// keeps the slot digits (`Demo00` -> `00`), the replacer drops the marker entirely.
+pub fn default_target(path: &Path, remove_demo: bool) -> Option<PathBuf> {
+    let filename = path.file_name()?.to_string_lossy();
+
+    if !filename.contains(DEMO_MARKER) {
+        return None;
+    }
+
+    let new_filename = if remove_demo {
+        filename.replace(DEMO_MARKER, "00")
+    } else {
+        filename.replace(DEMO_MARKER, "")
+    };
+
+    Some(path.parent()?.join(new_filename))
+}
+
+/// The default slot followed by every other non-demo `.sav` next to `path`.
+pub fn target_candidates(path: &Path, remove_demo: bool) -> Vec<TransferTarget> {
+    let mut paths: Vec<PathBuf> = default_target(path, remove_demo).into_iter().collect();
+
+    if let Some(dir) = path.parent()
+        && let Ok(read_dir) = std::fs::read_dir(if dir.as_os_str().is_empty() { Path::new(".") } else { dir })
+    {
+        let mut others: Vec<PathBuf> = read_dir
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|other| {
+                other.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("sav"))
+                    && other.file_name() != path.file_name()
+                    && !has_demo_name(other)
+                    && !paths.iter().any(|p| p.file_name() == other.file_name())
+            })
+            .collect();
+        others.sort();
+        paths.extend(others.into_iter().map(|other| path.with_file_name(other.file_name().unwrap_or_default())));
+    }
+
+    paths.into_iter().map(describe_target).collect()
+}
+
+pub fn describe_target(path: PathBuf) -> TransferTarget {
+    let steamid = std::fs::read(&path).ok().and_then(|data| replace::find_steamid(&data));
+    TransferTarget {
+        exists: path.exists(),
+        steamid,
+        path,
+    }
+}
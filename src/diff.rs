@@ -0,0 +1,322 @@
+//! Byte-level comparison of two saves, e.g. a save and one of its backups.
+//!
+//! Most edits overwrite bytes in place, but removing a SteamID shortens the
+//! file and writing one back lengthens it, so comparing offset by offset would
+//! report everything after the edit as changed. The files are aligned with
+//! Myers' algorithm instead, and the differences are reported as hunks.
+
+use std::fmt;
+
+/// Edits beyond this many inserted plus deleted bytes are not aligned; the
+/// span between the first and last difference is reported as one hunk.
+const MAX_EDIT_DISTANCE: usize = 2048;
+
+/// A range of the old file replaced by a range of the new one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hunk {
+    pub old_offset: usize,
+    pub old_len: usize,
+    pub new_offset: usize,
+    pub new_len: usize,
+}
+
+impl Hunk {
+    /// Bytes overwritten in place.
+    pub fn changed(&self) -> usize {
+        self.old_len.min(self.new_len)
+    }
+
+    pub fn added(&self) -> usize {
+        self.new_len.saturating_sub(self.old_len)
+    }
+
+    pub fn removed(&self) -> usize {
+        self.old_len.saturating_sub(self.new_len)
+    }
+}
+
+impl fmt::Display for Hunk {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "0x{:08X} ({} byte(s)) -> 0x{:08X} ({} byte(s))",
+            self.old_offset, self.old_len, self.new_offset, self.new_len
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DiffSummary {
+    pub hunks: usize,
+    pub changed: usize,
+    pub added: usize,
+    pub removed: usize,
+}
+
+impl fmt::Display for DiffSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.hunks == 0 {
+            return write!(f, "Files are identical");
+        }
+        write!(
+            f,
+            "{} difference(s): {} byte(s) changed, {} added, {} removed",
+            self.hunks, self.changed, self.added, self.removed
+        )
+    }
+}
+
+/// How a byte position differs between the two files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellKind {
+    Same,
+    Changed,
+    /// Only in the old file.
+    Removed,
+    /// Only in the new file.
+    Added,
+}
+
+/// One aligned byte position; a side is `None` where the other file has
+/// bytes the first one lacks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub old: Option<u8>,
+    pub new: Option<u8>,
+    pub kind: CellKind,
+}
+
+/// A row of aligned cells, as shown side by side.
+#[derive(Debug, Clone)]
+pub struct Row {
+    /// Offset of the row's first old byte, or of the next one if it has none.
+    pub old_offset: usize,
+    pub new_offset: usize,
+    pub cells: Vec<Cell>,
+}
+
+impl Row {
+    pub fn has_changes(&self) -> bool {
+        self.cells.iter().any(|cell| cell.kind != CellKind::Same)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SaveDiff {
+    pub old_len: usize,
+    pub new_len: usize,
+    /// Differing ranges, in file order.
+    pub hunks: Vec<Hunk>,
+}
+
+impl SaveDiff {
+    pub fn is_identical(&self) -> bool {
+        self.hunks.is_empty()
+    }
+
+    pub fn summary(&self) -> DiffSummary {
+        let mut summary = DiffSummary {
+            hunks: self.hunks.len(),
+            ..Default::default()
+        };
+        for hunk in &self.hunks {
+            summary.changed += hunk.changed();
+            summary.added += hunk.added();
+            summary.removed += hunk.removed();
+        }
+        summary
+    }
+
+    /// Both files aligned in rows of `width` cells. Inside a hunk the bytes
+    /// are paired up as changed, and the longer side's remainder is shown
+    /// against a gap.
+    pub fn rows(&self, old: &[u8], new: &[u8], width: usize) -> Vec<Row> {
+        let width = width.max(1);
+        let mut rows: Vec<Row> = Vec::new();
+        let (mut x, mut y) = (0, 0);
+        let mut push = |cell: Cell, x: usize, y: usize| {
+            if rows.last().is_none_or(|row| row.cells.len() == width) {
+                rows.push(Row {
+                    old_offset: x,
+                    new_offset: y,
+                    cells: Vec::with_capacity(width),
+                });
+            }
+            if let Some(row) = rows.last_mut() {
+                row.cells.push(cell);
+            }
+        };
+
+        let end = Hunk {
+            old_offset: old.len(),
+            old_len: 0,
+            new_offset: new.len(),
+            new_len: 0,
+        };
+        for hunk in self.hunks.iter().chain([&end]) {
+            while x < hunk.old_offset && y < new.len() {
+                let cell = Cell {
+                    old: Some(old[x]),
+                    new: Some(new[y]),
+                    kind: CellKind::Same,
+                };
+                push(cell, x, y);
+                x += 1;
+                y += 1;
+            }
+            for i in 0..hunk.old_len.max(hunk.new_len) {
+                let old_byte = (i < hunk.old_len).then(|| old[x + i]);
+                let new_byte = (i < hunk.new_len).then(|| new[y + i]);
+                let kind = match (old_byte, new_byte) {
+                    (Some(_), Some(_)) => CellKind::Changed,
+                    (Some(_), None) => CellKind::Removed,
+                    _ => CellKind::Added,
+                };
+                let position = (x + i.min(hunk.old_len), y + i.min(hunk.new_len));
+                push(Cell { old: old_byte, new: new_byte, kind }, position.0, position.1);
+            }
+            x += hunk.old_len;
+            y += hunk.new_len;
+        }
+        rows
+    }
+}
+
+/// Indices of the rows with changes and of up to `context` rows around each,
+/// with `None` where unchanged rows were left out.
+pub fn visible_rows(rows: &[Row], context: usize) -> Vec<Option<usize>> {
+    let mut shown = vec![false; rows.len()];
+    for (index, _) in rows.iter().enumerate().filter(|(_, row)| row.has_changes()) {
+        let start = index.saturating_sub(context);
+        let end = (index + context + 1).min(rows.len());
+        shown[start..end].fill(true);
+    }
+
+    let mut visible = Vec::new();
+    for (index, &is_shown) in shown.iter().enumerate() {
+        if is_shown {
+            if index > 0 && !shown[index - 1] {
+                visible.push(None);
+            }
+            visible.push(Some(index));
+        }
+    }
+    if !visible.is_empty() && !shown.last().copied().unwrap_or(true) {
+        visible.push(None);
+    }
+    visible
+}
+
+/// Compares `old` with `new`.
+pub fn diff(old: &[u8], new: &[u8]) -> SaveDiff {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let a = &old[prefix..old.len() - suffix];
+    let b = &new[prefix..new.len() - suffix];
+
+    let hunks = if a.is_empty() && b.is_empty() {
+        Vec::new()
+    } else {
+        align(a, b).unwrap_or_else(|| {
+            vec![Hunk {
+                old_offset: 0,
+                old_len: a.len(),
+                new_offset: 0,
+                new_len: b.len(),
+            }]
+        })
+    };
+
+    SaveDiff {
+        old_len: old.len(),
+        new_len: new.len(),
+        hunks: hunks
+            .into_iter()
+            .map(|hunk| Hunk {
+                old_offset: hunk.old_offset + prefix,
+                new_offset: hunk.new_offset + prefix,
+                ..hunk
+            })
+            .collect(),
+    }
+}
+
+/// Myers' shortest edit script between `a` and `b`, as hunks, or `None` if it
+/// takes more than [`MAX_EDIT_DISTANCE`] steps.
+fn align(a: &[u8], b: &[u8]) -> Option<Vec<Hunk>> {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    let max = MAX_EDIT_DISTANCE as isize;
+    let index = |k: isize| (k + max + 1) as usize;
+
+    // `v[k]` is the furthest x reached on diagonal k = x - y. Each round only
+    // reads diagonals -d-1..=d+1, so only that slice is kept for backtracking.
+    let mut v = vec![0isize; 2 * MAX_EDIT_DISTANCE + 3];
+    let mut trace: Vec<Vec<isize>> = Vec::new();
+    for d in 0..=max {
+        trace.push(v[index(-d - 1)..=index(d + 1)].to_vec());
+        for k in (-d..=d).step_by(2) {
+            let mut x = if k == -d || (k != d && v[index(k - 1)] < v[index(k + 1)]) {
+                v[index(k + 1)]
+            } else {
+                v[index(k - 1)] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[index(k)] = x;
+            if x >= n && y >= m {
+                return Some(backtrack(&trace, n, m));
+            }
+        }
+    }
+    None
+}
+
+/// Walks the recorded rounds back from the end, merging adjacent single-byte
+/// insertions and deletions into hunks.
+fn backtrack(trace: &[Vec<isize>], n: isize, m: isize) -> Vec<Hunk> {
+    let mut hunks: Vec<Hunk> = Vec::new();
+    let (mut x, mut y) = (n, m);
+    for (d, v) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        let at = |k: isize| v[(k + d + 1) as usize];
+        let k = x - y;
+        let prev_k = if k == -d || (k != d && at(k - 1) < at(k + 1)) { k + 1 } else { k - 1 };
+        let prev_x = at(prev_k);
+        let prev_y = prev_x - prev_k;
+
+        while x > prev_x && y > prev_y {
+            x -= 1;
+            y -= 1;
+        }
+        if d > 0 {
+            let (old_len, new_len) = if x == prev_x { (0, 1) } else { (1, 0) };
+            let (old_offset, new_offset) = (prev_x as usize, prev_y as usize);
+            match hunks.last_mut() {
+                Some(hunk) if old_offset + old_len == hunk.old_offset && new_offset + new_len == hunk.new_offset => {
+                    hunk.old_offset = old_offset;
+                    hunk.new_offset = new_offset;
+                    hunk.old_len += old_len;
+                    hunk.new_len += new_len;
+                }
+                _ => hunks.push(Hunk {
+                    old_offset,
+                    old_len,
+                    new_offset,
+                    new_len,
+                }),
+            }
+        }
+        x = prev_x;
+        y = prev_y;
+    }
+    hunks.reverse();
+    hunks
+}
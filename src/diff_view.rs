@@ -0,0 +1,67 @@
+use std::path::{Path, PathBuf};
+use steamid_replacer::diff::{self, DiffSummary, Row};
+use steamid_replacer::files;
+
+/// Bytes per side of a row in the compare window.
+pub const ROW_WIDTH: usize = 8;
+/// Unchanged rows kept around each change when only changes are shown.
+const CONTEXT_ROWS: usize = 2;
+
+/// Side-by-side comparison of a save with a backup or another save, shown in
+/// its own window.
+#[derive(Default)]
+pub struct DiffView {
+    pub show: bool,
+    /// The backup or other save.
+    pub old_path: PathBuf,
+    /// The save as it is now.
+    pub new_path: PathBuf,
+    pub old_len: usize,
+    pub new_len: usize,
+    pub summary: DiffSummary,
+    pub rows: Vec<Row>,
+    /// Rows listed in the window; `None` marks left-out unchanged rows.
+    pub visible: Vec<Option<usize>>,
+    pub changes_only: bool,
+    pub error: String,
+}
+
+impl DiffView {
+    /// Compares `new_path` against `old_path` and opens the window.
+    pub fn open(&mut self, old_path: &Path, new_path: &Path) {
+        self.old_path = old_path.to_path_buf();
+        self.new_path = new_path.to_path_buf();
+        self.changes_only = true;
+        self.compare();
+        self.show = true;
+    }
+
+    pub fn compare(&mut self) {
+        self.rows.clear();
+        self.visible.clear();
+        self.summary = DiffSummary::default();
+        self.error.clear();
+
+        let (old_data, new_data) = match (files::read_save(&self.old_path), files::read_save(&self.new_path)) {
+            (Ok(old_data), Ok(new_data)) => (old_data, new_data),
+            (Err(e), _) | (_, Err(e)) => {
+                self.error = format!("❌ Error: {}", e);
+                return;
+            }
+        };
+        let save_diff = diff::diff(&old_data, &new_data);
+        self.summary = save_diff.summary();
+        self.rows = save_diff.rows(&old_data, &new_data, ROW_WIDTH);
+        self.old_len = old_data.len();
+        self.new_len = new_data.len();
+        self.update_visible();
+    }
+
+    pub fn update_visible(&mut self) {
+        self.visible = if self.changes_only {
+            diff::visible_rows(&self.rows, CONTEXT_ROWS)
+        } else {
+            (0..self.rows.len()).map(Some).collect()
+        };
+    }
+}
@@ -0,0 +1,258 @@
+//! Typed edits of single property values, addressed by property path.
+//!
+//! Scalar values (booleans, numbers, strings and enum labels) are read and
+//! written as text and checked against the property's type, so a value that
+//! does not fit is refused instead of being written. The save is rebuilt
+//! through the property tree, which recomputes every size that changes.
+
+use crate::backup::BackupStore;
+use crate::error::{Error, Result};
+use crate::gvas::{ByteValue, FString, Property, PropertyValue, SaveGame, Value};
+use crate::manifest::{Change, Edit, Operation};
+use crate::{files, transaction};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// How a scalar value is entered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScalarKind {
+    Bool,
+    Integer { min: i128, max: i128 },
+    Float,
+    Text,
+    /// An enum label such as `EDifficulty::Hard`.
+    Label,
+}
+
+impl ScalarKind {
+    pub fn of(property: &Property) -> Option<ScalarKind> {
+        match &property.value {
+            PropertyValue::Bool(_) => Some(ScalarKind::Bool),
+            PropertyValue::Byte { value: ByteValue::Byte(_), .. } => Some(integer(u8::MIN, u8::MAX)),
+            PropertyValue::Byte { value: ByteValue::Label(_), .. } | PropertyValue::Enum { .. } => {
+                Some(ScalarKind::Label)
+            }
+            PropertyValue::Simple(value) => match value {
+                Value::Bool(_) => Some(ScalarKind::Bool),
+                Value::Int8(_) => Some(integer(i8::MIN, i8::MAX)),
+                Value::Int16(_) => Some(integer(i16::MIN, i16::MAX)),
+                Value::Int(_) => Some(integer(i32::MIN, i32::MAX)),
+                Value::Int64(_) => Some(integer(i64::MIN, i64::MAX)),
+                Value::UInt16(_) => Some(integer(u16::MIN, u16::MAX)),
+                Value::UInt32(_) => Some(integer(u32::MIN, u32::MAX)),
+                Value::UInt64(_) => Some(integer(u64::MIN, u64::MAX)),
+                Value::Byte(_) => Some(integer(u8::MIN, u8::MAX)),
+                Value::Float(_) | Value::Double(_) => Some(ScalarKind::Float),
+                Value::Str(_) => Some(ScalarKind::Text),
+                Value::Struct(_) => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+fn integer(min: impl Into<i128>, max: impl Into<i128>) -> ScalarKind {
+    ScalarKind::Integer {
+        min: min.into(),
+        max: max.into(),
+    }
+}
+
+/// The value of a scalar property as text, or `None` for structs, containers
+/// and raw bodies.
+pub fn scalar_text(property: &Property) -> Option<String> {
+    Some(match &property.value {
+        PropertyValue::Bool(value) => value.to_string(),
+        PropertyValue::Byte { value, .. } => match value {
+            ByteValue::Byte(byte) => byte.to_string(),
+            ByteValue::Label(label) => label.to_string(),
+        },
+        PropertyValue::Enum { value, .. } => value.to_string(),
+        PropertyValue::Simple(value) => match value {
+            Value::Bool(value) => value.to_string(),
+            Value::Int8(value) => value.to_string(),
+            Value::Int16(value) => value.to_string(),
+            Value::Int(value) => value.to_string(),
+            Value::Int64(value) => value.to_string(),
+            Value::UInt16(value) => value.to_string(),
+            Value::UInt32(value) => value.to_string(),
+            Value::UInt64(value) => value.to_string(),
+            Value::Byte(value) => value.to_string(),
+            Value::Float(value) => value.to_string(),
+            Value::Double(value) => value.to_string(),
+            Value::Str(value) => value.to_string(),
+            Value::Struct(_) => return None,
+        },
+        _ => return None,
+    })
+}
+
+/// Sets a scalar property from text, keeping its type.
+pub fn set_scalar(property: &mut Property, text: &str) -> Result<()> {
+    let reason = match ScalarKind::of(property) {
+        Some(ScalarKind::Bool) => "expected true or false".to_string(),
+        Some(ScalarKind::Integer { min, max }) => format!("expected a whole number from {} to {}", min, max),
+        Some(ScalarKind::Float) => "expected a finite number".to_string(),
+        Some(ScalarKind::Label) => format!("expected a label of the form {}::Name", enum_name(property)),
+        Some(ScalarKind::Text) => String::new(),
+        None => format!("{} values cannot be edited as text", property.type_name),
+    };
+    let invalid = || Error::InvalidValue {
+        property: property.name.to_string(),
+        reason: reason.clone(),
+    };
+    // Strings stored as UTF-16 stay that way.
+    let string = |old: &FString| FString {
+        wide: old.wide || FString::new(text).wide,
+        ..FString::new(text)
+    };
+
+    let value = match &property.value {
+        PropertyValue::Bool(_) => PropertyValue::Bool(parse(text).ok_or_else(invalid)?),
+        PropertyValue::Byte { enum_name, value } => PropertyValue::Byte {
+            enum_name: enum_name.clone(),
+            value: match value {
+                ByteValue::Byte(_) => ByteValue::Byte(parse(text).ok_or_else(invalid)?),
+                ByteValue::Label(label) if is_label(text, enum_name) => ByteValue::Label(string(label)),
+                ByteValue::Label(_) => return Err(invalid()),
+            },
+        },
+        PropertyValue::Enum { enum_name, value } if is_label(text, enum_name) => PropertyValue::Enum {
+            enum_name: enum_name.clone(),
+            value: string(value),
+        },
+        PropertyValue::Simple(value) => PropertyValue::Simple(match value {
+            Value::Bool(_) => Value::Bool(parse(text).ok_or_else(invalid)?),
+            Value::Int8(_) => Value::Int8(parse(text).ok_or_else(invalid)?),
+            Value::Int16(_) => Value::Int16(parse(text).ok_or_else(invalid)?),
+            Value::Int(_) => Value::Int(parse(text).ok_or_else(invalid)?),
+            Value::Int64(_) => Value::Int64(parse(text).ok_or_else(invalid)?),
+            Value::UInt16(_) => Value::UInt16(parse(text).ok_or_else(invalid)?),
+            Value::UInt32(_) => Value::UInt32(parse(text).ok_or_else(invalid)?),
+            Value::UInt64(_) => Value::UInt64(parse(text).ok_or_else(invalid)?),
+            Value::Byte(_) => Value::Byte(parse(text).ok_or_else(invalid)?),
+            Value::Float(_) => Value::Float(parse(text).filter(|v: &f32| v.is_finite()).ok_or_else(invalid)?),
+            Value::Double(_) => Value::Double(parse(text).filter(|v: &f64| v.is_finite()).ok_or_else(invalid)?),
+            Value::Str(old) => Value::Str(string(old)),
+            Value::Struct(_) => return Err(invalid()),
+        }),
+        _ => return Err(invalid()),
+    };
+    property.value = value;
+    Ok(())
+}
+
+/// `EnumName::Label` for the property's own enum, with a label made of
+/// letters, digits and underscores.
+fn is_label(text: &str, enum_name: &FString) -> bool {
+    text.strip_prefix(enum_name.as_str())
+        .and_then(|rest| rest.strip_prefix("::"))
+        .is_some_and(|label| !label.is_empty() && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'))
+}
+
+fn enum_name(property: &Property) -> &str {
+    match &property.value {
+        PropertyValue::Byte { enum_name, .. } | PropertyValue::Enum { enum_name, .. } => enum_name.as_str(),
+        _ => "",
+    }
+}
+
+/// Numbers and booleans as Rust formats them, ignoring surrounding spaces.
+/// Floats parse straight into their own width, so the shortest text of a
+/// value reads back as exactly that value.
+fn parse<T: FromStr>(text: &str) -> Option<T> {
+    text.trim().parse().ok()
+}
+
+/// A new value for the property at `path`, as text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValueEdit {
+    pub path: String,
+    pub value: String,
+}
+
+/// `data` with every edit applied, and what changed. Fails without changing
+/// anything if a path is missing or a value does not fit its property.
+pub fn apply(data: &[u8], edits: &[ValueEdit]) -> Result<(Vec<u8>, Vec<Edit>)> {
+    let mut save = SaveGame::parse_lossless(data)?;
+    let mut changed = Vec::new();
+
+    for edit in edits {
+        let property = save
+            .get_mut(&edit.path)
+            .ok_or_else(|| Error::PropertyNotFound(edit.path.clone()))?;
+        let old = scalar_text(property);
+        set_scalar(property, &edit.value).map_err(|e| match e {
+            Error::InvalidValue { reason, .. } => Error::InvalidValue {
+                property: edit.path.clone(),
+                reason,
+            },
+            other => other,
+        })?;
+        let new = scalar_text(property);
+        if old != new {
+            changed.push(Edit {
+                offset: property.offset,
+                path: Some(edit.path.clone()),
+                old,
+                new,
+            });
+        }
+    }
+
+    Ok((save.to_bytes(), changed))
+}
+
+#[derive(Debug, Clone)]
+pub struct EditOptions {
+    /// Where to back up the original first; `None` skips the backup.
+    pub backup: Option<BackupStore>,
+    pub dry_run: bool,
+}
+
+impl Default for EditOptions {
+    fn default() -> Self {
+        Self {
+            backup: Some(BackupStore::default()),
+            dry_run: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EditReport {
+    /// The values that actually changed, with their old and new text.
+    pub edits: Vec<Edit>,
+    pub backup_path: Option<PathBuf>,
+}
+
+/// Applies `edits` to the save at `path` through the usual backup and
+/// manifest path. Values that already match are left out; if none differ,
+/// nothing is written.
+pub fn edit_file(path: &Path, edits: &[ValueEdit], options: &EditOptions) -> Result<EditReport> {
+    let original = files::read_save(path)?;
+    let (data, changed) = apply(&original, edits)?;
+
+    if options.dry_run || changed.is_empty() {
+        return Ok(EditReport {
+            edits: changed,
+            backup_path: None,
+        });
+    }
+
+    let plan = transaction::Plan {
+        backup: options.backup.as_ref(),
+        record: Some(Change {
+            operation: Operation::Edit,
+            new_steamid: None,
+            edits: changed.clone(),
+        }),
+        ..Default::default()
+    };
+    let committed = transaction::commit(path, &original, &data, &plan)?;
+
+    Ok(EditReport {
+        edits: changed,
+        backup_path: committed.backup_path,
+    })
+}
@@ -0,0 +1,229 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+use steamid_replacer::backup::BackupStore;
+use steamid_replacer::edit::{self, EditOptions, ValueEdit};
+use steamid_replacer::export::{self, ImportOptions};
+use steamid_replacer::files;
+use steamid_replacer::gvas::{ArrayValue, ByteValue, MapValue, Property, PropertyValue, SaveGame, SetValue, StructValue};
+
+#[derive(Default)]
+pub struct EditorTab {
+    pub file_path: String,
+    /// The save as it is on disk.
+    pub save: Option<SaveGame>,
+    /// Paths shared by several properties, which cannot be edited by path.
+    pub ambiguous: HashSet<String>,
+    /// Labels used in the save for each enum type, offered when editing one.
+    pub enum_labels: HashMap<String, Vec<String>>,
+    pub filter: String,
+    /// Paths of the properties matching `filter` and of everything above
+    /// them; `None` shows the whole tree.
+    pub visible: Option<HashSet<String>>,
+    /// Open every header on the next frame, after the filter changed.
+    pub expand: bool,
+    /// New values by property path, as entered.
+    pub staged: BTreeMap<String, String>,
+    pub status: String,
+    pub backup_filename: String,
+}
+
+impl EditorTab {
+    /// Reads and parses `file_path`, dropping any staged values.
+    pub fn load(&mut self) {
+        self.save = None;
+        self.ambiguous.clear();
+        self.enum_labels.clear();
+        self.staged.clear();
+        if self.file_path.is_empty() {
+            self.visible = None;
+            return;
+        }
+
+        match files::read_save(Path::new(&self.file_path)).and_then(|data| SaveGame::parse_lossless(&data)) {
+            Ok(save) => {
+                let properties = save.walk();
+                let count = properties.len();
+                let mut seen = HashSet::new();
+                for (path, property) in properties {
+                    if let Some(label) = enum_label(property) {
+                        let labels = self.enum_labels.entry(label.0).or_default();
+                        if !labels.contains(&label.1) {
+                            labels.push(label.1);
+                        }
+                    }
+                    if !seen.insert(path.clone()) {
+                        self.ambiguous.insert(path);
+                    }
+                }
+                for labels in self.enum_labels.values_mut() {
+                    labels.sort();
+                }
+                self.status = format!("{} properties", count);
+                self.save = Some(save);
+            }
+            Err(e) => self.status = format!("❌ Error: {}", e),
+        }
+        self.apply_filter();
+    }
+
+    /// Works out which properties the filter shows: those whose name contains
+    /// it, ignoring case, and the properties and elements they are nested in.
+    pub fn apply_filter(&mut self) {
+        let filter = self.filter.trim().to_lowercase();
+        let Some(save) = self.save.as_ref().filter(|_| !filter.is_empty()) else {
+            self.visible = None;
+            return;
+        };
+
+        let mut visible = HashSet::new();
+        for (path, property) in save.walk() {
+            if !property.name.as_str().to_lowercase().contains(&filter) {
+                continue;
+            }
+            for (index, c) in path.char_indices() {
+                if c == '.' || c == '[' {
+                    visible.insert(path[..index].to_string());
+                }
+            }
+            visible.insert(path);
+        }
+        self.visible = Some(visible);
+        self.expand = true;
+    }
+
+    pub fn is_visible(&self, path: &str) -> bool {
+        self.visible.as_ref().is_none_or(|visible| visible.contains(path))
+    }
+
+    /// Stages `text` for the property at `path`, or unstages it if that is
+    /// the value on disk.
+    pub fn stage(&mut self, path: &str, text: String) {
+        let current = self.save.as_ref().and_then(|save| save.get(path)).and_then(edit::scalar_text);
+        if current.as_deref() == Some(text.as_str()) {
+            self.staged.remove(path);
+        } else {
+            self.staged.insert(path.to_string(), text);
+        }
+        self.backup_filename.clear();
+    }
+
+    /// Why the value staged for `path` does not fit its property, if it does not.
+    pub fn check(&self, path: &str) -> Option<String> {
+        let text = self.staged.get(path)?;
+        let mut property = self.save.as_ref()?.get(path)?.clone();
+        edit::set_scalar(&mut property, text).err().map(|e| e.to_string())
+    }
+
+    pub fn discard(&mut self) {
+        self.staged.clear();
+        self.status = "Staged values discarded".to_string();
+    }
+
+    /// Writes the staged values, backing the save up first.
+    pub fn handle_apply(&mut self, backup: BackupStore) {
+        let edits: Vec<ValueEdit> = self
+            .staged
+            .iter()
+            .map(|(path, value)| ValueEdit {
+                path: path.clone(),
+                value: value.clone(),
+            })
+            .collect();
+        let options = EditOptions {
+            backup: Some(backup),
+            dry_run: false,
+        };
+        match edit::edit_file(Path::new(&self.file_path), &edits, &options) {
+            Ok(report) => {
+                self.load();
+                self.status = format!("✅ Successfully set {} value(s)!", report.edits.len());
+                self.backup_filename = report
+                    .backup_path
+                    .as_deref()
+                    .map(files::backup_file_name)
+                    .unwrap_or_default();
+            }
+            Err(e) => self.status = format!("❌ Error: {}", e),
+        }
+    }
+
+    pub fn export_json(&mut self, output: &Path) {
+        match export::export_file(Path::new(&self.file_path), output) {
+            Ok(()) => self.status = format!("✅ Successfully exported to {}", output.display()),
+            Err(e) => self.status = format!("❌ Error: {}", e),
+        }
+    }
+
+    /// Replaces the save with the one a JSON export describes, backing it up first.
+    pub fn import_json(&mut self, json: &Path, backup: BackupStore) {
+        let options = ImportOptions {
+            backup: Some(backup),
+            overwrite: true,
+            dry_run: false,
+        };
+        match export::import_file(json, Path::new(&self.file_path), &options) {
+            Ok(report) => {
+                self.load();
+                self.status = if report.unchanged {
+                    "(!) The save already matches this JSON".to_string()
+                } else {
+                    format!("✅ Successfully imported {}", json.display())
+                };
+                self.backup_filename = report
+                    .backup_path
+                    .as_deref()
+                    .map(files::backup_file_name)
+                    .unwrap_or_default();
+            }
+            Err(e) => self.status = format!("❌ Error: {}", e),
+        }
+    }
+}
+
+/// `Array<Int>`, `InfoStruct`, `EDifficulty`: the type as shown in the tree.
+pub fn type_label(property: &Property) -> String {
+    let short = |type_name: &str| type_name.strip_suffix("Property").unwrap_or(type_name).to_string();
+    match &property.value {
+        PropertyValue::Struct { struct_type, .. } => struct_type.to_string(),
+        PropertyValue::Array { value: ArrayValue::Structs(array), .. } => format!("Array<{}>", array.struct_type),
+        PropertyValue::Array { inner_type, .. } => format!("Array<{}>", short(inner_type.as_str())),
+        PropertyValue::Set { inner_type, .. } => format!("Set<{}>", short(inner_type.as_str())),
+        PropertyValue::Map { key_type, value_type, .. } => {
+            format!("Map<{}, {}>", short(key_type.as_str()), short(value_type.as_str()))
+        }
+        PropertyValue::Enum { enum_name, .. } => enum_name.to_string(),
+        PropertyValue::Byte { enum_name, .. } if *enum_name != *"None" => enum_name.to_string(),
+        _ => short(property.type_name.as_str()),
+    }
+}
+
+/// What a value that cannot be edited here holds.
+pub fn summary(property: &Property) -> String {
+    match &property.value {
+        PropertyValue::Struct { value: StructValue::Raw(raw), .. } => format!("{} bytes", raw.len()),
+        PropertyValue::Array { value, .. } => match value {
+            ArrayValue::Values(values) => format!("{} element(s)", values.len()),
+            ArrayValue::Bytes(bytes) => format!("{} byte(s)", bytes.len()),
+            ArrayValue::Structs(array) => format!("{} element(s)", array.elements.len()),
+            ArrayValue::Raw(raw) => format!("{} bytes, not parsed", raw.len()),
+        },
+        PropertyValue::Set { value: SetValue::Elements { elements, .. }, .. } => format!("{} element(s)", elements.len()),
+        PropertyValue::Map { value: MapValue::Entries { entries, .. }, .. } => format!("{} entries", entries.len()),
+        PropertyValue::Set { value: SetValue::Raw(raw), .. }
+        | PropertyValue::Map { value: MapValue::Raw(raw), .. }
+        | PropertyValue::RawEnum { raw, .. }
+        | PropertyValue::Raw(raw) => format!("{} bytes, not parsed", raw.len()),
+        _ => String::new(),
+    }
+}
+
+/// The enum type and label of an enum property.
+fn enum_label(property: &Property) -> Option<(String, String)> {
+    match &property.value {
+        PropertyValue::Enum { enum_name, value }
+        | PropertyValue::Byte { enum_name, value: ByteValue::Label(value) } => {
+            Some((enum_name.to_string(), value.to_string()))
+        }
+        _ => None,
+    }
+}
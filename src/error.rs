@@ -0,0 +1,148 @@
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    Io { context: &'static str, source: io::Error },
+    InvalidSteamId(String),
+    SteamIdNotation { input: String, reason: &'static str },
+    NoSteamIdFound,
+    AlreadyUniversal,
+    /// The save still has an owner; holds its SteamID.
+    NotUniversal(String),
+    NoEmptyOwnerProperty,
+    NoIdentifiersFound,
+    NotDemoSave,
+    NoTransferTarget,
+    InvalidTransferTarget(PathBuf),
+    TransferTargetExists(PathBuf),
+    NothingSelected,
+    /// Selected offsets where no occurrence starts.
+    UnknownOffsets(Vec<usize>),
+    InvalidSave { offset: usize, reason: String },
+    InvalidVdf { line: usize, reason: String },
+    SteamNotFound,
+    BackupNotFound(String),
+    SaveDirNotFound,
+    SlotExists(PathBuf),
+    SameSlot(PathBuf),
+    InvalidSlotName(String),
+    /// This many files of a batch could not be processed.
+    BatchFailed(usize),
+    /// No operation has been recorded for this save.
+    NoHistory(PathBuf),
+    /// The save no longer matches its last recorded operation.
+    HistoryMismatch(PathBuf),
+    /// Reverting this operation would not give back the recorded original.
+    UndoMismatch(crate::manifest::Operation),
+    InvalidManifest { path: PathBuf, reason: String },
+    InvalidHexBytes(String),
+    InvalidPatch(String),
+    /// A JSON export that cannot be turned back into a save.
+    InvalidSaveJson(String),
+    PropertyNotFound(String),
+    InvalidValue { property: String, reason: String },
+    /// The bytes at a patch edit's offset are not the ones it expects.
+    PatchMismatch { offset: usize, expected: String, found: String },
+    /// A write failed part way and the original could not be put back.
+    RollbackFailed { cause: Box<Error>, rollback: Box<Error>, backup_path: Option<PathBuf> },
+}
+
+impl Error {
+    pub(crate) fn io(context: &'static str) -> impl FnOnce(io::Error) -> Error {
+        move |source| Error::Io { context, source }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io { context, source } => write!(f, "{}: {}", context, source),
+            Error::InvalidSteamId(_) => {
+                write!(f, "Invalid SteamID format. Must be 17 digits starting with 7656")
+            }
+            Error::SteamIdNotation { input, reason } => {
+                write!(f, "Cannot read '{}' as a SteamID: {}", input, reason)
+            }
+            Error::NoSteamIdFound => write!(f, "No valid SteamIDs found in file"),
+            Error::AlreadyUniversal => write!(f, "No SteamIDs found - file is already universal"),
+            Error::NotUniversal(steamid) => {
+                write!(f, "Save already belongs to SteamID {}; use replace instead", steamid)
+            }
+            Error::NoEmptyOwnerProperty => {
+                write!(f, "No emptied SteamID property found - the save cannot be re-personalized")
+            }
+            Error::NoIdentifiersFound => write!(f, "No SteamIDs or account names found in file"),
+            Error::NotDemoSave => {
+                write!(f, "File is not a demo save. Cannot transfer demo save.")
+            }
+            Error::NoTransferTarget => {
+                write!(f, "File name does not contain 'Demo00'; choose a target slot for the transfer")
+            }
+            Error::InvalidTransferTarget(path) => {
+                write!(f, "Cannot transfer demo save onto itself: {}", path.display())
+            }
+            Error::TransferTargetExists(path) => {
+                write!(f, "{} already exists; confirm replacing it to continue", path.display())
+            }
+            Error::NothingSelected => write!(f, "None of the selected occurrences were found in the file"),
+            Error::UnknownOffsets(offsets) => {
+                let offsets: Vec<String> = offsets.iter().map(|offset| format!("0x{:X}", offset)).collect();
+                write!(f, "No occurrence starts at offset(s) {}", offsets.join(", "))
+            }
+            Error::InvalidSave { offset, reason } => {
+                write!(f, "Invalid save data at offset 0x{:X}: {}", offset, reason)
+            }
+            Error::InvalidVdf { line, reason } => write!(f, "Invalid VDF file at line {}: {}", line, reason),
+            Error::SteamNotFound => write!(f, "Steam installation not found"),
+            Error::BackupNotFound(backup) => write!(f, "No backup '{}'", backup),
+            Error::SaveDirNotFound => write!(f, "Stellar Blade SaveGames folder not found"),
+            Error::SlotExists(path) => {
+                write!(f, "{} already exists; choose another name or confirm replacing it", path.display())
+            }
+            Error::SameSlot(path) => write!(f, "Cannot copy or swap {} with itself", path.display()),
+            Error::InvalidSlotName(name) => write!(f, "'{}' is not a valid save file name", name),
+            Error::BatchFailed(count) => write!(f, "{} file(s) could not be processed", count),
+            Error::NoHistory(path) => write!(f, "No recorded operations for {}", path.display()),
+            Error::HistoryMismatch(path) => {
+                write!(f, "{} has changed since its last recorded operation; restore a backup instead", path.display())
+            }
+            Error::UndoMismatch(operation) => {
+                write!(f, "Cannot undo the last {}: the result would not match the recorded original", operation)
+            }
+            Error::InvalidManifest { path, reason } => {
+                write!(f, "Invalid operation manifest {}: {}", path.display(), reason)
+            }
+            Error::InvalidHexBytes(text) => write!(f, "'{}' is not a list of hex bytes", text),
+            Error::InvalidPatch(reason) => write!(f, "Invalid patch: {}", reason),
+            Error::InvalidSaveJson(reason) => write!(f, "Invalid save JSON: {}", reason),
+            Error::PropertyNotFound(path) => write!(f, "No property '{}' in the save", path),
+            Error::InvalidValue { property, reason } => write!(f, "Invalid value for {}: {}", property, reason),
+            Error::PatchMismatch { offset, expected, found } => write!(
+                f,
+                "Patch does not fit this file: expected {} at 0x{:X}, found {}",
+                expected, offset, found
+            ),
+            Error::RollbackFailed { cause, rollback, backup_path } => {
+                write!(f, "{}; restoring the original also failed: {}", cause, rollback)?;
+                match backup_path {
+                    Some(backup_path) => write!(f, ". Your backup is at {}", backup_path.display()),
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            Error::RollbackFailed { cause, .. } => Some(cause.as_ref()),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,114 @@
+//! Saves as editable JSON documents, and back.
+//!
+//! An export holds the whole property tree with the type information needed
+//! to rebuild the binary, and is only produced if importing it again gives
+//! back every byte of the save. An import over an existing save goes through
+//! the usual backup and manifest path, so it can be undone.
+
+use crate::backup::BackupStore;
+use crate::error::{Error, Result};
+use crate::gvas::SaveGame;
+use crate::manifest::{Change, Operation};
+use crate::{files, transaction};
+use std::path::{Path, PathBuf};
+
+/// `data` as a pretty-printed JSON document.
+pub fn export(data: &[u8]) -> Result<String> {
+    let save = SaveGame::parse_lossless(data)?;
+    let text = serde_json::to_string_pretty(&save.to_json()).map_err(|e| Error::InvalidSaveJson(e.to_string()))?;
+
+    let rebuilt = import(&text)?;
+    if rebuilt != data {
+        let offset = rebuilt
+            .iter()
+            .zip(data)
+            .position(|(a, b)| a != b)
+            .unwrap_or(rebuilt.len().min(data.len()));
+        return Err(Error::InvalidSave {
+            offset,
+            reason: "save does not round-trip through JSON".to_string(),
+        });
+    }
+    Ok(text)
+}
+
+/// The save described by a JSON document from [`export`].
+pub fn import(text: &str) -> Result<Vec<u8>> {
+    let document = serde_json::from_str(text).map_err(|e| Error::InvalidSaveJson(e.to_string()))?;
+    let data = SaveGame::from_json(&document)?.to_bytes();
+    // Edited tags can describe something the parser cannot read back.
+    SaveGame::parse(&data)?;
+    Ok(data)
+}
+
+pub fn export_file(path: &Path, output: &Path) -> Result<()> {
+    let text = export(&files::read_save(path)?)?;
+    std::fs::write(output, text).map_err(Error::io("Failed to write JSON file"))
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportOptions {
+    /// Where to back up the save being replaced; `None` skips the backup.
+    pub backup: Option<BackupStore>,
+    /// Allow replacing an existing save.
+    pub overwrite: bool,
+    pub dry_run: bool,
+}
+
+impl Default for ImportOptions {
+    fn default() -> Self {
+        Self {
+            backup: Some(BackupStore::default()),
+            overwrite: false,
+            dry_run: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportReport {
+    pub size: usize,
+    /// An existing save was replaced rather than a new one created.
+    pub replaced: bool,
+    /// The existing save already had exactly these bytes, so nothing was written.
+    pub unchanged: bool,
+    pub backup_path: Option<PathBuf>,
+}
+
+/// Rebuilds the save described by the JSON file at `json_path` into `target`.
+pub fn import_file(json_path: &Path, target: &Path, options: &ImportOptions) -> Result<ImportReport> {
+    let text = std::fs::read_to_string(json_path).map_err(Error::io("Failed to read JSON file"))?;
+    let data = import(&text)?;
+
+    let replaced = target.exists();
+    if replaced && !options.overwrite {
+        return Err(Error::SlotExists(target.to_path_buf()));
+    }
+    let original = if replaced { Some(files::read_save(target)?) } else { None };
+    let mut report = ImportReport {
+        size: data.len(),
+        replaced,
+        unchanged: original.as_deref() == Some(data.as_slice()),
+        backup_path: None,
+    };
+    if options.dry_run || report.unchanged {
+        return Ok(report);
+    }
+
+    match original {
+        Some(original) => {
+            let plan = transaction::Plan {
+                backup: options.backup.as_ref(),
+                record: Some(Change {
+                    operation: Operation::Import,
+                    new_steamid: None,
+                    edits: Vec::new(),
+                }),
+                ..Default::default()
+            };
+            report.backup_path = transaction::commit(target, &original, &data, &plan)?.backup_path;
+        }
+        None => files::write_save(target, &data)?,
+    }
+    Ok(report)
+}
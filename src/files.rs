@@ -0,0 +1,85 @@
+use crate::error::{Error, Result};
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+pub fn read_save(path: &Path) -> Result<Vec<u8>> {
+    let mut file = File::open(path).map_err(Error::io("Failed to open file"))?;
+
+    let mut data = Vec::new();
+    file.read_to_end(&mut data)
+        .map_err(Error::io("Failed to read file"))?;
+
+    Ok(data)
+}
+
+/// Replaces `path` atomically: the data goes to a temporary file in the same
+/// folder, is flushed to disk and read back, then renamed over the original.
+/// On failure the original is untouched and the temporary file is removed.
+pub fn write_save(path: &Path, data: &[u8]) -> Result<()> {
+    let temp_path = temp_path_for(path);
+
+    let result = write_synced(&temp_path, data)
+        .and_then(|()| verify_written(&temp_path, data))
+        .and_then(|()| std::fs::rename(&temp_path, path).map_err(Error::io("Failed to replace file")));
+    if result.is_err() {
+        let _ = std::fs::remove_file(&temp_path);
+    }
+    result?;
+
+    sync_parent(path);
+    Ok(())
+}
+
+fn write_synced(path: &Path, data: &[u8]) -> Result<()> {
+    let mut file = File::create(path).map_err(Error::io("Failed to write to file"))?;
+    file.write_all(data)
+        .map_err(Error::io("Failed to write to file"))?;
+    file.sync_all()
+        .map_err(Error::io("Failed to flush file to disk"))
+}
+
+fn verify_written(path: &Path, data: &[u8]) -> Result<()> {
+    let written = read_save(path)?;
+    if written != data {
+        let offset = written.iter().zip(data).position(|(a, b)| a != b).unwrap_or(written.len().min(data.len()));
+        return Err(Error::InvalidSave {
+            offset,
+            reason: "file on disk differs from the data written".to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Unique per call, so threads writing the same file never share a temp file.
+fn temp_path_for(path: &Path) -> PathBuf {
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let id = NEXT.fetch_add(1, Ordering::Relaxed);
+    path.with_file_name(format!(".{}.{}.{}.tmp", name, std::process::id(), id))
+}
+
+/// Makes the rename itself durable.
+#[cfg(unix)]
+fn sync_parent(path: &Path) {
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    if let Ok(dir) = File::open(parent) {
+        let _ = dir.sync_all();
+    }
+}
+
+/// Directories cannot be opened for syncing on Windows.
+#[cfg(not(unix))]
+fn sync_parent(_path: &Path) {}
+
+pub fn backup_file_name(backup_path: &Path) -> String {
+    backup_path
+        .file_name()
+        .unwrap_or_else(|| std::ffi::OsStr::new("backup.bak"))
+        .to_string_lossy()
+        .to_string()
+}
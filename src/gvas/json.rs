@@ -0,0 +1,569 @@
+//! The save as a JSON document, for editing in a text editor, keeping in git
+//! and diffing.
+//!
+//! Each property is an object with its name, type and the tag fields of its
+//! type, and its value in the most readable form the type allows: numbers,
+//! strings, lists of child properties. Bodies the parser keeps as bytes
+//! (native structs, unknown types) are written as hex. Enough is kept to
+//! rebuild the binary byte for byte; sizes are recomputed on import.
+
+use super::*;
+use crate::error::{Error, Result};
+use serde_json::{Map, Number, Value as Json, json};
+
+/// The type of a struct array's inner tag when `element_type` is left out.
+const STRUCT_PROPERTY: &str = "StructProperty";
+
+pub(crate) fn to_json(save: &SaveGame) -> Json {
+    json!({
+        "header": header_to_json(&save.header),
+        "properties": properties_to_json(&save.properties),
+        "trailer": bytes_to_json(&save.trailer),
+    })
+}
+
+pub(crate) fn from_json(json: &Json) -> Result<SaveGame> {
+    Ok(SaveGame {
+        header: header(field(json, "header", "")?, "header")?,
+        properties: properties(field(json, "properties", "")?, "")?,
+        trailer: bytes(field(json, "trailer", "")?, "trailer")?,
+    })
+}
+
+fn invalid(at: &str, reason: impl Into<String>) -> Error {
+    let reason = reason.into();
+    Error::InvalidSaveJson(if at.is_empty() {
+        reason
+    } else {
+        format!("{}: {}", at, reason)
+    })
+}
+
+fn join(prefix: &str, name: &str) -> String {
+    if prefix.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", prefix, name)
+    }
+}
+
+fn field<'a>(json: &'a Json, key: &str, at: &str) -> Result<&'a Json> {
+    match json {
+        Json::Object(object) => object.get(key).ok_or_else(|| invalid(at, format!("missing '{}'", key))),
+        _ => Err(invalid(at, "expected an object")),
+    }
+}
+
+fn optional<'a>(json: &'a Json, key: &str) -> Option<&'a Json> {
+    json.get(key).filter(|value| !value.is_null())
+}
+
+fn array<'a>(json: &'a Json, at: &str) -> Result<&'a Vec<Json>> {
+    json.as_array().ok_or_else(|| invalid(at, "expected a list"))
+}
+
+// Writing
+
+/// Strings are plain JSON strings and a null `FString` is `null`. A string
+/// stored as UTF-16 although it would fit in one byte per character is
+/// written as `{"utf16": "..."}` so it comes back the same way.
+fn fstring_to_json(s: &FString) -> Json {
+    match &s.value {
+        None => Json::Null,
+        Some(value) if s.wide && !FString::new(value).wide => json!({ "utf16": value }),
+        Some(value) => Json::String(value.clone()),
+    }
+}
+
+fn bytes_to_json(bytes: &[u8]) -> Json {
+    Json::String(crate::hex::format_bytes(bytes))
+}
+
+fn guid_to_json(guid: &Guid) -> Json {
+    Json::String(hex::encode_upper(guid))
+}
+
+/// The shortest decimal that reads back as the same `f32`, so 0.1 stays 0.1.
+/// Infinities and NaN have no JSON number and are written as their bits.
+fn float_to_json(value: f32) -> Json {
+    match value.to_string().parse().ok().and_then(Number::from_f64) {
+        Some(number) => Json::Number(number),
+        None => Json::String(format!("0x{:08X}", value.to_bits())),
+    }
+}
+
+fn double_to_json(value: f64) -> Json {
+    match Number::from_f64(value) {
+        Some(number) => Json::Number(number),
+        None => Json::String(format!("0x{:016X}", value.to_bits())),
+    }
+}
+
+fn header_to_json(header: &Header) -> Json {
+    let mut object = Map::new();
+    object.insert("save_game_version".into(), json!(header.save_game_version));
+    object.insert("package_version".into(), json!(header.package_version));
+    if let Some(version) = header.package_version_ue5 {
+        object.insert("package_version_ue5".into(), json!(version));
+    }
+    let engine = &header.engine_version;
+    object.insert(
+        "engine_version".into(),
+        json!({
+            "major": engine.major,
+            "minor": engine.minor,
+            "patch": engine.patch,
+            "changelist": engine.changelist,
+            "branch": fstring_to_json(&engine.branch),
+        }),
+    );
+    object.insert("custom_version_format".into(), json!(header.custom_version_format));
+    object.insert(
+        "custom_versions".into(),
+        header
+            .custom_versions
+            .iter()
+            .map(|custom| json!({ "guid": guid_to_json(&custom.guid), "version": custom.version }))
+            .collect(),
+    );
+    object.insert("save_game_class_name".into(), fstring_to_json(&header.save_game_class_name));
+    Json::Object(object)
+}
+
+fn properties_to_json(properties: &[Property]) -> Json {
+    properties.iter().map(property_to_json).collect()
+}
+
+fn property_to_json(property: &Property) -> Json {
+    let mut object = Map::new();
+    object.insert("name".into(), fstring_to_json(&property.name));
+    object.insert("type".into(), fstring_to_json(&property.type_name));
+    if property.array_index != 0 {
+        object.insert("array_index".into(), json!(property.array_index));
+    }
+    if let Some(guid) = &property.guid {
+        object.insert("guid".into(), guid_to_json(guid));
+    }
+
+    // `Err` holds the bytes of a body the parser did not understand.
+    let value = match &property.value {
+        PropertyValue::Bool(value) => Ok(json!(value)),
+        PropertyValue::Simple(value) => Ok(value_to_json(value)),
+        PropertyValue::Byte { enum_name, value } => {
+            object.insert("enum_name".into(), fstring_to_json(enum_name));
+            Ok(match value {
+                ByteValue::Byte(byte) => json!(byte),
+                ByteValue::Label(label) => fstring_to_json(label),
+            })
+        }
+        PropertyValue::Enum { enum_name, value } => {
+            object.insert("enum_name".into(), fstring_to_json(enum_name));
+            Ok(fstring_to_json(value))
+        }
+        PropertyValue::Struct { struct_type, struct_guid, value } => {
+            object.insert("struct_type".into(), fstring_to_json(struct_type));
+            if *struct_guid != Guid::default() {
+                object.insert("struct_guid".into(), guid_to_json(struct_guid));
+            }
+            Ok(struct_to_json(value))
+        }
+        PropertyValue::Array { inner_type, value } => {
+            object.insert("inner_type".into(), fstring_to_json(inner_type));
+            match value {
+                ArrayValue::Values(values) => Ok(values.iter().map(value_to_json).collect()),
+                ArrayValue::Bytes(bytes) => Ok(bytes_to_json(bytes)),
+                ArrayValue::Structs(array) => {
+                    object.insert("struct_type".into(), fstring_to_json(&array.struct_type));
+                    if array.struct_guid != Guid::default() {
+                        object.insert("struct_guid".into(), guid_to_json(&array.struct_guid));
+                    }
+                    // The inner tag normally repeats the property's name.
+                    if array.name != property.name {
+                        object.insert("element_name".into(), fstring_to_json(&array.name));
+                    }
+                    if array.type_name != *STRUCT_PROPERTY {
+                        object.insert("element_type".into(), fstring_to_json(&array.type_name));
+                    }
+                    if array.array_index != 0 {
+                        object.insert("element_array_index".into(), json!(array.array_index));
+                    }
+                    if let Some(guid) = &array.guid {
+                        object.insert("element_guid".into(), guid_to_json(guid));
+                    }
+                    Ok(array.elements.iter().map(struct_to_json).collect())
+                }
+                ArrayValue::Raw(raw) => Err(raw),
+            }
+        }
+        PropertyValue::Set { inner_type, value } => {
+            object.insert("inner_type".into(), fstring_to_json(inner_type));
+            match value {
+                SetValue::Elements { removed, elements } => {
+                    if !removed.is_empty() {
+                        object.insert("removed".into(), removed.iter().map(value_to_json).collect());
+                    }
+                    Ok(elements.iter().map(value_to_json).collect())
+                }
+                SetValue::Raw(raw) => Err(raw),
+            }
+        }
+        PropertyValue::Map { key_type, value_type, value } => {
+            object.insert("key_type".into(), fstring_to_json(key_type));
+            object.insert("value_type".into(), fstring_to_json(value_type));
+            match value {
+                MapValue::Entries { removed, entries } => {
+                    if !removed.is_empty() {
+                        object.insert("removed".into(), removed.iter().map(value_to_json).collect());
+                    }
+                    Ok(entries
+                        .iter()
+                        .map(|(key, value)| json!([value_to_json(key), value_to_json(value)]))
+                        .collect())
+                }
+                MapValue::Raw(raw) => Err(raw),
+            }
+        }
+        PropertyValue::RawEnum { enum_name, raw } => {
+            object.insert("enum_name".into(), fstring_to_json(enum_name));
+            Err(raw)
+        }
+        PropertyValue::Raw(raw) => Err(raw),
+    };
+
+    match value {
+        Ok(value) => object.insert("value".into(), value),
+        Err(raw) => object.insert("raw".into(), bytes_to_json(raw)),
+    };
+    Json::Object(object)
+}
+
+/// Child properties as a list, native structs as hex.
+fn struct_to_json(value: &StructValue) -> Json {
+    match value {
+        StructValue::Properties(properties) => properties_to_json(properties),
+        StructValue::Raw(raw) => bytes_to_json(raw),
+    }
+}
+
+fn value_to_json(value: &Value) -> Json {
+    match value {
+        Value::Bool(value) => json!(value),
+        Value::Int8(value) => json!(value),
+        Value::Int16(value) => json!(value),
+        Value::Int(value) => json!(value),
+        Value::Int64(value) => json!(value),
+        Value::UInt16(value) => json!(value),
+        Value::UInt32(value) => json!(value),
+        Value::UInt64(value) => json!(value),
+        Value::Float(value) => float_to_json(*value),
+        Value::Double(value) => double_to_json(*value),
+        Value::Byte(value) => json!(value),
+        Value::Str(value) => fstring_to_json(value),
+        Value::Struct(properties) => properties_to_json(properties),
+    }
+}
+
+// Reading
+
+fn fstring(json: &Json, at: &str) -> Result<FString> {
+    match json {
+        Json::Null => Ok(FString::default()),
+        Json::String(value) => Ok(FString::new(value)),
+        Json::Object(_) => match field(json, "utf16", at)? {
+            Json::String(value) => Ok(FString {
+                value: Some(value.clone()),
+                wide: true,
+            }),
+            _ => Err(invalid(at, "expected 'utf16' to be a string")),
+        },
+        _ => Err(invalid(at, "expected a string")),
+    }
+}
+
+fn bytes(json: &Json, at: &str) -> Result<Vec<u8>> {
+    let text = json.as_str().ok_or_else(|| invalid(at, "expected hex bytes"))?;
+    if text.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    crate::hex::parse_bytes(text).map_err(|_| invalid(at, "expected hex bytes such as \"05 00 00 00\""))
+}
+
+fn guid(json: &Json, at: &str) -> Result<Guid> {
+    bytes(json, at)?
+        .try_into()
+        .map_err(|_| invalid(at, "expected a 16-byte GUID"))
+}
+
+fn optional_guid(json: &Json, key: &str, at: &str) -> Result<Option<Guid>> {
+    optional(json, key).map(|guid_json| guid(guid_json, &join(at, key))).transpose()
+}
+
+fn boolean(json: &Json, at: &str) -> Result<bool> {
+    json.as_bool().ok_or_else(|| invalid(at, "expected true or false"))
+}
+
+fn int<T: TryFrom<i128>>(json: &Json, at: &str) -> Result<T> {
+    let value = json
+        .as_i64()
+        .map(i128::from)
+        .or_else(|| json.as_u64().map(i128::from))
+        .ok_or_else(|| invalid(at, "expected a whole number"))?;
+    T::try_from(value).map_err(|_| invalid(at, format!("{} is out of range", value)))
+}
+
+fn float(json: &Json, at: &str) -> Result<f32> {
+    match json {
+        Json::Number(number) => number.to_string().parse().map_err(|_| invalid(at, "expected a number")),
+        Json::String(bits) => crate::hex::parse_offset(bits)
+            .and_then(|bits| u32::try_from(bits).ok())
+            .map(f32::from_bits)
+            .ok_or_else(|| invalid(at, "expected a number")),
+        _ => Err(invalid(at, "expected a number")),
+    }
+}
+
+fn double(json: &Json, at: &str) -> Result<f64> {
+    match json {
+        Json::Number(number) => number.as_f64().ok_or_else(|| invalid(at, "expected a number")),
+        Json::String(bits) => crate::hex::parse_offset(bits)
+            .map(|bits| f64::from_bits(bits as u64))
+            .ok_or_else(|| invalid(at, "expected a number")),
+        _ => Err(invalid(at, "expected a number")),
+    }
+}
+
+fn number<T: TryFrom<i128>>(json: &Json, key: &str, at: &str) -> Result<T> {
+    int(field(json, key, at)?, &join(at, key))
+}
+
+fn header(json: &Json, at: &str) -> Result<Header> {
+    let engine_at = join(at, "engine_version");
+    let engine = field(json, "engine_version", at)?;
+    let versions_at = join(at, "custom_versions");
+
+    let mut custom_versions = Vec::new();
+    for (index, custom) in array(field(json, "custom_versions", at)?, &versions_at)?.iter().enumerate() {
+        let custom_at = format!("{}[{}]", versions_at, index);
+        custom_versions.push(CustomVersion {
+            guid: guid(field(custom, "guid", &custom_at)?, &join(&custom_at, "guid"))?,
+            version: number(custom, "version", &custom_at)?,
+        });
+    }
+
+    Ok(Header {
+        save_game_version: number(json, "save_game_version", at)?,
+        package_version: number(json, "package_version", at)?,
+        package_version_ue5: match optional(json, "package_version_ue5") {
+            Some(_) => Some(number(json, "package_version_ue5", at)?),
+            None => None,
+        },
+        engine_version: EngineVersion {
+            major: number(engine, "major", &engine_at)?,
+            minor: number(engine, "minor", &engine_at)?,
+            patch: number(engine, "patch", &engine_at)?,
+            changelist: number(engine, "changelist", &engine_at)?,
+            branch: fstring(field(engine, "branch", &engine_at)?, &join(&engine_at, "branch"))?,
+        },
+        custom_version_format: number(json, "custom_version_format", at)?,
+        custom_versions,
+        save_game_class_name: fstring(field(json, "save_game_class_name", at)?, &join(at, "save_game_class_name"))?,
+    })
+}
+
+/// A property list; `at` is the path of its owner.
+fn properties(json: &Json, at: &str) -> Result<Vec<Property>> {
+    array(json, at)?
+        .iter()
+        .enumerate()
+        .map(|(index, property_json)| property(property_json, at, index))
+        .collect()
+}
+
+fn property(json: &Json, owner: &str, index: usize) -> Result<Property> {
+    let name = fstring(field(json, "name", &format!("{}[{}]", owner, index))?, owner)?;
+    let at = join(owner, name.as_str());
+    let type_name = fstring(field(json, "type", &at)?, &join(&at, "type"))?;
+    let array_index = match optional(json, "array_index") {
+        Some(array_index) => int(array_index, &join(&at, "array_index"))?,
+        None => 0,
+    };
+    let guid = optional_guid(json, "guid", &at)?;
+    let tag = |key: &str| -> Result<FString> { fstring(field(json, key, &at)?, &join(&at, key)) };
+
+    let value = match optional(json, "raw") {
+        Some(raw) => PropertyValue::Raw(bytes(raw, &join(&at, "raw"))?),
+        None => property_value(json, &name, type_name.as_str(), &at, &tag)?,
+    };
+
+    // Containers and enums kept as raw bytes still have the tag of their type.
+    let value = match (value, type_name.as_str()) {
+        (PropertyValue::Raw(raw), "ArrayProperty") => PropertyValue::Array {
+            inner_type: tag("inner_type")?,
+            value: ArrayValue::Raw(raw),
+        },
+        (PropertyValue::Raw(raw), "SetProperty") => PropertyValue::Set {
+            inner_type: tag("inner_type")?,
+            value: SetValue::Raw(raw),
+        },
+        (PropertyValue::Raw(raw), "MapProperty") => PropertyValue::Map {
+            key_type: tag("key_type")?,
+            value_type: tag("value_type")?,
+            value: MapValue::Raw(raw),
+        },
+        (PropertyValue::Raw(raw), "ByteProperty" | "EnumProperty") => PropertyValue::RawEnum {
+            enum_name: tag("enum_name")?,
+            raw,
+        },
+        (value, _) => value,
+    };
+
+    Ok(Property {
+        name,
+        type_name,
+        array_index,
+        guid,
+        value,
+        offset: 0,
+    })
+}
+
+fn property_value(
+    json: &Json,
+    name: &FString,
+    type_name: &str,
+    at: &str,
+    tag: &dyn Fn(&str) -> Result<FString>,
+) -> Result<PropertyValue> {
+    let value_at = join(at, "value");
+    let value = field(json, "value", at)?;
+
+    Ok(match type_name {
+        "BoolProperty" => PropertyValue::Bool(boolean(value, &value_at)?),
+        "StructProperty" => PropertyValue::Struct {
+            struct_type: tag("struct_type")?,
+            struct_guid: optional_guid(json, "struct_guid", at)?.unwrap_or_default(),
+            value: struct_value(value, at)?,
+        },
+        "ArrayProperty" => {
+            let inner_type = tag("inner_type")?;
+            let value = match inner_type.as_str() {
+                "StructProperty" => {
+                    let mut elements = Vec::new();
+                    for (index, element) in array(value, &value_at)?.iter().enumerate() {
+                        elements.push(struct_value(element, &format!("{}[{}]", at, index))?);
+                    }
+                    ArrayValue::Structs(StructArray {
+                        name: match optional(json, "element_name") {
+                            Some(element_name) => fstring(element_name, &join(at, "element_name"))?,
+                            None => name.clone(),
+                        },
+                        type_name: match optional(json, "element_type") {
+                            Some(element_type) => fstring(element_type, &join(at, "element_type"))?,
+                            None => FString::new(STRUCT_PROPERTY),
+                        },
+                        array_index: match optional(json, "element_array_index") {
+                            Some(array_index) => int(array_index, &join(at, "element_array_index"))?,
+                            None => 0,
+                        },
+                        struct_type: tag("struct_type")?,
+                        struct_guid: optional_guid(json, "struct_guid", at)?.unwrap_or_default(),
+                        guid: optional_guid(json, "element_guid", at)?,
+                        elements,
+                    })
+                }
+                _ if value.is_string() => ArrayValue::Bytes(bytes(value, &value_at)?),
+                inner => ArrayValue::Values(values(value, inner, at)?),
+            };
+            PropertyValue::Array { inner_type, value }
+        }
+        "SetProperty" => {
+            let inner_type = tag("inner_type")?;
+            let removed = match optional(json, "removed") {
+                Some(removed) => values(removed, inner_type.as_str(), &join(at, "removed"))?,
+                None => Vec::new(),
+            };
+            let elements = values(value, inner_type.as_str(), at)?;
+            PropertyValue::Set {
+                inner_type,
+                value: SetValue::Elements { removed, elements },
+            }
+        }
+        "MapProperty" => {
+            let key_type = tag("key_type")?;
+            let value_type = tag("value_type")?;
+            let removed = match optional(json, "removed") {
+                Some(removed) => values(removed, key_type.as_str(), &join(at, "removed"))?,
+                None => Vec::new(),
+            };
+            let mut entries = Vec::new();
+            for (index, entry) in array(value, &value_at)?.iter().enumerate() {
+                let entry_at = format!("{}[{}]", at, index);
+                match entry.as_array().map(Vec::as_slice) {
+                    Some([key, value]) => entries.push((
+                        self::value(key, key_type.as_str(), &entry_at)?,
+                        self::value(value, value_type.as_str(), &entry_at)?,
+                    )),
+                    _ => return Err(invalid(&entry_at, "expected a [key, value] pair")),
+                }
+            }
+            PropertyValue::Map {
+                key_type,
+                value_type,
+                value: MapValue::Entries { removed, entries },
+            }
+        }
+        "ByteProperty" => PropertyValue::Byte {
+            enum_name: tag("enum_name")?,
+            value: match value {
+                Json::Number(_) => ByteValue::Byte(int(value, &value_at)?),
+                _ => ByteValue::Label(fstring(value, &value_at)?),
+            },
+        },
+        "EnumProperty" => PropertyValue::Enum {
+            enum_name: tag("enum_name")?,
+            value: fstring(value, &value_at)?,
+        },
+        _ => PropertyValue::Simple(self::value(value, type_name, &value_at)?),
+    })
+}
+
+/// Child properties from a list, native struct bytes from hex.
+fn struct_value(json: &Json, at: &str) -> Result<StructValue> {
+    match json {
+        Json::String(_) => Ok(StructValue::Raw(bytes(json, at)?)),
+        _ => Ok(StructValue::Properties(properties(json, at)?)),
+    }
+}
+
+/// Container elements; `at` is the path of the container.
+fn values(json: &Json, type_name: &str, at: &str) -> Result<Vec<Value>> {
+    array(json, at)?
+        .iter()
+        .enumerate()
+        .map(|(index, element)| value(element, type_name, &format!("{}[{}]", at, index)))
+        .collect()
+}
+
+fn value(json: &Json, type_name: &str, at: &str) -> Result<Value> {
+    Ok(match type_name {
+        "BoolProperty" => Value::Bool(boolean(json, at)?),
+        "Int8Property" => Value::Int8(int(json, at)?),
+        "Int16Property" => Value::Int16(int(json, at)?),
+        "IntProperty" => Value::Int(int(json, at)?),
+        "Int64Property" => Value::Int64(int(json, at)?),
+        "UInt16Property" => Value::UInt16(int(json, at)?),
+        "UInt32Property" => Value::UInt32(int(json, at)?),
+        "UInt64Property" => Value::UInt64(int(json, at)?),
+        "FloatProperty" => Value::Float(float(json, at)?),
+        "DoubleProperty" => Value::Double(double(json, at)?),
+        "ByteProperty" => Value::Byte(int(json, at)?),
+        "StrProperty" | "NameProperty" | "ObjectProperty" | "EnumProperty" => Value::Str(fstring(json, at)?),
+        "StructProperty" => Value::Struct(properties(json, at)?),
+        _ => {
+            return Err(invalid(
+                at,
+                format!("cannot read a value of type '{}'; keep the property's \"raw\" bytes instead", type_name),
+            ));
+        }
+    })
+}
@@ -0,0 +1,505 @@
+//! Unreal Engine `GVAS` save containers: header plus a typed property tree.
+
+mod json;
+mod reader;
+mod writer;
+
+use crate::error::{Error, Result};
+
+pub type Guid = [u8; 16];
+
+/// An Unreal `FString`. `value` is `None` for the zero-length form, which is
+/// serialized differently from an empty string with a terminator.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FString {
+    pub value: Option<String>,
+    pub wide: bool,
+}
+
+impl FString {
+    pub fn new(value: &str) -> Self {
+        Self {
+            value: Some(value.to_string()),
+            wide: value.chars().any(|c| c as u32 > 0xFF),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        self.value.as_deref().unwrap_or("")
+    }
+
+    pub fn is_null(&self) -> bool {
+        self.value.is_none()
+    }
+
+    /// Whether the string is written as UTF-16: if it was stored that way, or
+    /// if it has characters Latin-1 cannot hold.
+    pub fn is_written_wide(&self) -> bool {
+        self.wide || self.as_str().chars().any(|c| c as u32 > 0xFF)
+    }
+
+    /// Size in bytes including the length prefix and terminator.
+    pub fn serialized_len(&self) -> usize {
+        match &self.value {
+            None => 4,
+            Some(value) if self.is_written_wide() => 4 + (value.encode_utf16().count() + 1) * 2,
+            Some(value) => 4 + value.chars().count() + 1,
+        }
+    }
+}
+
+impl PartialEq<str> for FString {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl std::fmt::Display for FString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SaveGame {
+    pub header: Header,
+    pub properties: Vec<Property>,
+    /// Bytes after the top-level `None`, usually four zero bytes.
+    pub trailer: Vec<u8>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Header {
+    pub save_game_version: i32,
+    pub package_version: i32,
+    /// Only present from save game version 3 (UE5) onwards.
+    pub package_version_ue5: Option<i32>,
+    pub engine_version: EngineVersion,
+    pub custom_version_format: i32,
+    pub custom_versions: Vec<CustomVersion>,
+    pub save_game_class_name: FString,
+}
+
+#[derive(Debug, Clone)]
+pub struct EngineVersion {
+    pub major: u16,
+    pub minor: u16,
+    pub patch: u16,
+    pub changelist: u32,
+    pub branch: FString,
+}
+
+#[derive(Debug, Clone)]
+pub struct CustomVersion {
+    pub guid: Guid,
+    pub version: i32,
+}
+
+#[derive(Debug, Clone)]
+pub struct Property {
+    pub name: FString,
+    pub type_name: FString,
+    pub array_index: i32,
+    pub guid: Option<Guid>,
+    pub value: PropertyValue,
+    /// Offset of the property tag in the parsed file.
+    pub offset: usize,
+}
+
+#[derive(Debug, Clone)]
+pub enum PropertyValue {
+    Bool(bool),
+    Simple(Value),
+    Byte { enum_name: FString, value: ByteValue },
+    Enum { enum_name: FString, value: FString },
+    Struct { struct_type: FString, struct_guid: Guid, value: StructValue },
+    Array { inner_type: FString, value: ArrayValue },
+    Set { inner_type: FString, value: SetValue },
+    Map { key_type: FString, value_type: FString, value: MapValue },
+    /// A `ByteProperty` or `EnumProperty` body this parser does not understand,
+    /// kept verbatim with the enum name of its tag.
+    RawEnum { enum_name: FString, raw: Vec<u8> },
+    /// A property type this parser does not understand, kept verbatim.
+    Raw(Vec<u8>),
+}
+
+/// An untagged value, as stored in a simple property body or a container element.
+#[derive(Debug, Clone)]
+pub enum Value {
+    Bool(bool),
+    Int8(i8),
+    Int16(i16),
+    Int(i32),
+    Int64(i64),
+    UInt16(u16),
+    UInt32(u32),
+    UInt64(u64),
+    Float(f32),
+    Double(f64),
+    Byte(u8),
+    /// `StrProperty`, `NameProperty`, `ObjectProperty` and `EnumProperty` elements.
+    Str(FString),
+    Struct(Vec<Property>),
+}
+
+#[derive(Debug, Clone)]
+pub enum ByteValue {
+    Byte(u8),
+    Label(FString),
+}
+
+#[derive(Debug, Clone)]
+pub enum StructValue {
+    Properties(Vec<Property>),
+    /// Native structs (`Vector`, `Guid`, `DateTime`, ...) kept as their binary layout.
+    Raw(Vec<u8>),
+}
+
+#[derive(Debug, Clone)]
+pub enum ArrayValue {
+    Values(Vec<Value>),
+    Bytes(Vec<u8>),
+    Structs(StructArray),
+    Raw(Vec<u8>),
+}
+
+/// An `ArrayProperty` of structs carries its own inner property tag.
+#[derive(Debug, Clone)]
+pub struct StructArray {
+    pub name: FString,
+    pub type_name: FString,
+    pub array_index: i32,
+    pub struct_type: FString,
+    pub struct_guid: Guid,
+    pub guid: Option<Guid>,
+    pub elements: Vec<StructValue>,
+}
+
+#[derive(Debug, Clone)]
+pub enum SetValue {
+    Elements { removed: Vec<Value>, elements: Vec<Value> },
+    Raw(Vec<u8>),
+}
+
+#[derive(Debug, Clone)]
+pub enum MapValue {
+    Entries { removed: Vec<Value>, entries: Vec<(Value, Value)> },
+    Raw(Vec<u8>),
+}
+
+impl SaveGame {
+    pub fn parse(data: &[u8]) -> Result<SaveGame> {
+        reader::parse(data)
+    }
+
+    /// Parses `data` and checks that writing it back reproduces every byte, so
+    /// structural edits cannot disturb anything the parser did not understand.
+    pub fn parse_lossless(data: &[u8]) -> Result<SaveGame> {
+        let save = reader::parse(data)?;
+        let written = save.to_bytes();
+
+        if written != data {
+            let offset = written
+                .iter()
+                .zip(data)
+                .position(|(a, b)| a != b)
+                .unwrap_or(written.len().min(data.len()));
+            return Err(Error::InvalidSave {
+                offset,
+                reason: "save does not round-trip through the serializer".to_string(),
+            });
+        }
+
+        Ok(save)
+    }
+
+    /// Serializes the save, recomputing every property size.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        writer::write(self)
+    }
+
+    /// The whole save as a JSON document that [`SaveGame::from_json`] turns
+    /// back into the same bytes.
+    pub fn to_json(&self) -> serde_json::Value {
+        json::to_json(self)
+    }
+
+    /// Rebuilds a save from a document written by [`SaveGame::to_json`],
+    /// possibly edited since. Property offsets are left at zero.
+    pub fn from_json(document: &serde_json::Value) -> Result<SaveGame> {
+        json::from_json(document)
+    }
+
+    /// Byte ranges of the characters of every string value in the written
+    /// save, including the elements of arrays, sets and maps. They are offsets
+    /// in the parsed file when it round-trips, see [`SaveGame::parse_lossless`].
+    pub fn string_ranges(&self) -> Vec<std::ops::Range<usize>> {
+        writer::string_ranges(self)
+    }
+
+    /// Every property in the tree with its path, depth first.
+    pub fn walk(&self) -> Vec<(String, &Property)> {
+        let mut out = Vec::new();
+        walk_properties(&self.properties, "", &mut out);
+        out
+    }
+
+    /// Every property list in the tree with the path of its owner, top level first.
+    pub fn lists(&self) -> Vec<(String, &[Property])> {
+        let mut out = vec![(String::new(), self.properties.as_slice())];
+        for (path, property) in self.walk() {
+            for (index, children) in property.value.children() {
+                let prefix = match index {
+                    Some(index) => format!("{}[{}]", path, index),
+                    None => path.clone(),
+                };
+                out.push((prefix, children.as_slice()));
+            }
+        }
+        out
+    }
+
+    pub fn get(&self, path: &str) -> Option<&Property> {
+        let mut properties = &self.properties;
+        let mut segments = path.split('.').peekable();
+
+        while let Some(segment) = segments.next() {
+            let (name, index) = parse_segment(segment)?;
+            let property = properties.iter().find(|p| p.name == *name)?;
+
+            match index {
+                None if segments.peek().is_none() => return Some(property),
+                None => properties = property.value.child_properties(0)?,
+                Some(index) => properties = property.value.child_properties(index)?,
+            }
+        }
+
+        None
+    }
+
+    pub fn get_mut(&mut self, path: &str) -> Option<&mut Property> {
+        let mut properties = &mut self.properties;
+        let mut segments = path.split('.').peekable();
+
+        while let Some(segment) = segments.next() {
+            let (name, index) = parse_segment(segment)?;
+            let property = properties.iter_mut().find(|p| p.name == *name)?;
+
+            match index {
+                None if segments.peek().is_none() => return Some(property),
+                None => properties = property.value.child_properties_mut(0)?,
+                Some(index) => properties = property.value.child_properties_mut(index)?,
+            }
+        }
+
+        None
+    }
+
+    /// The property whose tag starts at `offset` in the parsed file. Unlike a
+    /// path, which repeats for the elements of a static array, this is unique.
+    pub fn get_at_mut(&mut self, offset: usize) -> Option<&mut Property> {
+        find_at_mut(&mut self.properties, offset)
+    }
+}
+
+impl Property {
+    pub fn as_str(&self) -> Option<&str> {
+        match &self.value {
+            PropertyValue::Simple(Value::Str(s)) => Some(s.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Offset of the value body in the parsed file, just past the property tag.
+    pub fn body_offset(&self) -> usize {
+        let type_header = match &self.value {
+            PropertyValue::Bool(_) => 1,
+            PropertyValue::Struct { struct_type, .. } => struct_type.serialized_len() + 16,
+            PropertyValue::Array { inner_type, .. } | PropertyValue::Set { inner_type, .. } => {
+                inner_type.serialized_len()
+            }
+            PropertyValue::Map { key_type, value_type, .. } => {
+                key_type.serialized_len() + value_type.serialized_len()
+            }
+            PropertyValue::Byte { enum_name, .. }
+            | PropertyValue::Enum { enum_name, .. }
+            | PropertyValue::RawEnum { enum_name, .. } => enum_name.serialized_len(),
+            PropertyValue::Simple(_) | PropertyValue::Raw(_) => 0,
+        };
+        let guid = if self.guid.is_some() { 17 } else { 1 };
+
+        self.offset + self.name.serialized_len() + self.type_name.serialized_len() + 8 + type_header + guid
+    }
+}
+
+impl PropertyValue {
+    /// The nested property list at `index` (structs, struct arrays and struct-valued maps).
+    fn child_properties(&self, index: usize) -> Option<&Vec<Property>> {
+        match self {
+            PropertyValue::Struct { value: StructValue::Properties(p), .. } if index == 0 => Some(p),
+            PropertyValue::Array { value: ArrayValue::Structs(array), .. } => {
+                match array.elements.get(index)? {
+                    StructValue::Properties(p) => Some(p),
+                    StructValue::Raw(_) => None,
+                }
+            }
+            PropertyValue::Array { value: ArrayValue::Values(values), .. } => {
+                match values.get(index)? {
+                    Value::Struct(p) => Some(p),
+                    _ => None,
+                }
+            }
+            PropertyValue::Set { value: SetValue::Elements { elements, .. }, .. } => {
+                match elements.get(index)? {
+                    Value::Struct(p) => Some(p),
+                    _ => None,
+                }
+            }
+            PropertyValue::Map { value: MapValue::Entries { entries, .. }, .. } => {
+                match &entries.get(index)?.1 {
+                    Value::Struct(p) => Some(p),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn child_properties_mut(&mut self, index: usize) -> Option<&mut Vec<Property>> {
+        match self {
+            PropertyValue::Struct { value: StructValue::Properties(p), .. } if index == 0 => Some(p),
+            PropertyValue::Array { value: ArrayValue::Structs(array), .. } => {
+                match array.elements.get_mut(index)? {
+                    StructValue::Properties(p) => Some(p),
+                    StructValue::Raw(_) => None,
+                }
+            }
+            PropertyValue::Array { value: ArrayValue::Values(values), .. } => {
+                match values.get_mut(index)? {
+                    Value::Struct(p) => Some(p),
+                    _ => None,
+                }
+            }
+            PropertyValue::Set { value: SetValue::Elements { elements, .. }, .. } => {
+                match elements.get_mut(index)? {
+                    Value::Struct(p) => Some(p),
+                    _ => None,
+                }
+            }
+            PropertyValue::Map { value: MapValue::Entries { entries, .. }, .. } => {
+                match &mut entries.get_mut(index)?.1 {
+                    Value::Struct(p) => Some(p),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// The nested property lists, with the element index for containers.
+    pub fn children(&self) -> Vec<(Option<usize>, &Vec<Property>)> {
+        match self {
+            PropertyValue::Struct { value: StructValue::Properties(p), .. } => vec![(None, p)],
+            PropertyValue::Array { value: ArrayValue::Structs(array), .. } => array
+                .elements
+                .iter()
+                .enumerate()
+                .filter_map(|(i, element)| match element {
+                    StructValue::Properties(p) => Some((Some(i), p)),
+                    StructValue::Raw(_) => None,
+                })
+                .collect(),
+            PropertyValue::Array { value: ArrayValue::Values(values), .. }
+            | PropertyValue::Set { value: SetValue::Elements { elements: values, .. }, .. } => values
+                .iter()
+                .enumerate()
+                .filter_map(|(i, value)| match value {
+                    Value::Struct(p) => Some((Some(i), p)),
+                    _ => None,
+                })
+                .collect(),
+            PropertyValue::Map { value: MapValue::Entries { entries, .. }, .. } => entries
+                .iter()
+                .enumerate()
+                .filter_map(|(i, (_, value))| match value {
+                    Value::Struct(p) => Some((Some(i), p)),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut Vec<Property>> {
+        match self {
+            PropertyValue::Struct { value: StructValue::Properties(p), .. } => vec![p],
+            PropertyValue::Array { value: ArrayValue::Structs(array), .. } => array
+                .elements
+                .iter_mut()
+                .filter_map(|element| match element {
+                    StructValue::Properties(p) => Some(p),
+                    StructValue::Raw(_) => None,
+                })
+                .collect(),
+            PropertyValue::Array { value: ArrayValue::Values(values), .. }
+            | PropertyValue::Set { value: SetValue::Elements { elements: values, .. }, .. } => values
+                .iter_mut()
+                .filter_map(|value| match value {
+                    Value::Struct(p) => Some(p),
+                    _ => None,
+                })
+                .collect(),
+            PropertyValue::Map { value: MapValue::Entries { entries, .. }, .. } => entries
+                .iter_mut()
+                .filter_map(|(_, value)| match value {
+                    Value::Struct(p) => Some(p),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+fn walk_properties<'a>(properties: &'a [Property], prefix: &str, out: &mut Vec<(String, &'a Property)>) {
+    for property in properties {
+        let path = if prefix.is_empty() {
+            property.name.to_string()
+        } else {
+            format!("{}.{}", prefix, property.name)
+        };
+
+        out.push((path.clone(), property));
+        for (index, children) in property.value.children() {
+            let child_prefix = match index {
+                Some(index) => format!("{}[{}]", path, index),
+                None => path.clone(),
+            };
+            walk_properties(children, &child_prefix, out);
+        }
+    }
+}
+
+fn find_at_mut(properties: &mut [Property], offset: usize) -> Option<&mut Property> {
+    for property in properties {
+        if property.offset == offset {
+            return Some(property);
+        }
+        for children in property.value.children_mut() {
+            if let Some(found) = find_at_mut(children, offset) {
+                return Some(found);
+            }
+        }
+    }
+    None
+}
+
+fn parse_segment(segment: &str) -> Option<(&str, Option<usize>)> {
+    match segment.split_once('[') {
+        Some((name, rest)) => {
+            let index = rest.strip_suffix(']')?.parse().ok()?;
+            Some((name, Some(index)))
+        }
+        None => Some((segment, None)),
+    }
+}
@@ -0,0 +1,416 @@
+use super::*;
+use crate::error::{Error, Result};
+
+const MAGIC: &[u8] = b"GVAS";
+
+/// Struct types serialized as a fixed binary layout instead of a property list.
+pub(crate) const NATIVE_STRUCTS: &[&str] = &[
+    "Vector", "Vector2D", "Vector4", "IntVector", "IntPoint", "Rotator", "Quat",
+    "LinearColor", "Color", "Guid", "DateTime", "Timespan", "Box", "Box2D",
+];
+
+pub(crate) fn parse(data: &[u8]) -> Result<SaveGame> {
+    let mut reader = Reader::new(data);
+
+    if reader.bytes(MAGIC.len())? != MAGIC {
+        return Err(reader.error("missing GVAS magic"));
+    }
+
+    let header = reader.header()?;
+    let properties = reader.properties()?;
+    let trailer = reader.bytes(reader.remaining())?.to_vec();
+
+    Ok(SaveGame {
+        header,
+        properties,
+        trailer,
+    })
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+    end: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self {
+            data,
+            pos: 0,
+            end: data.len(),
+        }
+    }
+
+    /// A reader over the next `len` bytes; offsets stay absolute.
+    fn sub(&self, len: usize) -> Result<Reader<'a>> {
+        if len > self.remaining() {
+            return Err(self.error("value size exceeds enclosing data"));
+        }
+        Ok(Reader {
+            data: self.data,
+            pos: self.pos,
+            end: self.pos + len,
+        })
+    }
+
+    fn remaining(&self) -> usize {
+        self.end - self.pos
+    }
+
+    fn error(&self, reason: &str) -> Error {
+        Error::InvalidSave {
+            offset: self.pos,
+            reason: reason.to_string(),
+        }
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        if len > self.remaining() {
+            return Err(self.error("unexpected end of data"));
+        }
+        let bytes = &self.data[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        Ok(self.bytes(N)?.try_into().unwrap())
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.array::<1>()?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        Ok(u16::from_le_bytes(self.array()?))
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+
+    fn i32(&mut self) -> Result<i32> {
+        Ok(i32::from_le_bytes(self.array()?))
+    }
+
+    fn count(&mut self) -> Result<usize> {
+        let count = self.i32()?;
+        if count < 0 || count as usize > self.remaining() {
+            return Err(self.error("invalid element count"));
+        }
+        Ok(count as usize)
+    }
+
+    fn guid(&mut self) -> Result<Guid> {
+        self.array()
+    }
+
+    fn optional_guid(&mut self) -> Result<Option<Guid>> {
+        match self.u8()? {
+            0 => Ok(None),
+            _ => Ok(Some(self.guid()?)),
+        }
+    }
+
+    fn fstring(&mut self) -> Result<FString> {
+        let len = self.i32()?;
+
+        if len == 0 {
+            return Ok(FString::default());
+        }
+
+        if len > 0 {
+            let bytes = self.bytes(len as usize)?;
+            let (&terminator, text) = bytes.split_last().unwrap();
+            if terminator != 0 {
+                return Err(self.error("string is not null terminated"));
+            }
+            return Ok(FString {
+                value: Some(text.iter().map(|&b| b as char).collect()),
+                wide: false,
+            });
+        }
+
+        let units = len.unsigned_abs() as usize;
+        let bytes = self.bytes(units * 2)?;
+        let mut text: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+            .collect();
+        if text.pop() != Some(0) {
+            return Err(self.error("string is not null terminated"));
+        }
+        let value = String::from_utf16(&text).map_err(|_| self.error("invalid UTF-16 string"))?;
+
+        Ok(FString {
+            value: Some(value),
+            wide: true,
+        })
+    }
+
+    fn header(&mut self) -> Result<Header> {
+        let save_game_version = self.i32()?;
+        let package_version = self.i32()?;
+        let package_version_ue5 = if save_game_version >= 3 {
+            Some(self.i32()?)
+        } else {
+            None
+        };
+
+        let engine_version = EngineVersion {
+            major: self.u16()?,
+            minor: self.u16()?,
+            patch: self.u16()?,
+            changelist: self.u32()?,
+            branch: self.fstring()?,
+        };
+
+        let custom_version_format = self.i32()?;
+        let count = self.count()?;
+        let mut custom_versions = Vec::with_capacity(count);
+        for _ in 0..count {
+            custom_versions.push(CustomVersion {
+                guid: self.guid()?,
+                version: self.i32()?,
+            });
+        }
+
+        Ok(Header {
+            save_game_version,
+            package_version,
+            package_version_ue5,
+            engine_version,
+            custom_version_format,
+            custom_versions,
+            save_game_class_name: self.fstring()?,
+        })
+    }
+
+    /// A property list terminated by a `None` name.
+    fn properties(&mut self) -> Result<Vec<Property>> {
+        let mut properties = Vec::new();
+        while let Some(property) = self.property()? {
+            properties.push(property);
+        }
+        Ok(properties)
+    }
+
+    fn property(&mut self) -> Result<Option<Property>> {
+        let offset = self.pos;
+        let name = self.fstring()?;
+        if name == *"None" {
+            return Ok(None);
+        }
+
+        let type_name = self.fstring()?;
+        let size = self.i32()?;
+        let array_index = self.i32()?;
+        if size < 0 {
+            return Err(self.error("negative property size"));
+        }
+        let size = size as usize;
+
+        let (value, guid) = match type_name.as_str() {
+            "BoolProperty" => {
+                let value = self.u8()? != 0;
+                let guid = self.optional_guid()?;
+                (PropertyValue::Bool(value), guid)
+            }
+            "StructProperty" => {
+                let struct_type = self.fstring()?;
+                let struct_guid = self.guid()?;
+                let guid = self.optional_guid()?;
+                let value = self.body(size, |r| r.struct_value(struct_type.as_str()))?;
+                let value = value.unwrap_or_else(StructValue::Raw);
+                (PropertyValue::Struct { struct_type, struct_guid, value }, guid)
+            }
+            "ArrayProperty" => {
+                let inner_type = self.fstring()?;
+                let guid = self.optional_guid()?;
+                let value = self.body(size, |r| r.array_value(inner_type.as_str()))?;
+                let value = value.unwrap_or_else(ArrayValue::Raw);
+                (PropertyValue::Array { inner_type, value }, guid)
+            }
+            "SetProperty" => {
+                let inner_type = self.fstring()?;
+                let guid = self.optional_guid()?;
+                let value = self.body(size, |r| {
+                    let removed = r.values(inner_type.as_str())?;
+                    let elements = r.values(inner_type.as_str())?;
+                    Ok(SetValue::Elements { removed, elements })
+                })?;
+                let value = value.unwrap_or_else(SetValue::Raw);
+                (PropertyValue::Set { inner_type, value }, guid)
+            }
+            "MapProperty" => {
+                let key_type = self.fstring()?;
+                let value_type = self.fstring()?;
+                let guid = self.optional_guid()?;
+                let value = self.body(size, |r| r.map_value(key_type.as_str(), value_type.as_str()))?;
+                let value = value.unwrap_or_else(MapValue::Raw);
+                (PropertyValue::Map { key_type, value_type, value }, guid)
+            }
+            "ByteProperty" => {
+                let enum_name = self.fstring()?;
+                let guid = self.optional_guid()?;
+                let value = if enum_name == *"None" {
+                    self.body(size, |r| Ok(ByteValue::Byte(r.u8()?)))?
+                } else {
+                    self.body(size, |r| Ok(ByteValue::Label(r.fstring()?)))?
+                };
+                match value {
+                    Ok(value) => (PropertyValue::Byte { enum_name, value }, guid),
+                    Err(raw) => (PropertyValue::RawEnum { enum_name, raw }, guid),
+                }
+            }
+            "EnumProperty" => {
+                let enum_name = self.fstring()?;
+                let guid = self.optional_guid()?;
+                match self.body(size, |r| r.fstring())? {
+                    Ok(value) => (PropertyValue::Enum { enum_name, value }, guid),
+                    Err(raw) => (PropertyValue::RawEnum { enum_name, raw }, guid),
+                }
+            }
+            _ => {
+                let guid = self.optional_guid()?;
+                match self.body(size, |r| r.value(type_name.as_str()))? {
+                    Ok(value) => (PropertyValue::Simple(value), guid),
+                    Err(raw) => (PropertyValue::Raw(raw), guid),
+                }
+            }
+        };
+
+        Ok(Some(Property {
+            name,
+            type_name,
+            array_index,
+            guid,
+            value,
+            offset,
+        }))
+    }
+
+    /// Parses a `size`-byte property body. A body that fails to parse or is not
+    /// fully consumed is returned as raw bytes instead.
+    fn body<T>(
+        &mut self,
+        size: usize,
+        parse: impl FnOnce(&mut Reader<'a>) -> Result<T>,
+    ) -> Result<std::result::Result<T, Vec<u8>>> {
+        let mut body = self.sub(size)?;
+        let parsed = match parse(&mut body) {
+            Ok(value) if body.remaining() == 0 => Ok(value),
+            _ => Err(self.data[self.pos..self.pos + size].to_vec()),
+        };
+        self.pos += size;
+        Ok(parsed)
+    }
+
+    fn struct_value(&mut self, struct_type: &str) -> Result<StructValue> {
+        if NATIVE_STRUCTS.contains(&struct_type) {
+            return Ok(StructValue::Raw(self.bytes(self.remaining())?.to_vec()));
+        }
+        Ok(StructValue::Properties(self.properties()?))
+    }
+
+    fn array_value(&mut self, inner_type: &str) -> Result<ArrayValue> {
+        let count = self.count()?;
+
+        match inner_type {
+            "StructProperty" => {
+                let name = self.fstring()?;
+                let type_name = self.fstring()?;
+                let size = self.i32()?;
+                let array_index = self.i32()?;
+                let struct_type = self.fstring()?;
+                let struct_guid = self.guid()?;
+                let guid = self.optional_guid()?;
+
+                if size < 0 || size as usize != self.remaining() {
+                    return Err(self.error("struct array size mismatch"));
+                }
+
+                let mut elements = Vec::with_capacity(count);
+                if NATIVE_STRUCTS.contains(&struct_type.as_str()) && count > 0 {
+                    if !self.remaining().is_multiple_of(count) {
+                        return Err(self.error("native struct array is not evenly sized"));
+                    }
+                    let element_size = self.remaining() / count;
+                    for _ in 0..count {
+                        elements.push(StructValue::Raw(self.bytes(element_size)?.to_vec()));
+                    }
+                } else {
+                    for _ in 0..count {
+                        elements.push(StructValue::Properties(self.properties()?));
+                    }
+                }
+
+                Ok(ArrayValue::Structs(StructArray {
+                    name,
+                    type_name,
+                    array_index,
+                    struct_type,
+                    struct_guid,
+                    guid,
+                    elements,
+                }))
+            }
+            "ByteProperty" if self.remaining() == count => {
+                Ok(ArrayValue::Bytes(self.bytes(count)?.to_vec()))
+            }
+            _ => {
+                let mut values = Vec::with_capacity(count);
+                for _ in 0..count {
+                    values.push(self.value(inner_type)?);
+                }
+                Ok(ArrayValue::Values(values))
+            }
+        }
+    }
+
+    fn map_value(&mut self, key_type: &str, value_type: &str) -> Result<MapValue> {
+        let removed = self.values(key_type)?;
+
+        let count = self.count()?;
+        let mut entries = Vec::with_capacity(count);
+        for _ in 0..count {
+            let key = self.value(key_type)?;
+            let value = self.value(value_type)?;
+            entries.push((key, value));
+        }
+
+        Ok(MapValue::Entries { removed, entries })
+    }
+
+    /// A count-prefixed run of untagged values.
+    fn values(&mut self, type_name: &str) -> Result<Vec<Value>> {
+        let count = self.count()?;
+        let mut values = Vec::with_capacity(count);
+        for _ in 0..count {
+            values.push(self.value(type_name)?);
+        }
+        Ok(values)
+    }
+
+    fn value(&mut self, type_name: &str) -> Result<Value> {
+        Ok(match type_name {
+            "BoolProperty" => Value::Bool(self.u8()? != 0),
+            "Int8Property" => Value::Int8(i8::from_le_bytes(self.array()?)),
+            "Int16Property" => Value::Int16(i16::from_le_bytes(self.array()?)),
+            "IntProperty" => Value::Int(self.i32()?),
+            "Int64Property" => Value::Int64(i64::from_le_bytes(self.array()?)),
+            "UInt16Property" => Value::UInt16(self.u16()?),
+            "UInt32Property" => Value::UInt32(self.u32()?),
+            "UInt64Property" => Value::UInt64(u64::from_le_bytes(self.array()?)),
+            "FloatProperty" => Value::Float(f32::from_le_bytes(self.array()?)),
+            "DoubleProperty" => Value::Double(f64::from_le_bytes(self.array()?)),
+            "ByteProperty" => Value::Byte(self.u8()?),
+            "StrProperty" | "NameProperty" | "ObjectProperty" | "EnumProperty" => {
+                Value::Str(self.fstring()?)
+            }
+            "StructProperty" => Value::Struct(self.properties()?),
+            _ => return Err(self.error("unsupported property type")),
+        })
+    }
+}
@@ -0,0 +1,259 @@
+use super::*;
+use std::ops::Range;
+
+const MAGIC: &[u8] = b"GVAS";
+
+pub(crate) fn write(save: &SaveGame) -> Vec<u8> {
+    let mut writer = Writer::default();
+
+    writer.bytes(MAGIC);
+    writer.header(&save.header);
+    writer.properties(&save.properties);
+    writer.bytes(&save.trailer);
+
+    writer.buf
+}
+
+/// Where the characters of every string value land when `save` is written.
+pub(crate) fn string_ranges(save: &SaveGame) -> Vec<Range<usize>> {
+    let mut writer = Writer::default();
+
+    writer.bytes(MAGIC);
+    writer.header(&save.header);
+    writer.properties(&save.properties);
+
+    writer.strings
+}
+
+#[derive(Default)]
+struct Writer {
+    buf: Vec<u8>,
+    /// Character ranges of the `Value::Str` values written so far.
+    strings: Vec<Range<usize>>,
+}
+
+impl Writer {
+    fn bytes(&mut self, bytes: &[u8]) {
+        self.buf.extend_from_slice(bytes);
+    }
+
+    fn u8(&mut self, value: u8) {
+        self.buf.push(value);
+    }
+
+    fn i32(&mut self, value: i32) {
+        self.bytes(&value.to_le_bytes());
+    }
+
+    fn count(&mut self, count: usize) {
+        self.i32(count as i32);
+    }
+
+    fn optional_guid(&mut self, guid: &Option<Guid>) {
+        match guid {
+            Some(guid) => {
+                self.u8(1);
+                self.bytes(guid);
+            }
+            None => self.u8(0),
+        }
+    }
+
+    fn fstring(&mut self, s: &FString) {
+        let Some(value) = &s.value else {
+            self.i32(0);
+            return;
+        };
+
+        if s.is_written_wide() {
+            let units: Vec<u16> = value.encode_utf16().chain(std::iter::once(0)).collect();
+            self.i32(-(units.len() as i32));
+            for unit in units {
+                self.bytes(&unit.to_le_bytes());
+            }
+        } else {
+            self.i32(value.chars().count() as i32 + 1);
+            self.buf.extend(value.chars().map(|c| c as u8));
+            self.u8(0);
+        }
+    }
+
+    /// Reserves a 32-bit size field to be filled in by `end_size`.
+    fn begin_size(&mut self) -> usize {
+        let at = self.buf.len();
+        self.i32(0);
+        at
+    }
+
+    fn end_size(&mut self, at: usize, body_start: usize) {
+        let size = (self.buf.len() - body_start) as i32;
+        self.buf[at..at + 4].copy_from_slice(&size.to_le_bytes());
+    }
+
+    fn header(&mut self, header: &Header) {
+        self.i32(header.save_game_version);
+        self.i32(header.package_version);
+        if let Some(version) = header.package_version_ue5 {
+            self.i32(version);
+        }
+
+        let engine = &header.engine_version;
+        self.bytes(&engine.major.to_le_bytes());
+        self.bytes(&engine.minor.to_le_bytes());
+        self.bytes(&engine.patch.to_le_bytes());
+        self.bytes(&engine.changelist.to_le_bytes());
+        self.fstring(&engine.branch);
+
+        self.i32(header.custom_version_format);
+        self.count(header.custom_versions.len());
+        for custom in &header.custom_versions {
+            self.bytes(&custom.guid);
+            self.i32(custom.version);
+        }
+
+        self.fstring(&header.save_game_class_name);
+    }
+
+    fn properties(&mut self, properties: &[Property]) {
+        for property in properties {
+            self.property(property);
+        }
+        self.fstring(&FString::new("None"));
+    }
+
+    fn property(&mut self, property: &Property) {
+        self.fstring(&property.name);
+        self.fstring(&property.type_name);
+        let size_at = self.begin_size();
+        self.i32(property.array_index);
+
+        match &property.value {
+            PropertyValue::Bool(value) => {
+                self.u8(*value as u8);
+                self.optional_guid(&property.guid);
+            }
+            PropertyValue::Struct { struct_type, struct_guid, .. } => {
+                self.fstring(struct_type);
+                self.bytes(struct_guid);
+                self.optional_guid(&property.guid);
+            }
+            PropertyValue::Array { inner_type, .. } | PropertyValue::Set { inner_type, .. } => {
+                self.fstring(inner_type);
+                self.optional_guid(&property.guid);
+            }
+            PropertyValue::Map { key_type, value_type, .. } => {
+                self.fstring(key_type);
+                self.fstring(value_type);
+                self.optional_guid(&property.guid);
+            }
+            PropertyValue::Byte { enum_name, .. }
+            | PropertyValue::Enum { enum_name, .. }
+            | PropertyValue::RawEnum { enum_name, .. } => {
+                self.fstring(enum_name);
+                self.optional_guid(&property.guid);
+            }
+            PropertyValue::Simple(_) | PropertyValue::Raw(_) => {
+                self.optional_guid(&property.guid);
+            }
+        }
+
+        let body_start = self.buf.len();
+        match &property.value {
+            PropertyValue::Bool(_) => {}
+            PropertyValue::Simple(value) => self.value(value),
+            PropertyValue::Byte { value, .. } => match value {
+                ByteValue::Byte(byte) => self.u8(*byte),
+                ByteValue::Label(label) => self.fstring(label),
+            },
+            PropertyValue::Enum { value, .. } => self.fstring(value),
+            PropertyValue::Struct { value, .. } => self.struct_value(value),
+            PropertyValue::Array { value, .. } => self.array_value(value),
+            PropertyValue::Set { value, .. } => match value {
+                SetValue::Elements { removed, elements } => {
+                    self.values(removed);
+                    self.values(elements);
+                }
+                SetValue::Raw(raw) => self.bytes(raw),
+            },
+            PropertyValue::Map { value, .. } => match value {
+                MapValue::Entries { removed, entries } => {
+                    self.values(removed);
+                    self.count(entries.len());
+                    for (key, value) in entries {
+                        self.value(key);
+                        self.value(value);
+                    }
+                }
+                MapValue::Raw(raw) => self.bytes(raw),
+            },
+            PropertyValue::RawEnum { raw, .. } | PropertyValue::Raw(raw) => self.bytes(raw),
+        }
+        self.end_size(size_at, body_start);
+    }
+
+    fn struct_value(&mut self, value: &StructValue) {
+        match value {
+            StructValue::Properties(properties) => self.properties(properties),
+            StructValue::Raw(raw) => self.bytes(raw),
+        }
+    }
+
+    fn array_value(&mut self, value: &ArrayValue) {
+        match value {
+            ArrayValue::Values(values) => self.values(values),
+            ArrayValue::Bytes(bytes) => {
+                self.count(bytes.len());
+                self.bytes(bytes);
+            }
+            ArrayValue::Structs(array) => {
+                self.count(array.elements.len());
+                self.fstring(&array.name);
+                self.fstring(&array.type_name);
+                let size_at = self.begin_size();
+                self.i32(array.array_index);
+                self.fstring(&array.struct_type);
+                self.bytes(&array.struct_guid);
+                self.optional_guid(&array.guid);
+
+                let body_start = self.buf.len();
+                for element in &array.elements {
+                    self.struct_value(element);
+                }
+                self.end_size(size_at, body_start);
+            }
+            ArrayValue::Raw(raw) => self.bytes(raw),
+        }
+    }
+
+    fn values(&mut self, values: &[Value]) {
+        self.count(values.len());
+        for value in values {
+            self.value(value);
+        }
+    }
+
+    fn value(&mut self, value: &Value) {
+        match value {
+            Value::Bool(value) => self.u8(*value as u8),
+            Value::Int8(value) => self.bytes(&value.to_le_bytes()),
+            Value::Int16(value) => self.bytes(&value.to_le_bytes()),
+            Value::Int(value) => self.bytes(&value.to_le_bytes()),
+            Value::Int64(value) => self.bytes(&value.to_le_bytes()),
+            Value::UInt16(value) => self.bytes(&value.to_le_bytes()),
+            Value::UInt32(value) => self.bytes(&value.to_le_bytes()),
+            Value::UInt64(value) => self.bytes(&value.to_le_bytes()),
+            Value::Float(value) => self.bytes(&value.to_le_bytes()),
+            Value::Double(value) => self.bytes(&value.to_le_bytes()),
+            Value::Byte(value) => self.u8(*value),
+            Value::Str(value) => {
+                let start = self.buf.len() + 4;
+                self.fstring(value);
+                if !value.is_null() {
+                    let terminator = if value.is_written_wide() { 2 } else { 1 };
+                    self.strings.push(start..self.buf.len() - terminator);
+                }
+            }
+            Value::Struct(properties) => self.properties(properties),
+        }
+    }
+}
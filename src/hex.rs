@@ -0,0 +1,144 @@
+//! Finding things in the raw bytes of a save: the markers the editors rely on,
+//! and searches typed into the hex viewer.
+
+use crate::error::{Error, Result};
+use crate::occurrences;
+use crate::replace::STEAMID_LEN;
+use std::fmt;
+
+const STR_PROPERTY: &[u8] = b"StrProperty";
+/// The `None` name that ends a property list, with its length prefix.
+const NONE_TAG: &[u8] = b"\x05\x00\x00\x00None\x00";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Marker {
+    SteamId,
+    /// The `StrProperty` type name the owner search starts from.
+    StrProperty,
+    /// The `None` terminator that ends the owner property's list.
+    NoneTag,
+}
+
+impl fmt::Display for Marker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Marker::SteamId => write!(f, "SteamID"),
+            Marker::StrProperty => write!(f, "StrProperty"),
+            Marker::NoneTag => write!(f, "None"),
+        }
+    }
+}
+
+/// A marked byte range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Highlight {
+    pub offset: usize,
+    pub len: usize,
+    pub marker: Marker,
+}
+
+impl Highlight {
+    pub fn end(&self) -> usize {
+        self.offset + self.len
+    }
+}
+
+/// SteamID occurrences, `StrProperty` type names and `None` terminators,
+/// ordered by offset.
+pub fn markers(data: &[u8]) -> Vec<Highlight> {
+    let mut highlights: Vec<Highlight> = occurrences::find_occurrences(data)
+        .into_iter()
+        .map(|o| Highlight {
+            offset: o.offset,
+            len: STEAMID_LEN,
+            marker: Marker::SteamId,
+        })
+        .collect();
+    for (pattern, marker) in [(STR_PROPERTY, Marker::StrProperty), (NONE_TAG, Marker::NoneTag)] {
+        highlights.extend(find_all(data, pattern).into_iter().map(|offset| Highlight {
+            offset,
+            len: pattern.len(),
+            marker,
+        }));
+    }
+    highlights.sort_by_key(|h| h.offset);
+    highlights
+}
+
+/// How search text is turned into bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SearchKind {
+    /// Hex bytes such as `05 00 00 00` or `DEADBEEF`.
+    #[default]
+    Bytes,
+    Ascii,
+    /// Text as UTF-16LE, the way non-ASCII `FString`s are stored.
+    Utf16,
+}
+
+impl SearchKind {
+    pub const ALL: [SearchKind; 3] = [SearchKind::Bytes, SearchKind::Ascii, SearchKind::Utf16];
+}
+
+impl fmt::Display for SearchKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SearchKind::Bytes => write!(f, "Hex bytes"),
+            SearchKind::Ascii => write!(f, "ASCII"),
+            SearchKind::Utf16 => write!(f, "UTF-16"),
+        }
+    }
+}
+
+/// The bytes to search for.
+pub fn pattern(text: &str, kind: SearchKind) -> Result<Vec<u8>> {
+    match kind {
+        SearchKind::Bytes => parse_bytes(text),
+        SearchKind::Ascii => Ok(text.as_bytes().to_vec()),
+        SearchKind::Utf16 => Ok(text.encode_utf16().flat_map(u16::to_le_bytes).collect()),
+    }
+}
+
+/// Hex bytes, optionally separated by spaces, e.g. `05 00 00 00` or `0500`.
+pub fn parse_bytes(text: &str) -> Result<Vec<u8>> {
+    let digits: String = text.split_whitespace().collect();
+    let is_hex = digits.bytes().all(|b| b.is_ascii_hexdigit());
+    if digits.is_empty() || !is_hex || !digits.len().is_multiple_of(2) {
+        return Err(Error::InvalidHexBytes(text.to_string()));
+    }
+    Ok((0..digits.len())
+        .step_by(2)
+        .filter_map(|i| u8::from_str_radix(&digits[i..i + 2], 16).ok())
+        .collect())
+}
+
+/// `05 00 00 00`
+pub fn format_bytes(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02X}", b)).collect::<Vec<_>>().join(" ")
+}
+
+/// An offset in decimal or `0x` hex.
+pub fn parse_offset(text: &str) -> Option<usize> {
+    let text = text.trim();
+    match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        Some(hex) => usize::from_str_radix(hex, 16).ok(),
+        None => text.parse().ok(),
+    }
+}
+
+/// Every offset where `needle` starts, including overlapping matches.
+pub fn find_all(data: &[u8], needle: &[u8]) -> Vec<usize> {
+    let Some(&first) = needle.first() else {
+        return Vec::new();
+    };
+    let mut offsets = Vec::new();
+    let mut start = 0;
+    while let Some(found) = data[start..].iter().position(|&b| b == first) {
+        let offset = start + found;
+        if data[offset..].starts_with(needle) {
+            offsets.push(offset);
+        }
+        start = offset + 1;
+    }
+    offsets
+}
@@ -0,0 +1,224 @@
+use std::path::Path;
+use steamid_replacer::backup::BackupStore;
+use steamid_replacer::files;
+use steamid_replacer::hex::{self, Highlight, Marker, SearchKind};
+use steamid_replacer::patch::{self, Patch, PatchEdit, PatchOptions};
+
+/// Bytes per row of the hex view.
+pub const ROW_WIDTH: usize = 16;
+/// Rows kept above a go-to target when scrolling to it.
+const ROWS_ABOVE_TARGET: usize = 4;
+
+#[derive(Default)]
+pub struct HexViewTab {
+    pub file_path: String,
+    /// The file as it is on disk.
+    pub data: Vec<u8>,
+    /// `data` with the staged edits applied; this is what the view shows.
+    pub edited: Vec<u8>,
+    /// Hex bytes to write at the cursor.
+    pub edit_bytes: String,
+    /// Edits waiting to be written.
+    pub staged: Patch,
+    /// SteamIDs and owner property markers, ordered by offset.
+    pub highlights: Vec<Highlight>,
+    pub goto: String,
+    pub search_kind: SearchKind,
+    pub search: String,
+    /// Offsets where the last search matched.
+    pub matches: Vec<usize>,
+    pub match_len: usize,
+    pub current_match: Option<usize>,
+    /// The selected byte.
+    pub cursor: Option<usize>,
+    /// Row to scroll to on the next frame.
+    pub scroll_to_row: Option<usize>,
+    pub status: String,
+    pub backup_filename: String,
+}
+
+impl HexViewTab {
+    /// Reads `file_path` and finds its markers.
+    pub fn load(&mut self) {
+        self.data.clear();
+        self.edited.clear();
+        self.staged = Patch::default();
+        self.highlights.clear();
+        self.matches.clear();
+        self.current_match = None;
+        self.cursor = None;
+        self.scroll_to_row = Some(0);
+        if self.file_path.is_empty() {
+            return;
+        }
+
+        match files::read_save(Path::new(&self.file_path)) {
+            Ok(data) => {
+                self.highlights = hex::markers(&data);
+                self.edited = data.clone();
+                self.data = data;
+                let steamids = self.highlights.iter().filter(|h| h.marker == Marker::SteamId).count();
+                self.status = format!("{} bytes, {} SteamID occurrence(s)", self.data.len(), steamids);
+            }
+            Err(e) => self.status = format!("❌ Error: {}", e),
+        }
+    }
+
+    pub fn handle_goto(&mut self) {
+        match hex::parse_offset(&self.goto) {
+            Some(offset) if offset < self.data.len() => self.select(offset),
+            Some(offset) => self.status = format!("(!) Offset 0x{:X} is past the end of the file", offset),
+            None => self.status = "(!) Enter an offset in decimal or 0x hex".to_string(),
+        }
+    }
+
+    /// Searches the whole file and selects the first match after the cursor.
+    pub fn handle_search(&mut self) {
+        let pattern = match hex::pattern(&self.search, self.search_kind) {
+            Ok(pattern) if !pattern.is_empty() => pattern,
+            Ok(_) => {
+                self.status = "(!) Enter something to search for".to_string();
+                return;
+            }
+            Err(e) => {
+                self.status = format!("❌ Error: {}", e);
+                return;
+            }
+        };
+        self.matches = hex::find_all(&self.edited, &pattern);
+        self.match_len = pattern.len();
+        self.current_match = None;
+        if self.matches.is_empty() {
+            self.status = format!("(!) {} not found", self.search_kind);
+            return;
+        }
+
+        let from = self.cursor.unwrap_or_default();
+        let index = self.matches.iter().position(|&offset| offset >= from).unwrap_or_default();
+        self.show_match(index);
+    }
+
+    /// Moves to the next or previous match, wrapping around.
+    pub fn step_match(&mut self, forward: bool) {
+        if self.matches.is_empty() {
+            self.handle_search();
+            return;
+        }
+        let count = self.matches.len();
+        let index = match (self.current_match, forward) {
+            (Some(index), true) => (index + 1) % count,
+            (Some(index), false) => (index + count - 1) % count,
+            (None, _) => 0,
+        };
+        self.show_match(index);
+    }
+
+    fn show_match(&mut self, index: usize) {
+        self.current_match = Some(index);
+        self.select(self.matches[index]);
+        self.status = format!("Match {} of {} at 0x{:08X}", index + 1, self.matches.len(), self.matches[index]);
+    }
+
+    /// Selects `offset` and scrolls it into view.
+    pub fn select(&mut self, offset: usize) {
+        self.cursor = Some(offset);
+        self.scroll_to_row = Some((offset / ROW_WIDTH).saturating_sub(ROWS_ABOVE_TARGET));
+        self.status = self.describe(offset);
+    }
+
+    /// `0x00000C34 = 0x37 (55) in SteamID`
+    pub fn describe(&self, offset: usize) -> String {
+        let Some(byte) = self.edited.get(offset) else {
+            return String::new();
+        };
+        let mut text = format!("0x{:08X} = 0x{:02X} ({})", offset, byte, byte);
+        if let Some(highlight) = self.highlight_at(offset) {
+            text.push_str(&format!(" in {} at 0x{:08X}", highlight.marker, highlight.offset));
+        }
+        text
+    }
+
+    /// Stages writing `edit_bytes` at the cursor.
+    pub fn stage_edit(&mut self) {
+        let Some(offset) = self.cursor else {
+            self.status = "(!) Click a byte or go to an offset first".to_string();
+            return;
+        };
+        let result = hex::parse_bytes(&self.edit_bytes).and_then(|bytes| PatchEdit::at(&self.edited, offset, bytes));
+        match result {
+            Ok(edit) => {
+                self.edited[offset..offset + edit.new.len()].copy_from_slice(&edit.new);
+                self.status = format!(
+                    "Staged {} -> {} at 0x{:08X}; click Apply to write it",
+                    hex::format_bytes(&edit.old),
+                    hex::format_bytes(&edit.new),
+                    offset
+                );
+                self.staged.edits.push(edit);
+                self.backup_filename.clear();
+            }
+            Err(e) => self.status = format!("❌ Error: {}", e),
+        }
+    }
+
+    /// Stages the edits of a patch file, if they fit the file as shown.
+    pub fn load_patch(&mut self, path: &Path) {
+        match Patch::load(path).and_then(|loaded| Ok((patch::apply(&self.edited, &loaded)?, loaded))) {
+            Ok((edited, loaded)) => {
+                self.edited = edited;
+                self.status = format!("Staged {} edit(s) from {}; click Apply to write them", loaded.edits.len(), files::backup_file_name(path));
+                self.staged.edits.extend(loaded.edits);
+                self.backup_filename.clear();
+            }
+            Err(e) => self.status = format!("❌ Error: {}", e),
+        }
+    }
+
+    pub fn save_patch(&mut self, path: &Path) {
+        match self.staged.save(path) {
+            Ok(()) => self.status = format!("✅ Successfully saved {} edit(s) as {}", self.staged.edits.len(), path.display()),
+            Err(e) => self.status = format!("❌ Error: {}", e),
+        }
+    }
+
+    pub fn discard_edits(&mut self) {
+        self.staged = Patch::default();
+        self.edited = self.data.clone();
+        self.status = "Staged edits discarded".to_string();
+    }
+
+    /// Writes the staged edits, backing the save up first.
+    pub fn handle_apply(&mut self, backup: BackupStore) {
+        let options = PatchOptions {
+            backup: Some(backup),
+            dry_run: false,
+        };
+        match patch::patch_file(Path::new(&self.file_path), &self.staged, &options) {
+            Ok(report) => {
+                let cursor = self.cursor;
+                self.load();
+                self.cursor = cursor;
+                self.scroll_to_row = None;
+                self.status = format!("✅ Successfully applied {} edit(s)!", report.edits);
+                self.backup_filename = report
+                    .backup_path
+                    .as_deref()
+                    .map(files::backup_file_name)
+                    .unwrap_or_default();
+            }
+            Err(e) => self.status = format!("❌ Error: {}", e),
+        }
+    }
+
+    /// The marker covering `offset`, if any. Markers barely overlap, so only
+    /// the last few starting at or before `offset` need checking.
+    pub fn highlight_at(&self, offset: usize) -> Option<&Highlight> {
+        let next = self.highlights.partition_point(|h| h.offset <= offset);
+        self.highlights[..next].iter().rev().take(4).find(|h| offset < h.end())
+    }
+
+    pub fn is_match(&self, offset: usize) -> bool {
+        let next = self.matches.partition_point(|&start| start <= offset);
+        next > 0 && offset < self.matches[next - 1] + self.match_len
+    }
+}
@@ -0,0 +1,42 @@
+// Copyright (C) 2025 Dxian998
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Save editing for Stellar Blade `.sav` files, independent of the GUI.
+
+pub mod anonymize;
+pub mod backup;
+pub mod batch;
+pub mod cloud;
+pub mod demo;
+pub mod diff;
+pub mod edit;
+pub mod error;
+pub mod export;
+pub mod files;
+pub mod gvas;
+pub mod hex;
+pub mod manifest;
+pub mod occurrences;
+pub mod patch;
+pub mod personalize;
+pub mod remove;
+pub mod replace;
+pub mod slots;
+pub mod steam;
+pub mod steamid;
+pub mod transaction;
+pub mod vdf;
+
+pub use error::{Error, Result};
@@ -15,9 +15,17 @@
 
 #![windows_subsystem = "windows"]
 
+mod anonymizer;
 mod app;
+mod backups;
+mod batch_view;
+mod diff_view;
+mod editor;
+mod hex_viewer;
 mod replacer;
 mod remover;
+mod slot_manager;
+mod transfer;
 mod utils;
 
 use app::SteamIDApp;
@@ -26,13 +34,12 @@ use eframe::egui;
 fn main() -> Result<(), eframe::Error> {
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
-            .with_inner_size([540.0, 476.0])
-            .with_min_inner_size([520.0, 476.0])
+            .with_inner_size([760.0, 640.0])
+            .with_min_inner_size([520.0, 540.0])
             .with_drag_and_drop(true)
             .with_maximized(false)
-            .with_maximize_button(false)
             .with_icon(utils::load_icon())
-            .with_resizable(false),
+            .with_resizable(true),
         ..Default::default()
     };
     eframe::run_native(
@@ -40,7 +47,7 @@ fn main() -> Result<(), eframe::Error> {
         options,
         Box::new(|cc| {
             cc.egui_ctx.set_visuals(egui::Visuals::dark());
-            Ok(Box::new(SteamIDApp::default()))
+            Ok(Box::new(SteamIDApp::new()))
         }),
     )
 }
\ No newline at end of file
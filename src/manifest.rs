@@ -0,0 +1,355 @@
+//! A per-save log of every edit, kept as JSON next to the backups.
+//!
+//! Each committed operation appends an entry to
+//! `<backup folder>/<save>.history.json` with the SteamIDs involved, the
+//! offsets touched, the SHA-256 of the file before and after and where its
+//! backup went. That is enough to tell where a save came from and to undo the
+//! last operation exactly, even a removal whose backup was deleted.
+
+use crate::backup::{self, BackupStore};
+use crate::edit::{self, ValueEdit};
+use crate::error::{Error, Result};
+use crate::patch::{self, Patch, PatchEdit};
+use crate::{files, personalize, remove, replace, transaction};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+const MANIFEST_SUFFIX: &str = "history.json";
+
+/// Held while a manifest is read and rewritten, so batch threads sharing a
+/// backup folder do not lose each other's entries.
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Operation {
+    Replace,
+    Remove,
+    Personalize,
+    Anonymize,
+    Patch,
+    Edit,
+    Import,
+    Undo,
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Operation::Replace => write!(f, "replace"),
+            Operation::Remove => write!(f, "remove"),
+            Operation::Personalize => write!(f, "personalize"),
+            Operation::Anonymize => write!(f, "anonymize"),
+            Operation::Patch => write!(f, "patch"),
+            Operation::Edit => write!(f, "edit"),
+            Operation::Import => write!(f, "import"),
+            Operation::Undo => write!(f, "undo"),
+        }
+    }
+}
+
+/// One value that was changed: SteamID digits, or hex bytes for a raw patch.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Edit {
+    /// Offset of the SteamID digits: in the original file for a replacement
+    /// or removal, in the new file when a SteamID was written into an empty property.
+    pub offset: usize,
+    /// Property path when the save could be parsed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// `None` for an empty (universal) value.
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+/// What an operation changed, handed to [`transaction::commit`] to be recorded.
+#[derive(Debug, Clone)]
+pub struct Change {
+    pub operation: Operation,
+    pub new_steamid: Option<String>,
+    pub edits: Vec<Edit>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Entry {
+    /// Unix time of the operation.
+    pub timestamp: u64,
+    pub operation: Operation,
+    /// The file that was edited.
+    pub file: PathBuf,
+    /// Where a demo transfer moved the file afterwards, or where a copy went.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transferred_to: Option<PathBuf>,
+    pub original_steamids: Vec<String>,
+    pub new_steamid: Option<String>,
+    pub edits: Vec<Edit>,
+    pub sha256_before: String,
+    pub sha256_after: String,
+    pub backup: Option<PathBuf>,
+    /// Backup of the save the demo transfer replaced.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replaced_backup: Option<PathBuf>,
+}
+
+impl Entry {
+    /// `replace 76561198000000001 -> 76561198000000002`
+    pub fn summary(&self) -> String {
+        match self.operation {
+            Operation::Patch => return format!("{} {} edit(s)", self.operation, self.edits.len()),
+            Operation::Edit => return format!("{} {} value(s)", self.operation, self.edits.len()),
+            Operation::Import => return self.operation.to_string(),
+            _ => {}
+        }
+        let old = if self.original_steamids.is_empty() {
+            "universal".to_string()
+        } else {
+            self.original_steamids.join(", ")
+        };
+        let new = self.new_steamid.as_deref().unwrap_or("universal");
+        format!("{} {} -> {}", self.operation, old, new)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UndoOptions {
+    /// Where to back up the current file first; `None` skips the backup.
+    /// The manifest is kept in this store's folder either way.
+    pub backup: Option<BackupStore>,
+    pub dry_run: bool,
+}
+
+impl Default for UndoOptions {
+    fn default() -> Self {
+        Self {
+            backup: Some(BackupStore::default()),
+            dry_run: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UndoReport {
+    /// The operation that was reverted.
+    pub undone: Entry,
+    /// The contents came from the operation's backup rather than being rebuilt
+    /// from the recorded edits.
+    pub from_backup: bool,
+    pub backup_path: Option<PathBuf>,
+}
+
+/// `<backup folder>/<save>.history.json` for `save`, named like its backups
+/// so that same-named saves sharing a backup folder keep separate histories.
+pub fn manifest_path(store: &BackupStore, save: &Path) -> PathBuf {
+    store
+        .dir_for(save)
+        .join(format!("{}.{}", store.source_name(save), MANIFEST_SUFFIX))
+}
+
+/// Recorded operations on `save`, oldest first.
+pub fn history(store: &BackupStore, save: &Path) -> Result<Vec<Entry>> {
+    load(&manifest_path(store, save))
+}
+
+pub fn load(path: &Path) -> Result<Vec<Entry>> {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(Error::io("Failed to read operation manifest")(e)),
+    };
+    serde_json::from_str(&text).map_err(|e| Error::InvalidManifest {
+        path: path.to_path_buf(),
+        reason: e.to_string(),
+    })
+}
+
+pub fn sha256_hex(data: &[u8]) -> String {
+    hex::encode(Sha256::digest(data))
+}
+
+/// Appends the entry for a committed change to the manifest of the file it
+/// ended up in, and returns the manifest's path.
+pub(crate) fn record(
+    store: &BackupStore,
+    change: &Change,
+    path: &Path,
+    original: &[u8],
+    data: &[u8],
+    committed: &transaction::Committed,
+) -> Result<PathBuf> {
+    let mut original_steamids: Vec<String> = Vec::new();
+    // Raw patches record hex bytes rather than SteamIDs.
+    for old in change.edits.iter().filter_map(|edit| edit.old.as_ref()) {
+        if replace::is_valid_steamid(old) && !original_steamids.contains(old) {
+            original_steamids.push(old.clone());
+        }
+    }
+
+    let entry = Entry {
+        timestamp: backup::now(),
+        operation: change.operation,
+        file: path.to_path_buf(),
+        transferred_to: (committed.final_path != path).then(|| committed.final_path.clone()),
+        original_steamids,
+        new_steamid: change.new_steamid.clone(),
+        edits: change.edits.clone(),
+        sha256_before: sha256_hex(original),
+        sha256_after: sha256_hex(data),
+        backup: committed.backup_path.clone(),
+        replaced_backup: committed.replaced_backup.clone(),
+    };
+
+    let manifest = manifest_path(store, &committed.final_path);
+    let _guard = WRITE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut entries = load(&manifest)?;
+    entries.push(entry);
+    write(&manifest, &entries)?;
+    Ok(manifest)
+}
+
+/// Drops the entry [`record`] just appended, when the commit it logged is
+/// rolled back.
+pub(crate) fn unrecord(manifest: &Path) -> Result<()> {
+    let _guard = WRITE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut entries = load(manifest)?;
+    entries.pop();
+    write(manifest, &entries)
+}
+
+fn write(manifest: &Path, entries: &[Entry]) -> Result<()> {
+    if let Some(dir) = manifest.parent() {
+        std::fs::create_dir_all(dir).map_err(Error::io("Failed to create backup folder"))?;
+    }
+    let json = serde_json::to_string_pretty(entries).map_err(|e| Error::InvalidManifest {
+        path: manifest.to_path_buf(),
+        reason: e.to_string(),
+    })?;
+    // Undo depends on the history, so it is replaced atomically like a save.
+    files::write_save(manifest, json.as_bytes())
+}
+
+/// Reverts the last recorded operation on `save`. The operation's backup is
+/// used when it still matches the recorded hash; otherwise the original is
+/// rebuilt from the recorded edits. Either way the result must hash to the
+/// recorded `sha256_before`, or nothing is written.
+pub fn undo(save: &Path, options: &UndoOptions) -> Result<UndoReport> {
+    let store = options.backup.clone().unwrap_or_default();
+    let entry = history(&store, save)?
+        .pop()
+        .ok_or_else(|| Error::NoHistory(save.to_path_buf()))?;
+
+    let current = files::read_save(save)?;
+    if sha256_hex(&current) != entry.sha256_after {
+        return Err(Error::HistoryMismatch(save.to_path_buf()));
+    }
+
+    let (data, from_backup) = match from_backup(&entry) {
+        Some(data) => (data, true),
+        None => (rebuild(&entry, &current)?, false),
+    };
+    if sha256_hex(&data) != entry.sha256_before {
+        return Err(Error::UndoMismatch(entry.operation));
+    }
+
+    if options.dry_run {
+        return Ok(UndoReport {
+            undone: entry,
+            from_backup,
+            backup_path: None,
+        });
+    }
+
+    let change = Change {
+        operation: Operation::Undo,
+        new_steamid: entry.original_steamids.first().cloned(),
+        edits: entry
+            .edits
+            .iter()
+            .map(|edit| Edit {
+                old: edit.new.clone(),
+                new: edit.old.clone(),
+                ..edit.clone()
+            })
+            .collect(),
+    };
+    let plan = transaction::Plan {
+        backup: options.backup.as_ref(),
+        record: Some(change),
+        ..Default::default()
+    };
+    let committed = transaction::commit(save, &current, &data, &plan)?;
+
+    Ok(UndoReport {
+        undone: entry,
+        from_backup,
+        backup_path: committed.backup_path,
+    })
+}
+
+fn from_backup(entry: &Entry) -> Option<Vec<u8>> {
+    let data = files::read_save(entry.backup.as_deref()?).ok()?;
+    (sha256_hex(&data) == entry.sha256_before).then_some(data)
+}
+
+/// The file before `entry`, recomputed from `current` and the recorded edits.
+fn rebuild(entry: &Entry, current: &[u8]) -> Result<Vec<u8>> {
+    let mut data = current.to_vec();
+    match entry.operation {
+        Operation::Replace => {
+            for edit in &entry.edits {
+                let Some(old) = &edit.old else { continue };
+                let end = edit.offset + old.len();
+                if end > data.len() || edit.new.as_ref().is_none_or(|new| data[edit.offset..end] != *new.as_bytes()) {
+                    return Err(Error::UndoMismatch(entry.operation));
+                }
+                data[edit.offset..end].copy_from_slice(old.as_bytes());
+            }
+        }
+        Operation::Remove => {
+            let restored: Vec<(Option<&str>, &str)> = entry
+                .edits
+                .iter()
+                .filter_map(|edit| Some((edit.path.as_deref(), edit.old.as_deref()?)))
+                .collect();
+            personalize::restore_owners(&mut data, &restored)?;
+        }
+        Operation::Personalize => {
+            let offsets: Vec<usize> = entry.edits.iter().map(|edit| edit.offset).collect();
+            remove::make_universal(&mut data, Some(&offsets))?;
+        }
+        Operation::Patch => {
+            let mut reverse = Patch::default();
+            for edit in entry.edits.iter().rev() {
+                let (Some(old), Some(new)) = (&edit.old, &edit.new) else { continue };
+                reverse.edits.push(PatchEdit {
+                    offset: edit.offset,
+                    old: crate::hex::parse_bytes(new)?,
+                    new: crate::hex::parse_bytes(old)?,
+                });
+            }
+            data = patch::apply(&data, &reverse).map_err(|_| Error::UndoMismatch(entry.operation))?;
+        }
+        Operation::Edit => {
+            // The recorded text of each old value, set back newest first.
+            let reverse: Vec<ValueEdit> = entry
+                .edits
+                .iter()
+                .rev()
+                .filter_map(|edit| {
+                    Some(ValueEdit {
+                        path: edit.path.clone()?,
+                        value: edit.old.clone()?,
+                    })
+                })
+                .collect();
+            data = edit::apply(&data, &reverse).map_err(|_| Error::UndoMismatch(entry.operation))?.0;
+        }
+        // Anonymized names, imports and undos are only reverted from the backup.
+        Operation::Anonymize | Operation::Import | Operation::Undo => {
+            return Err(Error::UndoMismatch(entry.operation));
+        }
+    }
+    Ok(data)
+}
@@ -0,0 +1,133 @@
+use crate::hex;
+use crate::remove;
+use crate::replace::{self, STEAMID_LEN};
+use std::fmt;
+
+/// Bytes of context shown on each side of an occurrence.
+const CONTEXT_BYTES: usize = 8;
+
+/// Which scanner reported an occurrence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FoundBy {
+    /// Only the raw 17-digit scan.
+    DigitRun,
+    /// Only the `StrProperty` owner search.
+    StrProperty,
+    /// Both scanners.
+    Both,
+}
+
+impl FoundBy {
+    /// Whether the `StrProperty` search (used by removal) reported it.
+    pub fn is_owner_property(self) -> bool {
+        matches!(self, FoundBy::StrProperty | FoundBy::Both)
+    }
+}
+
+impl fmt::Display for FoundBy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FoundBy::DigitRun => write!(f, "digit run"),
+            FoundBy::StrProperty => write!(f, "StrProperty"),
+            FoundBy::Both => write!(f, "both"),
+        }
+    }
+}
+
+/// One SteamID hit in a save, as listed in the occurrence report.
+#[derive(Debug, Clone)]
+pub struct Occurrence {
+    /// Offset of the first digit.
+    pub offset: usize,
+    pub steamid: String,
+    pub found_by: FoundBy,
+    /// Property path when the save could be parsed.
+    pub path: Option<String>,
+    /// False for digit runs that merely look like a SteamID.
+    pub is_property: bool,
+    /// Hex bytes around the match with the SteamID itself bracketed.
+    pub context: String,
+}
+
+/// Every SteamID occurrence found by either scanner, ordered by offset.
+pub fn find_occurrences(data: &[u8]) -> Vec<Occurrence> {
+    let mut occurrences: Vec<Occurrence> = replace::find_steamid_matches(data)
+        .into_iter()
+        .map(|m| Occurrence {
+            offset: m.offset,
+            steamid: m.steamid,
+            found_by: FoundBy::DigitRun,
+            path: m.path,
+            is_property: m.is_property,
+            context: hex_context(data, m.offset),
+        })
+        .collect();
+
+    for owner in remove::find_owner_properties(data) {
+        match occurrences.iter_mut().find(|o| o.offset == owner.value_offset) {
+            Some(occurrence) => {
+                occurrence.found_by = FoundBy::Both;
+                if occurrence.path.is_none() {
+                    occurrence.path = owner.path;
+                }
+            }
+            None => occurrences.push(Occurrence {
+                offset: owner.value_offset,
+                steamid: owner.steamid,
+                found_by: FoundBy::StrProperty,
+                path: owner.path,
+                is_property: true,
+                context: hex_context(data, owner.value_offset),
+            }),
+        }
+    }
+
+    occurrences.sort_by_key(|o| o.offset);
+    occurrences
+}
+
+/// One distinct SteamID and where it appears.
+#[derive(Debug, Clone)]
+pub struct SteamIdGroup {
+    pub steamid: String,
+    pub offsets: Vec<usize>,
+}
+
+/// Groups the SteamIDs held by properties, in order of first appearance.
+/// Look-alikes are ignored; they are not expected to match the owner.
+pub fn group_steamids(occurrences: &[Occurrence]) -> Vec<SteamIdGroup> {
+    let mut groups: Vec<SteamIdGroup> = Vec::new();
+
+    for occurrence in occurrences.iter().filter(|o| o.is_property) {
+        match groups.iter_mut().find(|g| g.steamid == occurrence.steamid) {
+            Some(group) => group.offsets.push(occurrence.offset),
+            None => groups.push(SteamIdGroup {
+                steamid: occurrence.steamid.clone(),
+                offsets: vec![occurrence.offset],
+            }),
+        }
+    }
+
+    groups
+}
+
+/// True when every SteamID property holds the same value.
+pub fn is_consistent(groups: &[SteamIdGroup]) -> bool {
+    groups.len() <= 1
+}
+
+/// `00 12 00 00 00 [7656…] 00 05 00 …`, clamped to the file bounds.
+pub fn hex_context(data: &[u8], offset: usize) -> String {
+    let end = (offset + STEAMID_LEN).min(data.len());
+    let before = &data[offset.saturating_sub(CONTEXT_BYTES)..offset];
+    let after = &data[end..(end + CONTEXT_BYTES).min(data.len())];
+
+    format!(
+        "{} [{}] {}",
+        hex::format_bytes(before),
+        String::from_utf8_lossy(&data[offset..end]),
+        hex::format_bytes(after)
+    )
+        .trim()
+        .to_string()
+}
@@ -0,0 +1,180 @@
+//! Raw byte edits by offset, for values the tool has no dedicated operation for.
+//!
+//! A patch lists each edit with the bytes expected at its offset and the
+//! bytes to write there. It is kept as JSON so it can be saved, reviewed and
+//! applied to other copies of the same save; nothing is written unless every
+//! expected byte matches.
+
+use crate::backup::BackupStore;
+use crate::error::{Error, Result};
+use crate::manifest::{Change, Edit, Operation};
+use crate::{files, hex, transaction};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// One overwrite. `old` and `new` have the same length, so no other offset moves.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PatchEdit {
+    #[serde(with = "hex_offset")]
+    pub offset: usize,
+    #[serde(with = "hex_bytes")]
+    pub old: Vec<u8>,
+    #[serde(with = "hex_bytes")]
+    pub new: Vec<u8>,
+}
+
+impl PatchEdit {
+    /// An edit writing `new` at `offset`, expecting the bytes `data` has there now.
+    pub fn at(data: &[u8], offset: usize, new: Vec<u8>) -> Result<Self> {
+        let old = offset
+            .checked_add(new.len())
+            .and_then(|end| data.get(offset..end))
+            .ok_or_else(|| Error::InvalidPatch(format!("the edit at 0x{:X} runs past the end of the file", offset)))?;
+        Ok(Self {
+            offset,
+            old: old.to_vec(),
+            new,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Patch {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Applied in order, each against the result of the previous ones.
+    pub edits: Vec<PatchEdit>,
+}
+
+impl Patch {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path).map_err(Error::io("Failed to read patch file"))?;
+        serde_json::from_str(&text).map_err(|e| Error::InvalidPatch(e.to_string()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| Error::InvalidPatch(e.to_string()))?;
+        std::fs::write(path, json).map_err(Error::io("Failed to write patch file"))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PatchOptions {
+    /// Where to back up the original first; `None` skips the backup.
+    pub backup: Option<BackupStore>,
+    pub dry_run: bool,
+}
+
+impl Default for PatchOptions {
+    fn default() -> Self {
+        Self {
+            backup: Some(BackupStore::default()),
+            dry_run: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PatchReport {
+    pub edits: usize,
+    pub backup_path: Option<PathBuf>,
+}
+
+/// `data` with every edit applied in order, or an error if any expected bytes
+/// do not match.
+pub fn apply(data: &[u8], patch: &Patch) -> Result<Vec<u8>> {
+    let mut data = data.to_vec();
+    if patch.edits.is_empty() {
+        return Err(Error::InvalidPatch("the patch has no edits".to_string()));
+    }
+    for edit in &patch.edits {
+        if edit.old.len() != edit.new.len() {
+            return Err(Error::InvalidPatch(format!(
+                "the edit at 0x{:X} replaces {} byte(s) with {}",
+                edit.offset,
+                edit.old.len(),
+                edit.new.len()
+            )));
+        }
+        let end = edit.offset.checked_add(edit.old.len()).ok_or_else(|| {
+            Error::InvalidPatch(format!("the edit at 0x{:X} runs past the end of any file", edit.offset))
+        })?;
+        let found = data.get(edit.offset..end).unwrap_or_default();
+        if found != edit.old.as_slice() {
+            return Err(Error::PatchMismatch {
+                offset: edit.offset,
+                expected: hex::format_bytes(&edit.old),
+                found: hex::format_bytes(found),
+            });
+        }
+        data[edit.offset..end].copy_from_slice(&edit.new);
+    }
+    Ok(data)
+}
+
+/// Applies `patch` to the save at `path` through the usual backup and
+/// manifest path.
+pub fn patch_file(path: &Path, patch: &Patch, options: &PatchOptions) -> Result<PatchReport> {
+    let original = files::read_save(path)?;
+    let data = apply(&original, patch)?;
+
+    if options.dry_run {
+        return Ok(PatchReport {
+            edits: patch.edits.len(),
+            backup_path: None,
+        });
+    }
+
+    let plan = transaction::Plan {
+        backup: options.backup.as_ref(),
+        record: Some(Change {
+            operation: Operation::Patch,
+            new_steamid: None,
+            edits: patch
+                .edits
+                .iter()
+                .map(|edit| Edit {
+                    offset: edit.offset,
+                    path: None,
+                    old: Some(hex::format_bytes(&edit.old)),
+                    new: Some(hex::format_bytes(&edit.new)),
+                })
+                .collect(),
+        }),
+        ..Default::default()
+    };
+    let committed = transaction::commit(path, &original, &data, &plan)?;
+
+    Ok(PatchReport {
+        edits: patch.edits.len(),
+        backup_path: committed.backup_path,
+    })
+}
+
+/// Offsets as `0x00000C34`; decimal is accepted when reading.
+mod hex_offset {
+    use serde::{Deserialize, Deserializer, Serializer, de};
+
+    pub fn serialize<S: Serializer>(offset: &usize, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("0x{:08X}", offset))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<usize, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        crate::hex::parse_offset(&text).ok_or_else(|| de::Error::custom(format!("invalid offset '{}'", text)))
+    }
+}
+
+/// Byte strings as `05 00 00 00`.
+mod hex_bytes {
+    use serde::{Deserialize, Deserializer, Serializer, de};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&crate::hex::format_bytes(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        crate::hex::parse_bytes(&text).map_err(de::Error::custom)
+    }
+}
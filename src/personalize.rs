@@ -0,0 +1,274 @@
+//! Binding a universal save to an account again: the inverse of
+//! [`remove::make_universal`](crate::remove::make_universal).
+//!
+//! The remover keeps the owner `StrProperty` but empties its value. Writing a
+//! SteamID back into that property, with its size fields fixed up, gives the
+//! same bytes the game writes for a save owned by that account.
+
+use crate::backup::BackupStore;
+use crate::demo::DemoTransfer;
+use crate::error::{Error, Result};
+use crate::gvas::{FString, PropertyValue, SaveGame, Value};
+use crate::manifest::{Change, Edit, Operation};
+use crate::replace::{self, STEAMID_LEN};
+use crate::{files, remove, transaction};
+use std::path::{Path, PathBuf};
+
+const STR_PROPERTY: &[u8] = b"StrProperty";
+
+/// An emptied owner property as the remover leaves it: size 4, no GUID, and a
+/// zero-length string, starting at the terminator of `StrProperty`.
+const EMPTY_GAP: [u8; 14] = [
+    0x00, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+];
+/// The `None` terminator that follows the owner property.
+const NONE_TAG: &[u8] = b"\x05\x00\x00\x00None";
+/// Extra bytes the byte-pattern remover leaves before the terminator.
+const PATTERN_PADDING: [u8; 5] = [0x00, 0x05, 0x00, 0x00, 0x00];
+
+#[derive(Debug, Clone)]
+pub struct PersonalizeOptions {
+    /// Move a demo save into a full-game slot after editing.
+    pub demo_transfer: Option<DemoTransfer>,
+    /// Where to back up the original first; `None` skips the backup.
+    pub backup: Option<BackupStore>,
+    pub dry_run: bool,
+    /// Only fill the empty properties starting at these offsets.
+    pub only_offsets: Option<Vec<usize>>,
+}
+
+impl Default for PersonalizeOptions {
+    fn default() -> Self {
+        Self {
+            demo_transfer: None,
+            backup: Some(BackupStore::default()),
+            dry_run: false,
+            only_offsets: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PersonalizeReport {
+    /// Where the SteamID digits now start.
+    pub offsets: Vec<usize>,
+    pub backup_path: Option<PathBuf>,
+    /// Backup of an existing save the demo transfer replaced.
+    pub replaced_backup: Option<PathBuf>,
+    pub final_path: PathBuf,
+}
+
+/// An owner property whose value was emptied.
+#[derive(Debug, Clone)]
+pub struct EmptyOwnerProperty {
+    /// Offset of the property tag's `StrProperty` type name.
+    pub offset: usize,
+    /// Property path when the save could be parsed.
+    pub path: Option<String>,
+    /// Offset of the property tag when the save was parsed; unlike the path
+    /// it is unique.
+    tag_offset: Option<usize>,
+}
+
+/// Empty owner properties: a narrow `StrProperty` with a zero-length value
+/// that ends its property list, which is where the game keeps the owner ID.
+pub fn find_empty_owner_properties(data: &[u8]) -> Vec<EmptyOwnerProperty> {
+    match SaveGame::parse(data) {
+        Ok(save) => find_empty_owner_properties_in(&save),
+        Err(_) => scan_empty_owner_properties(data),
+    }
+}
+
+fn find_empty_owner_properties_in(save: &SaveGame) -> Vec<EmptyOwnerProperty> {
+    let last_offsets: Vec<usize> = save
+        .lists()
+        .into_iter()
+        .filter_map(|(_, list)| list.last())
+        .filter(|property| {
+            property.type_name == *"StrProperty"
+                && matches!(&property.value, PropertyValue::Simple(Value::Str(value)) if value.is_null())
+        })
+        .map(|property| property.offset)
+        .collect();
+
+    save.walk()
+        .into_iter()
+        .filter(|(_, property)| last_offsets.contains(&property.offset))
+        .map(|(path, property)| EmptyOwnerProperty {
+            offset: property.offset + property.name.serialized_len() + 4,
+            path: Some(path),
+            tag_offset: Some(property.offset),
+        })
+        .collect()
+}
+
+fn scan_empty_owner_properties(data: &[u8]) -> Vec<EmptyOwnerProperty> {
+    (0..data.len())
+        .filter(|&pos| empty_gap_at(data, pos).is_some())
+        .map(|offset| EmptyOwnerProperty {
+            offset,
+            path: None,
+            tag_offset: None,
+        })
+        .collect()
+}
+
+/// Length of the empty value after `StrProperty` at `pos`, up to the `None`
+/// terminator, in either of the two shapes the remover produces.
+fn empty_gap_at(data: &[u8], pos: usize) -> Option<usize> {
+    let rest = data[pos..].strip_prefix(STR_PROPERTY)?.strip_prefix(&EMPTY_GAP[..])?;
+    if rest.starts_with(NONE_TAG) {
+        return Some(EMPTY_GAP.len());
+    }
+    let rest = rest.strip_prefix(&PATTERN_PADDING[..])?;
+    (rest.starts_with(&[0x00]) && rest[1..].starts_with(NONE_TAG)).then_some(EMPTY_GAP.len() + PATTERN_PADDING.len())
+}
+
+/// Writes `steamid` into every empty owner property, or only those at
+/// `only_offsets`. Returns where the SteamID digits start in the new data.
+pub fn personalize(data: &mut Vec<u8>, steamid: &str, only_offsets: Option<&[usize]>) -> Result<Vec<usize>> {
+    if !replace::is_valid_steamid(steamid) {
+        return Err(Error::InvalidSteamId(steamid.to_string()));
+    }
+    if let Some(owner) = remove::find_owner_properties(data).into_iter().next() {
+        return Err(Error::NotUniversal(owner.steamid));
+    }
+
+    if let Ok(mut save) = SaveGame::parse_lossless(data) {
+        let properties = select(find_empty_owner_properties_in(&save), only_offsets)?;
+        for empty in &properties {
+            let property = empty
+                .tag_offset
+                .and_then(|offset| save.get_at_mut(offset))
+                .ok_or_else(|| remove::lost_property(empty.offset))?;
+            property.value = PropertyValue::Simple(Value::Str(FString::new(steamid)));
+        }
+        *data = save.to_bytes();
+    } else {
+        let properties = select(scan_empty_owner_properties(data), only_offsets)?;
+        for property in properties.iter().rev() {
+            let gap_start = property.offset + STR_PROPERTY.len();
+            let Some(gap_length) = empty_gap_at(data, property.offset) else {
+                continue;
+            };
+            data.splice(gap_start..gap_start + gap_length, native_gap(steamid, gap_length));
+        }
+    }
+
+    Ok(remove::find_owner_properties(data)
+        .into_iter()
+        .filter(|owner| owner.steamid == steamid)
+        .map(|owner| owner.value_offset)
+        .collect())
+}
+
+/// Writes removed SteamIDs back, each into the property it was removed from:
+/// the next empty owner property with its path when the save parses,
+/// otherwise into the empty properties in order.
+pub(crate) fn restore_owners(data: &mut Vec<u8>, removed: &[(Option<&str>, &str)]) -> Result<()> {
+    if let Ok(mut save) = SaveGame::parse_lossless(data) {
+        let mut empty = find_empty_owner_properties_in(&save);
+        for (path, steamid) in removed {
+            let position = empty
+                .iter()
+                .position(|property| path.is_some() && property.path.as_deref() == *path)
+                .ok_or(Error::NoEmptyOwnerProperty)?;
+            let target = empty.remove(position);
+            let property = target
+                .tag_offset
+                .and_then(|offset| save.get_at_mut(offset))
+                .ok_or_else(|| remove::lost_property(target.offset))?;
+            property.value = PropertyValue::Simple(Value::Str(FString::new(steamid)));
+        }
+        *data = save.to_bytes();
+        return Ok(());
+    }
+
+    let properties = scan_empty_owner_properties(data);
+    if properties.len() != removed.len() {
+        return Err(Error::NoEmptyOwnerProperty);
+    }
+    for (property, (_, steamid)) in properties.iter().zip(removed).rev() {
+        let gap_start = property.offset + STR_PROPERTY.len();
+        if let Some(gap_length) = empty_gap_at(data, property.offset) {
+            data.splice(gap_start..gap_start + gap_length, native_gap(steamid, gap_length));
+        }
+    }
+    Ok(())
+}
+
+pub fn personalize_in_file(path: &Path, steamid: &str, options: &PersonalizeOptions) -> Result<PersonalizeReport> {
+    let original = files::read_save(path)?;
+    let rename_to = transaction::demo_transfer_target(path, &original, options.demo_transfer.as_ref(), false)?;
+    let mut data = original.clone();
+    let offsets = personalize(&mut data, steamid, options.only_offsets.as_deref())?;
+
+    if options.dry_run {
+        return Ok(PersonalizeReport {
+            offsets,
+            backup_path: None,
+            replaced_backup: None,
+            final_path: rename_to.unwrap_or_else(|| path.to_path_buf()),
+        });
+    }
+
+    let plan = transaction::Plan {
+        backup: options.backup.as_ref(),
+        rename_to,
+        overwrite_target: options.demo_transfer.as_ref().is_some_and(|t| t.overwrite_existing),
+        keep_source: false,
+        record: Some(Change {
+            operation: Operation::Personalize,
+            new_steamid: Some(steamid.to_string()),
+            edits: offsets
+                .iter()
+                .map(|&offset| Edit {
+                    offset,
+                    path: None,
+                    old: None,
+                    new: Some(steamid.to_string()),
+                })
+                .collect(),
+        }),
+    };
+    let committed = transaction::commit(path, &original, &data, &plan)?;
+
+    Ok(PersonalizeReport {
+        offsets,
+        backup_path: committed.backup_path,
+        replaced_backup: committed.replaced_backup,
+        final_path: committed.final_path,
+    })
+}
+
+/// The owner property value as the game writes it: size 22, array index 0,
+/// no GUID, then the 18-byte string. When the pattern padding is being
+/// replaced the string terminator is already in the file.
+fn native_gap(steamid: &str, replaced_length: usize) -> Vec<u8> {
+    let string_length = (STEAMID_LEN + 1) as i32;
+    let mut gap = vec![0x00];
+    gap.extend_from_slice(&(string_length + 4).to_le_bytes());
+    gap.extend_from_slice(&0i32.to_le_bytes());
+    gap.push(0x00);
+    gap.extend_from_slice(&string_length.to_le_bytes());
+    gap.extend_from_slice(steamid.as_bytes());
+    if replaced_length == EMPTY_GAP.len() {
+        gap.push(0x00);
+    }
+    gap
+}
+
+fn select(mut properties: Vec<EmptyOwnerProperty>, only_offsets: Option<&[usize]>) -> Result<Vec<EmptyOwnerProperty>> {
+    if properties.is_empty() {
+        return Err(Error::NoEmptyOwnerProperty);
+    }
+    if let Some(offsets) = only_offsets {
+        replace::check_selected(offsets, properties.iter().map(|p| p.offset))?;
+        properties.retain(|p| offsets.contains(&p.offset));
+        if properties.is_empty() {
+            return Err(Error::NothingSelected);
+        }
+    }
+    Ok(properties)
+}
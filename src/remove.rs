@@ -0,0 +1,321 @@
+use crate::backup::BackupStore;
+use crate::error::{Error, Result};
+use crate::demo::DemoTransfer;
+use crate::files;
+use crate::gvas::{FString, PropertyValue, SaveGame, Value};
+use crate::manifest::{Change, Edit, Operation};
+use crate::replace::{self, STEAMID_LEN};
+use crate::transaction;
+use std::path::{Path, PathBuf};
+
+const STR_PROPERTY: &[u8] = b"StrProperty";
+const NONE_TERMINATOR: &[u8] = b"None";
+const NONE_LENGTH_PREFIX: [u8; 5] = [0x00, 0x05, 0x00, 0x00, 0x00];
+const PROPERTY_SIZE_PREFIX: [u8; 5] = [0x00, 0x16, 0x00, 0x00, 0x00];
+const STRING_LENGTH_PREFIX: [u8; 5] = [0x00, 0x12, 0x00, 0x00, 0x00];
+
+/// The value an emptied owner property is rewritten to.
+const UNIVERSAL_GAP: [u8; 19] = [
+    0x00, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x05,
+    0x00, 0x00, 0x00,
+];
+
+#[derive(Debug, Clone)]
+pub struct RemoveOptions {
+    /// Move a demo save into a full-game slot after editing.
+    pub demo_transfer: Option<DemoTransfer>,
+    /// Where to back up the original first; `None` skips the backup.
+    pub backup: Option<BackupStore>,
+    pub dry_run: bool,
+    /// Restrict removal to the properties whose SteamID starts at these offsets.
+    pub only_offsets: Option<Vec<usize>>,
+}
+
+impl Default for RemoveOptions {
+    fn default() -> Self {
+        Self {
+            demo_transfer: None,
+            backup: Some(BackupStore::default()),
+            dry_run: false,
+            only_offsets: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RemoveReport {
+    pub removed: Vec<OwnerProperty>,
+    pub backup_path: Option<PathBuf>,
+    /// Backup of an existing save the demo transfer replaced.
+    pub replaced_backup: Option<PathBuf>,
+    pub final_path: PathBuf,
+}
+
+/// A `StrProperty` whose value is a SteamID, terminated by `None`.
+#[derive(Debug, Clone)]
+pub struct OwnerProperty {
+    pub offset: usize,
+    /// Offset of the SteamID digits themselves.
+    pub value_offset: usize,
+    pub steamid: String,
+    /// Property path when the save could be parsed, e.g. `PlayerData.OwnerId`.
+    pub path: Option<String>,
+    /// Offset of the property tag when the save was parsed; unlike the path
+    /// it is unique.
+    tag_offset: Option<usize>,
+    gap_start: usize,
+    gap_length: usize,
+}
+
+impl OwnerProperty {
+    /// Where the property was found, e.g. `PlayerData.OwnerId @ 0x000009AE`.
+    pub fn location(&self) -> String {
+        match &self.path {
+            Some(path) => format!("{} @ 0x{:08X}", path, self.offset),
+            None => format!("0x{:08X}", self.offset),
+        }
+    }
+}
+
+/// Locates owner properties through the property tree, falling back to a byte
+/// pattern search when the save cannot be parsed.
+pub fn find_owner_properties(data: &[u8]) -> Vec<OwnerProperty> {
+    match SaveGame::parse(data) {
+        Ok(save) => find_owner_properties_in(&save),
+        Err(_) => scan_owner_properties(data),
+    }
+}
+
+/// Every `StrProperty` holding a SteamID64 that ends its property list, so
+/// is followed by `None`, wherever that list is nested. SteamIDs in other
+/// string properties are left to `replace`.
+pub fn find_owner_properties_in(save: &SaveGame) -> Vec<OwnerProperty> {
+    let last_offsets: Vec<usize> = save
+        .lists()
+        .into_iter()
+        .filter_map(|(_, list)| list.last())
+        .map(|property| property.offset)
+        .collect();
+    let mut properties = Vec::new();
+
+    for (path, property) in save.walk() {
+        if !last_offsets.contains(&property.offset) {
+            continue;
+        }
+        let PropertyValue::Simple(Value::Str(value)) = &property.value else {
+            continue;
+        };
+        if property.type_name != *"StrProperty" || value.wide || !replace::is_valid_steamid(value.as_str()) {
+            continue;
+        }
+
+        let gap_start = property.offset + property.name.serialized_len() + property.type_name.serialized_len() - 1;
+        let gap_end = property.body_offset() + 4 + STEAMID_LEN;
+
+        properties.push(OwnerProperty {
+            offset: property.offset + property.name.serialized_len() + 4,
+            value_offset: property.body_offset() + 4,
+            steamid: value.to_string(),
+            path: Some(path),
+            tag_offset: Some(property.offset),
+            gap_start,
+            gap_length: gap_end - gap_start,
+        });
+    }
+
+    properties
+}
+
+/// Byte pattern search for a `StrProperty` holding a SteamID that is directly
+/// followed by the `None` terminator, over the whole file.
+fn scan_owner_properties(data: &[u8]) -> Vec<OwnerProperty> {
+    let mut properties = Vec::new();
+    let mut i = 0;
+
+    while i < data.len() {
+        if let Some((property, total_length)) = owner_property_at(data, i) {
+            i += total_length;
+            properties.push(property);
+        } else {
+            i += 1;
+        }
+    }
+
+    properties
+}
+
+pub fn find_steamid(data: &[u8]) -> Option<String> {
+    find_owner_properties(data)
+        .into_iter()
+        .next()
+        .map(|property| property.steamid)
+}
+
+pub fn is_universal(data: &[u8]) -> bool {
+    find_owner_properties(data).is_empty()
+}
+
+/// Empties every owner property, or only those listed in `only_offsets`, and
+/// returns what was removed. Saves that round-trip through the serializer are
+/// edited structurally, recomputing all sizes; anything else falls back to
+/// splicing the known universal pattern.
+///
+/// The two paths do not give the same bytes. The structural edit leaves a
+/// zero-length string, size 4, directly followed by `None`, so the result
+/// still parses. The pattern splice reproduces what earlier versions wrote,
+/// which has five more bytes before the terminator. `personalize` accepts
+/// both shapes.
+pub fn make_universal(data: &mut Vec<u8>, only_offsets: Option<&[usize]>) -> Result<Vec<OwnerProperty>> {
+    if let Ok(mut save) = SaveGame::parse_lossless(data) {
+        let properties = select(find_owner_properties_in(&save), only_offsets)?;
+
+        for owner in &properties {
+            let property = owner
+                .tag_offset
+                .and_then(|offset| save.get_at_mut(offset))
+                .ok_or_else(|| lost_property(owner.offset))?;
+            property.value = PropertyValue::Simple(Value::Str(FString::default()));
+        }
+
+        *data = save.to_bytes();
+        return Ok(properties);
+    }
+
+    let properties = select(scan_owner_properties(data), only_offsets)?;
+
+    // Apply modifications in reverse order to maintain positions
+    for property in properties.iter().rev() {
+        let gap_end = property.gap_start + property.gap_length;
+        if gap_end <= data.len() {
+            data.splice(property.gap_start..gap_end, UNIVERSAL_GAP.iter().copied());
+        }
+    }
+
+    Ok(properties)
+}
+
+/// A property found in the parsed save that could not be looked up again.
+pub(crate) fn lost_property(offset: usize) -> Error {
+    Error::InvalidSave {
+        offset,
+        reason: "the owner property could not be found again".to_string(),
+    }
+}
+
+pub fn remove_steamid_in_file(path: &Path, options: &RemoveOptions) -> Result<RemoveReport> {
+    let original = files::read_save(path)?;
+    let rename_to = transaction::demo_transfer_target(path, &original, options.demo_transfer.as_ref(), true)?;
+    let mut data = original.clone();
+    let removed = make_universal(&mut data, options.only_offsets.as_deref())?;
+
+    if options.dry_run {
+        return Ok(RemoveReport {
+            removed,
+            backup_path: None,
+            replaced_backup: None,
+            final_path: rename_to.unwrap_or_else(|| path.to_path_buf()),
+        });
+    }
+
+    let plan = transaction::Plan {
+        backup: options.backup.as_ref(),
+        rename_to,
+        overwrite_target: options.demo_transfer.as_ref().is_some_and(|t| t.overwrite_existing),
+        keep_source: false,
+        record: Some(Change {
+            operation: Operation::Remove,
+            new_steamid: None,
+            edits: removed
+                .iter()
+                .map(|property| Edit {
+                    offset: property.value_offset,
+                    path: property.path.clone(),
+                    old: Some(property.steamid.clone()),
+                    new: None,
+                })
+                .collect(),
+        }),
+    };
+    let committed = transaction::commit(path, &original, &data, &plan)?;
+
+    Ok(RemoveReport {
+        removed,
+        backup_path: committed.backup_path,
+        replaced_backup: committed.replaced_backup,
+        final_path: committed.final_path,
+    })
+}
+
+fn select(mut properties: Vec<OwnerProperty>, only_offsets: Option<&[usize]>) -> Result<Vec<OwnerProperty>> {
+    if properties.is_empty() {
+        return Err(Error::AlreadyUniversal);
+    }
+    if let Some(offsets) = only_offsets {
+        replace::check_selected(offsets, properties.iter().map(|p| p.value_offset))?;
+        properties.retain(|p| offsets.contains(&p.value_offset));
+        if properties.is_empty() {
+            return Err(Error::NothingSelected);
+        }
+    }
+    Ok(properties)
+}
+
+/// The property found at `pos` and its length through the `None` terminator.
+fn owner_property_at(data: &[u8], pos: usize) -> Option<(OwnerProperty, usize)> {
+    if !data[pos..].starts_with(STR_PROPERTY) {
+        return None;
+    }
+
+    let gap_start = pos + STR_PROPERTY.len();
+    let search_end = data.len().saturating_sub(4).min(gap_start + 100);
+
+    for i in gap_start..search_end {
+        if !data[i..].starts_with(NONE_TERMINATOR) {
+            continue;
+        }
+        if i < gap_start + NONE_LENGTH_PREFIX.len() || data[i - 5..i] != NONE_LENGTH_PREFIX {
+            continue;
+        }
+
+        let gap = &data[gap_start..i - 5];
+        if let Some(steamid_start) = steamid_in_gap(gap) {
+            let steamid = &gap[steamid_start..steamid_start + STEAMID_LEN];
+            let property = OwnerProperty {
+                offset: pos,
+                value_offset: gap_start + steamid_start,
+                steamid: String::from_utf8(steamid.to_vec()).ok()?,
+                path: None,
+                tag_offset: None,
+                gap_start,
+                gap_length: gap.len(),
+            };
+            return Some((property, (i + NONE_TERMINATOR.len()) - pos));
+        }
+    }
+
+    None
+}
+
+/// Start of the SteamID digits within the bytes between `StrProperty` and `None`.
+fn steamid_in_gap(gap: &[u8]) -> Option<usize> {
+    if gap.len() < 14 + STEAMID_LEN || !gap.starts_with(&PROPERTY_SIZE_PREFIX) {
+        return None;
+    }
+
+    for i in 5..(gap.len() - 21).min(15) {
+        if !gap[i..].starts_with(&STRING_LENGTH_PREFIX) {
+            continue;
+        }
+
+        let steamid_start = i + STRING_LENGTH_PREFIX.len();
+        if steamid_start + STEAMID_LEN <= gap.len() {
+            let steamid = &gap[steamid_start..steamid_start + STEAMID_LEN];
+            if steamid.iter().all(u8::is_ascii_digit) {
+                return Some(steamid_start);
+            }
+        }
+    }
+
+    None
+}
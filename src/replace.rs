@@ -0,0 +1,274 @@
+use crate::backup::BackupStore;
+use crate::error::{Error, Result};
+use crate::demo::DemoTransfer;
+use crate::files;
+use crate::gvas::SaveGame;
+use crate::manifest::{Change, Edit, Operation};
+use crate::steamid::SteamId;
+use crate::transaction;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+pub const STEAMID_LEN: usize = 17;
+pub const STEAMID_PREFIX: &[u8] = b"7656";
+
+/// Length prefix of an FString holding exactly a SteamID plus its terminator.
+const STEAMID_FSTRING_LEN: [u8; 4] = (STEAMID_LEN as i32 + 1).to_le_bytes();
+
+#[derive(Debug, Clone)]
+pub struct ReplaceOptions {
+    /// Move a demo save into a full-game slot after editing.
+    pub demo_transfer: Option<DemoTransfer>,
+    /// Where to back up the original first; `None` skips the backup.
+    pub backup: Option<BackupStore>,
+    pub dry_run: bool,
+    /// Also rewrite digit runs that are not a SteamID string property.
+    pub include_lookalikes: bool,
+    /// Replace exactly the matches starting at these offsets instead.
+    pub only_offsets: Option<Vec<usize>>,
+}
+
+impl Default for ReplaceOptions {
+    fn default() -> Self {
+        Self {
+            demo_transfer: None,
+            backup: Some(BackupStore::default()),
+            dry_run: false,
+            include_lookalikes: false,
+            only_offsets: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ReplaceReport {
+    pub previous_steamid: Option<String>,
+    /// Every distinct property value that was overwritten; more than one means the
+    /// save was inconsistent and has now been normalised.
+    pub previous_steamids: Vec<String>,
+    pub offsets: Vec<usize>,
+    /// Look-alike matches that were left untouched.
+    pub skipped: Vec<SteamIdMatch>,
+    pub backup_path: Option<PathBuf>,
+    /// Backup of an existing save the demo transfer replaced.
+    pub replaced_backup: Option<PathBuf>,
+    pub final_path: PathBuf,
+}
+
+impl ReplaceReport {
+    pub fn replacements(&self) -> usize {
+        self.offsets.len()
+    }
+}
+
+/// A 17-digit run starting with `7656` somewhere in the save.
+#[derive(Debug, Clone)]
+pub struct SteamIdMatch {
+    pub offset: usize,
+    pub steamid: String,
+    /// Path of the string property holding it, if it is one.
+    pub path: Option<String>,
+    /// False for digit runs inside other data that merely look like a SteamID.
+    pub is_property: bool,
+}
+
+/// A 17-digit SteamID64 of a public individual account. Other notations are
+/// converted with [`crate::steamid::parse`] first.
+pub fn is_valid_steamid(steamid: &str) -> bool {
+    steamid.len() == STEAMID_LEN
+        && steamid.as_bytes().starts_with(STEAMID_PREFIX)
+        && steamid.chars().all(|c| c.is_ascii_digit())
+        && steamid.parse().is_ok_and(|value| SteamId::from_steamid64(value).is_ok())
+}
+
+pub fn is_steamid_at(data: &[u8], pos: usize) -> bool {
+    if pos + STEAMID_LEN > data.len() {
+        return false;
+    }
+
+    data[pos..].starts_with(STEAMID_PREFIX)
+        && data[pos..pos + STEAMID_LEN].iter().all(u8::is_ascii_digit)
+}
+
+/// Offsets of every 17-digit run starting with `7656`, scanning without overlap.
+pub fn find_steamid_offsets(data: &[u8]) -> Vec<usize> {
+    let mut offsets = Vec::new();
+    let mut i = 0;
+
+    while i + STEAMID_LEN <= data.len() {
+        if is_steamid_at(data, i) {
+            offsets.push(i);
+            i += STEAMID_LEN;
+        } else {
+            i += 1;
+        }
+    }
+
+    offsets
+}
+
+/// Every SteamID-shaped digit run, classified as a string property value or a
+/// look-alike. Parsed saves are classified by the property tree; otherwise a
+/// run counts as a property when it is exactly the content of an FString.
+pub fn find_steamid_matches(data: &[u8]) -> Vec<SteamIdMatch> {
+    let property_paths = SaveGame::parse(data).ok().map(|save| steamid_property_paths(&save));
+
+    find_steamid_offsets(data)
+        .into_iter()
+        .map(|offset| {
+            let steamid = String::from_utf8_lossy(&data[offset..offset + STEAMID_LEN]).to_string();
+            let (path, is_property) = match &property_paths {
+                Some(paths) => {
+                    let path = paths.get(&offset).cloned();
+                    let is_property = path.is_some();
+                    (path, is_property)
+                }
+                None => (None, is_fstring_content(data, offset)),
+            };
+            SteamIdMatch {
+                offset,
+                steamid,
+                path,
+                is_property,
+            }
+        })
+        .collect()
+}
+
+/// The SteamID held by the first SteamID string property.
+pub fn find_steamid(data: &[u8]) -> Option<String> {
+    find_steamid_matches(data)
+        .into_iter()
+        .find(|m| m.is_property)
+        .map(|m| m.steamid)
+}
+
+/// Overwrites the given matches in place. The new value has the same length,
+/// so no size field changes.
+pub fn replace_steamid(data: &mut [u8], matches: &[SteamIdMatch], new_steamid: &str) -> Result<Vec<usize>> {
+    if !is_valid_steamid(new_steamid) {
+        return Err(Error::InvalidSteamId(new_steamid.to_string()));
+    }
+
+    let offsets: Vec<usize> = matches.iter().map(|m| m.offset).collect();
+    for &offset in &offsets {
+        data[offset..offset + STEAMID_LEN].copy_from_slice(new_steamid.as_bytes());
+    }
+
+    Ok(offsets)
+}
+
+pub fn replace_steamid_in_file(
+    path: &Path,
+    new_steamid: &str,
+    options: &ReplaceOptions,
+) -> Result<ReplaceReport> {
+    if !is_valid_steamid(new_steamid) {
+        return Err(Error::InvalidSteamId(new_steamid.to_string()));
+    }
+    let original = files::read_save(path)?;
+    let rename_to = transaction::demo_transfer_target(path, &original, options.demo_transfer.as_ref(), false)?;
+    let mut data = original.clone();
+    let matches = find_steamid_matches(&data);
+    if matches.is_empty() {
+        return Err(Error::NoSteamIdFound);
+    }
+
+    if let Some(offsets) = &options.only_offsets {
+        check_selected(offsets, matches.iter().map(|m| m.offset))?;
+    }
+
+    let (targets, skipped): (Vec<_>, Vec<_>) = matches.into_iter().partition(|m| match &options.only_offsets {
+        Some(offsets) => offsets.contains(&m.offset),
+        None => m.is_property || options.include_lookalikes,
+    });
+    // A selected look-alike is not the save's owner.
+    let previous_steamid = targets.iter().find(|m| m.is_property).map(|m| m.steamid.clone());
+    let mut previous_steamids: Vec<String> = Vec::new();
+    for target in targets.iter().filter(|m| m.is_property) {
+        if !previous_steamids.contains(&target.steamid) {
+            previous_steamids.push(target.steamid.clone());
+        }
+    }
+
+    let offsets = replace_steamid(&mut data, &targets, new_steamid)?;
+    if offsets.is_empty() {
+        return Err(match options.only_offsets {
+            Some(_) => Error::NothingSelected,
+            None => Error::NoSteamIdFound,
+        });
+    }
+
+    if options.dry_run {
+        return Ok(ReplaceReport {
+            previous_steamid,
+            previous_steamids,
+            offsets,
+            skipped,
+            backup_path: None,
+            replaced_backup: None,
+            final_path: rename_to.unwrap_or_else(|| path.to_path_buf()),
+        });
+    }
+
+    let plan = transaction::Plan {
+        backup: options.backup.as_ref(),
+        rename_to,
+        overwrite_target: options.demo_transfer.as_ref().is_some_and(|t| t.overwrite_existing),
+        keep_source: false,
+        record: Some(Change {
+            operation: Operation::Replace,
+            new_steamid: Some(new_steamid.to_string()),
+            edits: targets
+                .iter()
+                .map(|target| Edit {
+                    offset: target.offset,
+                    path: target.path.clone(),
+                    old: Some(target.steamid.clone()),
+                    new: Some(new_steamid.to_string()),
+                })
+                .collect(),
+        }),
+    };
+    let committed = transaction::commit(path, &original, &data, &plan)?;
+
+    Ok(ReplaceReport {
+        previous_steamid,
+        previous_steamids,
+        offsets,
+        skipped,
+        backup_path: committed.backup_path,
+        replaced_backup: committed.replaced_backup,
+        final_path: committed.final_path,
+    })
+}
+
+/// Fails naming every selected offset that is not one of `found`, so a stale
+/// or mistyped selection is not quietly ignored.
+pub(crate) fn check_selected(selected: &[usize], found: impl Iterator<Item = usize> + Clone) -> Result<()> {
+    let unknown: Vec<usize> = selected
+        .iter()
+        .copied()
+        .filter(|offset| !found.clone().any(|found| found == *offset))
+        .collect();
+    if unknown.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::UnknownOffsets(unknown))
+    }
+}
+
+/// Offsets of SteamID values held by string properties, keyed to their paths.
+fn steamid_property_paths(save: &SaveGame) -> HashMap<usize, String> {
+    save.walk()
+        .into_iter()
+        .filter(|(_, property)| property.as_str().is_some_and(is_valid_steamid))
+        .map(|(path, property)| (property.body_offset() + 4, path))
+        .collect()
+}
+
+fn is_fstring_content(data: &[u8], offset: usize) -> bool {
+    offset >= 4
+        && data[offset - 4..offset] == STEAMID_FSTRING_LEN
+        && data.get(offset + STEAMID_LEN) == Some(&0)
+}
@@ -1,66 +1,125 @@
+use std::path::{Path, PathBuf};
+use crate::transfer::TransferChoice;
 use crate::utils;
-use std::fs::{File, OpenOptions};
-use std::io::{Read, Write};
+use steamid_replacer::backup::BackupStore;
+use steamid_replacer::occurrences::{self, Occurrence, SteamIdGroup};
+use steamid_replacer::steam::{self, SteamAccount};
+use steamid_replacer::{Error, files, personalize, replace, steamid};
 
 #[derive(Default)]
 pub struct ReplacerTab {
     pub file_path: String,
     pub new_steamid: String,
     pub current_steamid: String,
+    /// The save had its SteamID removed and can be bound to an account again.
+    pub universal: bool,
     pub status: String,
     pub backup_filename: String,
-    pub transfer_demo_save: bool,
+    pub transfer: TransferChoice,
+    pub include_lookalikes: bool,
     pub show_demo_error: bool,
+    pub occurrences: Vec<Occurrence>,
+    /// Which entries of `occurrences` to replace.
+    pub selected: Vec<bool>,
+    pub show_occurrences: bool,
+    pub steamid_groups: Vec<SteamIdGroup>,
+    pub steam_dir: Option<PathBuf>,
+    pub accounts: Vec<SteamAccount>,
+    /// Why `accounts` is empty, if it is.
+    pub accounts_status: String,
 }
 
 impl ReplacerTab {
     pub fn read_current_steamid(&mut self) {
         self.current_steamid.clear();
+        self.universal = false;
+        self.occurrences.clear();
+        self.selected.clear();
+        self.steamid_groups.clear();
         
         if self.file_path.is_empty() {
             return;
         }
         
-        if let Ok(mut file) = File::open(&self.file_path) {
-            let mut data = Vec::new();
-            if file.read_to_end(&mut data).is_ok() {
-                for i in 0..=data.len().saturating_sub(17) {
-                    if self.is_steamid_at_position(&data, i) {
-                        if let Ok(steamid) = String::from_utf8(data[i..i+17].to_vec()) {
-                            self.current_steamid = steamid;
-                            break;
-                        }
-                    }
-                }
+        let data = files::read_save(Path::new(&self.file_path)).ok();
+        self.transfer.load(Path::new(&self.file_path), data.as_deref(), false);
+        if let Some(data) = data {
+            if let Some(steamid) = replace::find_steamid(&data) {
+                self.current_steamid = steamid;
+            } else {
+                self.universal = !personalize::find_empty_owner_properties(&data).is_empty();
+            }
+            self.occurrences = occurrences::find_occurrences(&data);
+            self.steamid_groups = occurrences::group_steamids(&self.occurrences);
+            self.select_default();
+        }
+    }
+
+    /// Points the tab at the transferred file so it keeps showing the edited save.
+    fn follow_transfer(&mut self, final_path: &Path) {
+        if final_path != Path::new(&self.file_path) {
+            self.file_path = final_path.to_string_lossy().to_string();
+            self.transfer.enabled = false;
+        }
+    }
+
+    /// Loads the accounts of `steam_dir`, detecting the install if none was chosen.
+    pub fn load_accounts(&mut self) {
+        self.accounts.clear();
+        if self.steam_dir.is_none() {
+            self.steam_dir = steam::find_steam_dir();
+        }
+
+        let Some(steam_dir) = &self.steam_dir else {
+            self.accounts_status = Error::SteamNotFound.to_string();
+            return;
+        };
+        match steam::load_accounts(steam_dir) {
+            Ok(accounts) if accounts.is_empty() => {
+                self.accounts_status = "No accounts in loginusers.vdf".to_string();
+            }
+            Ok(accounts) => {
+                self.accounts = accounts;
+                self.accounts_status.clear();
             }
+            Err(e) => self.accounts_status = e.to_string(),
         }
     }
 
-    pub fn handle_replace(&mut self) {
+    /// Ticks property matches, plus look-alikes when they are included.
+    pub fn select_default(&mut self) {
+        self.selected = self
+            .occurrences
+            .iter()
+            .map(|o| o.is_property || self.include_lookalikes)
+            .collect();
+    }
+
+    fn selected_offsets(&self) -> Option<Vec<usize>> {
+        if self.occurrences.is_empty() {
+            return None;
+        }
+        let offsets = self
+            .occurrences
+            .iter()
+            .zip(&self.selected)
+            .filter(|(_, selected)| **selected)
+            .map(|(o, _)| o.offset)
+            .collect();
+        Some(offsets)
+    }
+
+    pub fn handle_replace(&mut self, backup: BackupStore) {
         if self.file_path.is_empty() {
             self.status = "(!) Please select a file first".to_string();
             self.backup_filename.clear();
         } else if self.new_steamid.is_empty() {
             self.status = "(!) Please enter a new SteamID".to_string();
             self.backup_filename.clear();
-        } else if !self.current_steamid.is_empty() && self.current_steamid == self.new_steamid {
-            self.status = "(!) Error: New SteamID is the same as current SteamID".to_string();
-            self.backup_filename.clear();
-        } else if self.transfer_demo_save && !self.file_path.contains("Demo00") {
-            self.status = "❌ Error: File does not contain 'Demo00'. Cannot transfer demo save.".to_string();
-            self.backup_filename.clear();
         } else {
-            match self.replace_steamid() {
-                Ok((count, backup_name)) => {
-                    if count > 0 {
-                        self.status = format!("✅ Successfully replaced SteamID!");
-                        self.backup_filename = backup_name;
-                        self.current_steamid = self.new_steamid.clone();
-                    } else {
-                        self.status = "⚠️ No valid SteamIDs found in file".to_string();
-                        self.backup_filename.clear();
-                    }
-                }
+            match steamid::parse(&self.new_steamid) {
+                Ok(new_steamid) if self.universal => self.personalize_with(&new_steamid.to_string(), backup),
+                Ok(new_steamid) => self.replace_with(&new_steamid.to_string(), backup),
                 Err(e) => {
                     self.status = format!("❌ Error: {}", e);
                     self.backup_filename.clear();
@@ -69,81 +128,99 @@ impl ReplacerTab {
         }
     }
 
-    fn replace_steamid(&self) -> Result<(usize, String), String> {
-        if !self.is_valid_steamid(&self.new_steamid) {
-            return Err("Invalid SteamID format. Must be 17 digits starting with 7656".to_string());
-        }
-
-        let backup_path = utils::create_backup(&self.file_path)?;
-        let backup_filename = utils::get_backup_filename(&backup_path);
-
-        let mut file = File::open(&self.file_path)
-            .map_err(|e| format!("Failed to open file: {}", e))?;
-
-        let mut data = Vec::new();
-        file.read_to_end(&mut data)
-            .map_err(|e| format!("Failed to read file: {}", e))?;
-
-        let mut replacements = 0;
-        let mut i = 0;
-
-        while i <= data.len().saturating_sub(17) {
-            if self.is_steamid_at_position(&data, i) {
-                for (j, &byte) in self.new_steamid.as_bytes().iter().enumerate() {
-                    data[i + j] = byte;
+    /// Writes the SteamID back into a universal save.
+    fn personalize_with(&mut self, new_steamid: &str, backup: BackupStore) {
+        let options = personalize::PersonalizeOptions {
+            demo_transfer: self.transfer.to_options(),
+            backup: Some(backup),
+            ..Default::default()
+        };
+        match personalize::personalize_in_file(Path::new(&self.file_path), new_steamid, &options) {
+            Ok(report) => {
+                self.status = format!("✅ Successfully re-personalized save to {}!", new_steamid);
+                self.backup_filename = report
+                    .backup_path
+                    .as_deref()
+                    .map(files::backup_file_name)
+                    .unwrap_or_default();
+                if let Some(replaced) = &report.replaced_backup {
+                    self.status.push_str(&format!("\nReplaced save backed up as: {}", files::backup_file_name(replaced)));
                 }
-                replacements += 1;
-                i += 17; 
-            } else {
-                i += 1;
+                if let Some(warning) = utils::cloud_warning(&report.final_path) {
+                    self.status.push_str(&format!("\n{}", warning));
+                }
+                self.follow_transfer(&report.final_path);
+                self.read_current_steamid();
             }
-        }
-
-        if replacements > 0 {
-            let mut file = OpenOptions::new()
-                .write(true)
-                .truncate(true)
-                .open(&self.file_path)
-                .map_err(|e| {
-                    let _ = std::fs::copy(&backup_path, &self.file_path);
-                    format!("Failed to write to file: {}", e)
-                })?;
-
-            file.write_all(&data)
-                .map_err(|e| {
-                    let _ = std::fs::copy(&backup_path, &self.file_path);
-                    format!("Failed to write to file: {}", e)
-                })?;
-
-            if self.transfer_demo_save {
-                utils::handle_demo_transfer(&self.file_path, false)?;
+            Err(e) => {
+                self.status = format!("❌ Error: {}", e);
+                self.backup_filename.clear();
             }
         }
-
-        Ok((replacements, backup_filename))
     }
 
-    fn is_valid_steamid(&self, steamid: &str) -> bool {
-        steamid.len() == 17 
-            && steamid.starts_with("7656") 
-            && steamid.chars().all(|c| c.is_ascii_digit())
-    }
-
-    fn is_steamid_at_position(&self, data: &[u8], pos: usize) -> bool {
-        if pos + 17 > data.len() {
-            return false;
-        }
-
-        if &data[pos..pos + 4] != b"7656" {
-            return false;
+    fn replace_with(&mut self, new_steamid: &str, backup: BackupStore) {
+        if !self.current_steamid.is_empty()
+            && self.current_steamid == new_steamid
+            && occurrences::is_consistent(&self.steamid_groups)
+        {
+            self.status = "(!) Error: New SteamID is the same as current SteamID".to_string();
+            self.backup_filename.clear();
+            return;
         }
 
-        for i in pos..pos + 17 {
-            if !data[i].is_ascii_digit() {
-                return false;
+        let options = replace::ReplaceOptions {
+            demo_transfer: self.transfer.to_options(),
+            include_lookalikes: self.include_lookalikes,
+            only_offsets: self.selected_offsets(),
+            backup: Some(backup),
+            ..Default::default()
+        };
+        match replace::replace_steamid_in_file(Path::new(&self.file_path), new_steamid, &options) {
+            Ok(report) => {
+                self.status = if report.previous_steamids.len() > 1 {
+                    format!(
+                        "✅ Successfully normalised {} different SteamIDs to {}!",
+                        report.previous_steamids.len(),
+                        new_steamid
+                    )
+                } else if report.skipped.is_empty() {
+                    "✅ Successfully replaced SteamID!".to_string()
+                } else {
+                    format!(
+                        "✅ Successfully replaced SteamID! {} match(es) left untouched.",
+                        report.skipped.len()
+                    )
+                };
+                self.backup_filename = report
+                    .backup_path
+                    .as_deref()
+                    .map(files::backup_file_name)
+                    .unwrap_or_default();
+                if let Some(replaced) = &report.replaced_backup {
+                    self.status.push_str(&format!("\nReplaced save backed up as: {}", files::backup_file_name(replaced)));
+                }
+                if let Some(warning) = utils::cloud_warning(&report.final_path) {
+                    self.status.push_str(&format!("\n{}", warning));
+                }
+                self.follow_transfer(&report.final_path);
+                self.read_current_steamid();
+                if self.current_steamid.is_empty() {
+                    self.current_steamid = new_steamid.to_string();
+                }
+            }
+            Err(Error::NothingSelected) => {
+                self.status = "(!) No occurrences selected".to_string();
+                self.backup_filename.clear();
+            }
+            Err(Error::NoSteamIdFound) => {
+                self.status = "⚠️ No valid SteamIDs found in file".to_string();
+                self.backup_filename.clear();
+            }
+            Err(e) => {
+                self.status = format!("❌ Error: {}", e);
+                self.backup_filename.clear();
             }
         }
-
-        true
     }
-}
\ No newline at end of file
+}
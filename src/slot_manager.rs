@@ -0,0 +1,206 @@
+use std::path::{Path, PathBuf};
+use steamid_replacer::backup::BackupStore;
+use steamid_replacer::cloud::{self, CloudStatus, RemoteCache};
+use steamid_replacer::slots::{self, Slot};
+use steamid_replacer::{files, steam};
+
+#[derive(Default)]
+pub struct SlotsTab {
+    /// SaveGames folder being shown.
+    pub dir: String,
+    /// SaveGames folders found on this machine (Windows and Proton prefixes).
+    pub detected: Vec<PathBuf>,
+    pub slots: Vec<Slot>,
+    /// Steam Cloud status of each entry of `slots`.
+    pub cloud: Vec<CloudStatus>,
+    /// Steam Cloud records of this machine's accounts.
+    pub caches: Vec<RemoteCache>,
+    /// Index into `slots`.
+    pub selected: Option<usize>,
+    /// Name used by duplicate and rename.
+    pub new_name: String,
+    /// Index into `slots` of the other side of a swap.
+    pub swap_with: Option<usize>,
+    pub overwrite: bool,
+    pub status: String,
+    pub backup_filename: String,
+}
+
+impl SlotsTab {
+    /// Looks for SaveGames folders and shows the first one if none is set yet.
+    pub fn detect_dir(&mut self) {
+        self.detected = slots::default_save_dirs();
+        if self.dir.is_empty()
+            && let Some(dir) = self.detected.first()
+        {
+            self.dir = dir.to_string_lossy().to_string();
+        }
+    }
+
+    /// Shows the folder containing `file_path` with that slot selected, or
+    /// the folder itself.
+    pub fn open(&mut self, file_path: &str) {
+        let path = Path::new(file_path);
+        let dir = if path.is_dir() { Some(path) } else { path.parent() };
+        if let Some(dir) = dir {
+            self.dir = dir.to_string_lossy().to_string();
+        }
+        self.refresh();
+        if let Some(index) = self.position(path) {
+            self.select(index);
+        }
+    }
+
+    pub fn selected_slot(&self) -> Option<&Slot> {
+        self.selected.and_then(|i| self.slots.get(i))
+    }
+
+    /// Re-reads the folder, keeping the selection on the same files.
+    pub fn refresh(&mut self) {
+        let selected = self.selected_slot().map(|slot| slot.path.clone());
+        let swap_with = self.swap_with.and_then(|i| self.slots.get(i)).map(|slot| slot.path.clone());
+        self.slots.clear();
+        self.selected = None;
+        self.swap_with = None;
+        if self.dir.is_empty() {
+            return;
+        }
+
+        match slots::list(Path::new(&self.dir)) {
+            Ok(list) => self.slots = list,
+            Err(e) => self.status = format!("❌ Error: {}", e),
+        }
+        self.refresh_cloud();
+        self.selected = selected.and_then(|path| self.position(&path));
+        self.swap_with = swap_with.and_then(|path| self.position(&path));
+    }
+
+    fn refresh_cloud(&mut self) {
+        self.caches = steam::installed_steam_dirs()
+            .iter()
+            .flat_map(|steam_dir| cloud::remote_caches(steam_dir, steam::APP_ID))
+            .filter_map(|path| RemoteCache::load(&path).ok())
+            .collect();
+        self.cloud = self
+            .slots
+            .iter()
+            .map(|slot| {
+                self.caches
+                    .iter()
+                    .filter_map(|cache| cache.check(&slot.path).ok())
+                    .find(|status| *status != CloudStatus::Untracked)
+                    .unwrap_or(CloudStatus::Untracked)
+            })
+            .collect();
+    }
+
+    pub fn select(&mut self, index: usize) {
+        self.selected = Some(index);
+        self.new_name = self.slots[index].name();
+        self.overwrite = false;
+        if self.swap_with == Some(index) {
+            self.swap_with = None;
+        }
+    }
+
+    pub fn handle_duplicate(&mut self, backup: BackupStore) {
+        let Some(slot) = self.selected_slot() else {
+            return;
+        };
+        match slots::duplicate(&slot.path, &self.new_name, &backup, self.overwrite) {
+            Ok((target, replaced)) => {
+                self.status = format!("✅ Successfully copied {} to {}", slot.name(), files::backup_file_name(&target));
+                self.backup_filename = replaced.as_deref().map(files::backup_file_name).unwrap_or_default();
+                self.overwrite = false;
+                self.refresh();
+                self.selected = self.position(&target);
+            }
+            Err(e) => self.show_error(e),
+        }
+    }
+
+    pub fn handle_rename(&mut self) {
+        let Some(slot) = self.selected_slot() else {
+            return;
+        };
+        match slots::rename(&slot.path, &self.new_name) {
+            Ok(target) => {
+                self.status = format!("✅ Successfully renamed {} to {}", slot.name(), files::backup_file_name(&target));
+                self.backup_filename.clear();
+                self.refresh();
+                self.selected = self.position(&target);
+            }
+            Err(e) => self.show_error(e),
+        }
+    }
+
+    pub fn handle_swap(&mut self, backup: BackupStore) {
+        let (Some(a), Some(b)) = (self.selected_slot(), self.swap_with.and_then(|i| self.slots.get(i))) else {
+            return;
+        };
+        match slots::swap(&a.path, &b.path, &backup) {
+            Ok([backup_a, backup_b]) => {
+                self.status = format!("✅ Successfully swapped {} and {}", a.name(), b.name());
+                self.backup_filename = format!(
+                    "{}, {}",
+                    files::backup_file_name(&backup_a),
+                    files::backup_file_name(&backup_b)
+                );
+                self.refresh();
+            }
+            Err(e) => self.show_error(e),
+        }
+    }
+
+    pub fn handle_delete(&mut self, backup: BackupStore) {
+        let Some(slot) = self.selected_slot() else {
+            return;
+        };
+        match slots::delete(&slot.path, &backup) {
+            Ok(backup_path) => {
+                self.status = format!("✅ Successfully deleted {}", slot.name());
+                self.backup_filename = files::backup_file_name(&backup_path);
+                self.selected = None;
+                self.refresh();
+            }
+            Err(e) => self.show_error(e),
+        }
+    }
+
+    /// Records the selected slot's current contents in every Steam Cloud cache
+    /// that tracks it, so Steam uploads it rather than restoring the old one.
+    pub fn handle_update_cloud(&mut self, backup: BackupStore) {
+        let Some(slot) = self.selected_slot() else {
+            return;
+        };
+        let path = slot.path.clone();
+        let name = slot.name();
+
+        let mut backups = Vec::new();
+        for cache in &mut self.caches {
+            let result = cache
+                .update(&path)
+                .and_then(|tracked| if tracked { cache.save(&path, Some(&backup)) } else { Ok(None) });
+            match result {
+                Ok(backup_path) => backups.extend(backup_path),
+                Err(e) => {
+                    self.show_error(e);
+                    self.refresh();
+                    return;
+                }
+            }
+        }
+        self.status = format!("✅ Successfully updated the Steam Cloud record of {}", name);
+        self.backup_filename = backups.iter().map(|p| files::backup_file_name(p)).collect::<Vec<_>>().join(", ");
+        self.refresh();
+    }
+
+    fn position(&self, path: &Path) -> Option<usize> {
+        self.slots.iter().position(|slot| slot.path == path)
+    }
+
+    fn show_error(&mut self, e: steamid_replacer::Error) {
+        self.status = format!("❌ Error: {}", e);
+        self.backup_filename.clear();
+    }
+}
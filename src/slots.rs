@@ -0,0 +1,184 @@
+//! Listing and managing the save slots in a SaveGames folder.
+//!
+//! Every operation that would destroy a file copies it into the backup store
+//! first, so a slot can always be restored from the Backups tab.
+
+use crate::backup::BackupStore;
+use crate::demo::{self, DemoEvidence};
+use crate::error::{Error, Result};
+use crate::{files, replace, steam};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+const SAVE_EXTENSION: &str = "sav";
+
+/// A `.sav` file in a SaveGames folder.
+#[derive(Debug, Clone)]
+pub struct Slot {
+    pub path: PathBuf,
+    pub size: u64,
+    /// Unix time of the last modification, if the filesystem reports one.
+    pub modified: Option<u64>,
+    pub steamid: Option<String>,
+    /// Why the slot counts as a demo save; `None` for full-game saves.
+    pub demo: Option<DemoEvidence>,
+}
+
+impl Slot {
+    pub fn name(&self) -> String {
+        files::backup_file_name(&self.path)
+    }
+
+    pub fn is_demo(&self) -> bool {
+        self.demo.is_some()
+    }
+}
+
+/// SaveGames folders of local Stellar Blade installs that exist on this
+/// machine: the Windows profile first, then Proton prefixes in every Steam
+/// library (Linux and Steam Deck).
+pub fn default_save_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Some(local_app_data) = std::env::var_os("LOCALAPPDATA") {
+        dirs.push(PathBuf::from(local_app_data).join("SB").join("Saved").join("SaveGames"));
+    }
+    if let Some(user_profile) = std::env::var_os("USERPROFILE") {
+        dirs.push(
+            PathBuf::from(user_profile)
+                .join("AppData")
+                .join("Local")
+                .join("SB")
+                .join("Saved")
+                .join("SaveGames"),
+        );
+    }
+    for steam_dir in steam::installed_steam_dirs() {
+        dirs.extend(steam::proton_save_dirs(&steam_dir, steam::APP_ID));
+    }
+    dirs.dedup();
+    dirs.retain(|dir| dir.is_dir());
+    dirs
+}
+
+pub fn find_save_dir() -> Option<PathBuf> {
+    default_save_dirs().into_iter().next()
+}
+
+/// The `.sav` files directly inside `dir`, sorted by name.
+pub fn list(dir: &Path) -> Result<Vec<Slot>> {
+    let read_dir = std::fs::read_dir(dir).map_err(Error::io("Failed to read save folder"))?;
+
+    let mut slots: Vec<Slot> = read_dir
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && is_save_file(path))
+        .map(|path| describe(&path))
+        .collect();
+    slots.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(slots)
+}
+
+/// Reads the size, modification time, SteamID and demo status of one slot.
+pub fn describe(path: &Path) -> Slot {
+    let metadata = std::fs::metadata(path).ok();
+    let modified = metadata
+        .as_ref()
+        .and_then(|m| m.modified().ok())
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_secs());
+    let data = files::read_save(path).ok();
+
+    Slot {
+        path: path.to_path_buf(),
+        size: metadata.map(|m| m.len()).unwrap_or(0),
+        modified,
+        steamid: data.as_deref().and_then(replace::find_steamid),
+        demo: data.as_deref().and_then(|data| demo::detect(path, data)),
+    }
+}
+
+/// Copies `source` to the slot named `name` in the same folder. An existing
+/// slot is only replaced with `overwrite`, and is backed up first.
+/// Returns the new slot and the backup of the replaced one.
+pub fn duplicate(source: &Path, name: &str, store: &BackupStore, overwrite: bool) -> Result<(PathBuf, Option<PathBuf>)> {
+    let target = sibling(source, name)?;
+    if target == source {
+        return Err(Error::SameSlot(target));
+    }
+    if target.exists() && !overwrite {
+        return Err(Error::SlotExists(target));
+    }
+
+    let data = files::read_save(source)?;
+    let replaced_backup = if target.exists() {
+        Some(store.create(&target)?)
+    } else {
+        None
+    };
+    files::write_save(&target, &data)?;
+    Ok((target, replaced_backup))
+}
+
+/// Exchanges the contents of two slots after backing up both. If the second
+/// write fails the first slot is put back.
+pub fn swap(a: &Path, b: &Path, store: &BackupStore) -> Result<[PathBuf; 2]> {
+    if a == b {
+        return Err(Error::SameSlot(a.to_path_buf()));
+    }
+    let data_a = files::read_save(a)?;
+    let data_b = files::read_save(b)?;
+    let backups = [store.create(a)?, store.create(b)?];
+
+    files::write_save(a, &data_b)?;
+    if let Err(cause) = files::write_save(b, &data_a) {
+        return match files::write_save(a, &data_a) {
+            Ok(()) => Err(cause),
+            Err(rollback) => Err(Error::RollbackFailed {
+                cause: Box::new(cause),
+                rollback: Box::new(rollback),
+                backup_path: Some(backups[0].clone()),
+            }),
+        };
+    }
+    Ok(backups)
+}
+
+/// Renames a slot within its folder, refusing to replace another slot.
+pub fn rename(path: &Path, name: &str) -> Result<PathBuf> {
+    let target = sibling(path, name)?;
+    if target.exists() {
+        return Err(Error::SlotExists(target));
+    }
+    std::fs::rename(path, &target).map_err(Error::io("Failed to rename save"))?;
+    Ok(target)
+}
+
+/// Moves a slot into the backup store. Returns the backup.
+pub fn delete(path: &Path, store: &BackupStore) -> Result<PathBuf> {
+    let backup_path = store.create(path)?;
+    std::fs::remove_file(path).map_err(Error::io("Failed to delete save"))?;
+    Ok(backup_path)
+}
+
+fn is_save_file(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case(SAVE_EXTENSION))
+}
+
+/// `name` as a file in the folder of `path`, with `.sav` added when missing.
+fn sibling(path: &Path, name: &str) -> Result<PathBuf> {
+    let name = name.trim();
+    let is_plain_name = !name.is_empty()
+        && name != "."
+        && name != ".."
+        && !name.contains(['/', '\\', ':']);
+    if !is_plain_name {
+        return Err(Error::InvalidSlotName(name.to_string()));
+    }
+
+    let name = if is_save_file(Path::new(name)) {
+        name.to_string()
+    } else {
+        format!("{}.{}", name, SAVE_EXTENSION)
+    };
+    Ok(path.with_file_name(name))
+}
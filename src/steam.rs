@@ -0,0 +1,195 @@
+//! Locating a local Steam install, its library folders and the accounts that
+//! have logged into it.
+
+use crate::error::{Error, Result};
+use crate::steamid::SteamId;
+use crate::vdf::{self, Vdf};
+use std::path::{Path, PathBuf};
+
+const LOGIN_USERS: &str = "config/loginusers.vdf";
+const LIBRARY_FOLDERS: &str = "steamapps/libraryfolders.vdf";
+
+/// Stellar Blade's Steam app ID.
+pub const APP_ID: u32 = 3489700;
+
+/// The game's SaveGames folder inside a Proton prefix (`compatdata/<appid>`).
+const PROTON_SAVE_DIR: &str = "pfx/drive_c/users/steamuser/AppData/Local/SB/Saved/SaveGames";
+
+/// An account listed in `config/loginusers.vdf`.
+#[derive(Debug, Clone)]
+pub struct SteamAccount {
+    pub steamid: SteamId,
+    pub account_name: String,
+    pub persona_name: String,
+    pub most_recent: bool,
+    /// Unix time of the last login, 0 if unknown.
+    pub timestamp: u64,
+}
+
+impl SteamAccount {
+    /// `Persona (account_name)`, or just the account name if they match.
+    pub fn label(&self) -> String {
+        if self.persona_name.is_empty() || self.persona_name == self.account_name {
+            self.account_name.clone()
+        } else {
+            format!("{} ({})", self.persona_name, self.account_name)
+        }
+    }
+}
+
+/// Usual Steam install locations for this platform, existing or not.
+pub fn default_steam_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+
+    #[cfg(target_os = "windows")]
+    {
+        for var in ["ProgramFiles(x86)", "ProgramFiles"] {
+            if let Ok(program_files) = std::env::var(var) {
+                dirs.push(PathBuf::from(program_files).join("Steam"));
+            }
+        }
+        dirs.push(PathBuf::from(r"C:\Program Files (x86)\Steam"));
+    }
+
+    #[cfg(target_os = "macos")]
+    if let Ok(home) = std::env::var("HOME") {
+        dirs.push(PathBuf::from(home).join("Library/Application Support/Steam"));
+    }
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    if let Ok(home) = std::env::var("HOME") {
+        let home = PathBuf::from(home);
+        dirs.push(home.join(".steam/steam"));
+        dirs.push(home.join(".local/share/Steam"));
+        dirs.push(home.join(".var/app/com.valvesoftware.Steam/.local/share/Steam"));
+    }
+
+    dirs
+}
+
+/// The first default location that looks like a Steam install.
+pub fn find_steam_dir() -> Option<PathBuf> {
+    default_steam_dirs().into_iter().find(|dir| is_steam_dir(dir))
+}
+
+pub fn is_steam_dir(dir: &Path) -> bool {
+    dir.join(LOGIN_USERS).is_file() || dir.join("steamapps").is_dir()
+}
+
+/// Every default location that looks like a Steam install, with symlinked
+/// duplicates such as `~/.steam/steam` removed.
+pub fn installed_steam_dirs() -> Vec<PathBuf> {
+    let mut seen = Vec::new();
+    let mut dirs = Vec::new();
+    for dir in default_steam_dirs().into_iter().filter(|dir| is_steam_dir(dir)) {
+        let canonical = dir.canonicalize().unwrap_or_else(|_| dir.clone());
+        if !seen.contains(&canonical) {
+            seen.push(canonical);
+            dirs.push(dir);
+        }
+    }
+    dirs
+}
+
+/// The install itself followed by the libraries in `steamapps/libraryfolders.vdf`.
+/// A missing or unreadable file leaves just the install.
+pub fn library_folders(steam_dir: &Path) -> Vec<PathBuf> {
+    let mut folders = vec![steam_dir.to_path_buf()];
+    let listed = std::fs::read_to_string(steam_dir.join(LIBRARY_FOLDERS))
+        .ok()
+        .and_then(|text| parse_library_folders(&text).ok())
+        .unwrap_or_default();
+    for folder in listed {
+        let is_known = folders.iter().any(|known| same_dir(known, &folder));
+        if !is_known {
+            folders.push(folder);
+        }
+    }
+    folders
+}
+
+/// Library paths from the text of a `libraryfolders.vdf`. Handles both the
+/// current layout (`"0" { "path" "..." }`) and the old one (`"1" "D:\\Games"`).
+pub fn parse_library_folders(text: &str) -> Result<Vec<PathBuf>> {
+    let root = vdf::parse(text)?;
+    let Some(libraries) = root.get("libraryfolders") else {
+        return Err(Error::InvalidVdf {
+            line: 1,
+            reason: "missing \"libraryfolders\" section".to_string(),
+        });
+    };
+
+    let folders = libraries
+        .entries()
+        .iter()
+        .filter(|(key, _)| key.parse::<u32>().is_ok())
+        .filter_map(|(_, library)| library.as_str().or_else(|| library.get_str("path")))
+        .map(PathBuf::from)
+        .collect();
+    Ok(folders)
+}
+
+/// Existing SaveGames folders inside the Proton prefixes of `app_id` in every
+/// library of `steam_dir`.
+pub fn proton_save_dirs(steam_dir: &Path, app_id: u32) -> Vec<PathBuf> {
+    library_folders(steam_dir)
+        .into_iter()
+        .map(|library| {
+            library
+                .join("steamapps")
+                .join("compatdata")
+                .join(app_id.to_string())
+                .join(PROTON_SAVE_DIR)
+        })
+        .filter(|dir| dir.is_dir())
+        .collect()
+}
+
+fn same_dir(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+/// Accounts from `<steam_dir>/config/loginusers.vdf`, most recent first.
+pub fn load_accounts(steam_dir: &Path) -> Result<Vec<SteamAccount>> {
+    read_login_users(&steam_dir.join(LOGIN_USERS))
+}
+
+pub fn read_login_users(path: &Path) -> Result<Vec<SteamAccount>> {
+    let text = std::fs::read_to_string(path).map_err(Error::io("Failed to read loginusers.vdf"))?;
+    parse_login_users(&text)
+}
+
+/// Accounts from the text of a `loginusers.vdf`. Entries whose key is not a
+/// valid individual SteamID64 are skipped.
+pub fn parse_login_users(text: &str) -> Result<Vec<SteamAccount>> {
+    let root = vdf::parse(text)?;
+    let Some(users) = root.get("users") else {
+        return Err(Error::InvalidVdf {
+            line: 1,
+            reason: "missing \"users\" section".to_string(),
+        });
+    };
+
+    let mut accounts: Vec<SteamAccount> = users
+        .entries()
+        .iter()
+        .filter_map(|(key, user)| account(key, user))
+        .collect();
+    accounts.sort_by(|a, b| b.most_recent.cmp(&a.most_recent).then(b.timestamp.cmp(&a.timestamp)));
+    Ok(accounts)
+}
+
+fn account(key: &str, user: &Vdf) -> Option<SteamAccount> {
+    let steamid = SteamId::from_steamid64(key.parse().ok()?).ok()?;
+
+    Some(SteamAccount {
+        steamid,
+        account_name: user.get_str("AccountName").unwrap_or_default().to_string(),
+        persona_name: user.get_str("PersonaName").unwrap_or_default().to_string(),
+        most_recent: user.get_str("MostRecent") == Some("1"),
+        timestamp: user.get_str("Timestamp").and_then(|t| t.parse().ok()).unwrap_or(0),
+    })
+}
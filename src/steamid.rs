@@ -0,0 +1,183 @@
+//! Parsing and conversion between the common SteamID notations.
+//!
+//! A SteamID64 packs the account ID into the low 32 bits, followed by a
+//! 20-bit instance, a 4-bit account type and an 8-bit universe.
+
+use crate::error::{Error, Result};
+use std::fmt;
+
+const UNIVERSE_PUBLIC: u8 = 1;
+const ACCOUNT_TYPE_INDIVIDUAL: u8 = 1;
+const INSTANCE_DESKTOP: u32 = 1;
+
+const PROFILE_URL_PREFIXES: &[&str] = &[
+    "https://steamcommunity.com/profiles/",
+    "http://steamcommunity.com/profiles/",
+    "steamcommunity.com/profiles/",
+];
+const VANITY_URL_MARKER: &str = "steamcommunity.com/id/";
+
+/// A SteamID of a public individual account, the only kind that owns saves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SteamId(u64);
+
+impl SteamId {
+    pub fn from_account_id(account_id: u32) -> Self {
+        SteamId(
+            (UNIVERSE_PUBLIC as u64) << 56
+                | (ACCOUNT_TYPE_INDIVIDUAL as u64) << 52
+                | (INSTANCE_DESKTOP as u64) << 32
+                | account_id as u64,
+        )
+    }
+
+    /// Checks the universe, account type and instance bits of a raw SteamID64.
+    pub fn from_steamid64(value: u64) -> Result<Self> {
+        let id = SteamId(value);
+        if id.universe() != UNIVERSE_PUBLIC {
+            return Err(notation_error(&value.to_string(), "not in the public universe"));
+        }
+        if id.account_type() != ACCOUNT_TYPE_INDIVIDUAL {
+            return Err(notation_error(&value.to_string(), "not an individual account"));
+        }
+        if id.instance() != INSTANCE_DESKTOP {
+            return Err(notation_error(&value.to_string(), "not a user instance"));
+        }
+        if id.account_id() == 0 {
+            return Err(notation_error(&value.to_string(), "account ID is zero"));
+        }
+        Ok(id)
+    }
+
+    pub fn steamid64(self) -> u64 {
+        self.0
+    }
+
+    pub fn account_id(self) -> u32 {
+        self.0 as u32
+    }
+
+    pub fn instance(self) -> u32 {
+        ((self.0 >> 32) & 0xF_FFFF) as u32
+    }
+
+    pub fn account_type(self) -> u8 {
+        ((self.0 >> 52) & 0xF) as u8
+    }
+
+    pub fn universe(self) -> u8 {
+        (self.0 >> 56) as u8
+    }
+
+    /// `STEAM_0:Y:Z`, using the universe digit most tools print.
+    pub fn steam2(self) -> String {
+        let account_id = self.account_id();
+        format!("STEAM_0:{}:{}", account_id & 1, account_id >> 1)
+    }
+
+    /// `[U:1:N]`
+    pub fn steam3(self) -> String {
+        format!("[U:{}:{}]", self.universe(), self.account_id())
+    }
+
+    pub fn profile_url(self) -> String {
+        format!("https://steamcommunity.com/profiles/{}", self.0)
+    }
+}
+
+impl fmt::Display for SteamId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Reads a SteamID64, `STEAM_X:Y:Z`, `[U:1:N]`, a bare 32-bit account ID or a
+/// `steamcommunity.com/profiles/…` URL.
+pub fn parse(input: &str) -> Result<SteamId> {
+    let trimmed = input.trim();
+    if trimmed.is_empty() {
+        return Err(notation_error(input, "empty"));
+    }
+
+    if trimmed.contains(VANITY_URL_MARKER) {
+        return Err(notation_error(
+            input,
+            "custom profile URLs cannot be resolved offline; use the /profiles/ URL or SteamID64",
+        ));
+    }
+    if let Some(rest) = PROFILE_URL_PREFIXES.iter().find_map(|prefix| trimmed.strip_prefix(prefix)) {
+        let id = rest.trim_end_matches('/');
+        return parse(id).map_err(|_| notation_error(input, "profile URL does not end in a SteamID"));
+    }
+
+    if let Some(rest) = trimmed.strip_prefix("STEAM_") {
+        return parse_steam2(input, rest);
+    }
+    if trimmed.starts_with('[') || trimmed.starts_with("U:") {
+        return parse_steam3(input, trimmed.trim_start_matches('[').trim_end_matches(']'));
+    }
+
+    if !trimmed.chars().all(|c| c.is_ascii_digit()) {
+        return Err(notation_error(input, "unrecognised format"));
+    }
+    let value: u64 = trimmed.parse().map_err(|_| notation_error(input, "number is too large"))?;
+    match u32::try_from(value) {
+        Ok(0) => Err(notation_error(input, "account ID is zero")),
+        Ok(account_id) => Ok(SteamId::from_account_id(account_id)),
+        Err(_) => SteamId::from_steamid64(value),
+    }
+}
+
+fn parse_steam2(input: &str, rest: &str) -> Result<SteamId> {
+    let parts: Vec<&str> = rest.split(':').collect();
+    let [universe, low_bit, high_bits] = parts[..] else {
+        return Err(notation_error(input, "expected STEAM_X:Y:Z"));
+    };
+
+    // Older games print universe 0 for public accounts.
+    if !matches!(universe, "0" | "1") {
+        return Err(notation_error(input, "not in the public universe"));
+    }
+    let low_bit: u32 = match low_bit {
+        "0" => 0,
+        "1" => 1,
+        _ => return Err(notation_error(input, "Y must be 0 or 1")),
+    };
+    let high_bits: u32 = high_bits
+        .parse()
+        .ok()
+        .filter(|&z| z <= u32::MAX >> 1)
+        .ok_or_else(|| notation_error(input, "Z is not a valid account number"))?;
+
+    let account_id = high_bits << 1 | low_bit;
+    if account_id == 0 {
+        return Err(notation_error(input, "account ID is zero"));
+    }
+    Ok(SteamId::from_account_id(account_id))
+}
+
+fn parse_steam3(input: &str, inner: &str) -> Result<SteamId> {
+    let parts: Vec<&str> = inner.split(':').collect();
+    let [kind, universe, account_id] = parts[..] else {
+        return Err(notation_error(input, "expected [U:1:N]"));
+    };
+
+    if kind != "U" {
+        return Err(notation_error(input, "not an individual account"));
+    }
+    if universe != "1" {
+        return Err(notation_error(input, "not in the public universe"));
+    }
+    match account_id.parse::<u32>() {
+        Ok(0) => Err(notation_error(input, "account ID is zero")),
+        Ok(account_id) => Ok(SteamId::from_account_id(account_id)),
+        Err(_) => Err(notation_error(input, "N is not a valid account ID")),
+    }
+}
+
+fn notation_error(input: &str, reason: &'static str) -> Error {
+    Error::SteamIdNotation {
+        input: input.trim().to_string(),
+        reason,
+    }
+}
@@ -0,0 +1,161 @@
+//! The write pipeline shared by every operation that modifies a save.
+//!
+//! A commit backs up the original, replaces the file atomically, checks the
+//! result, logs the change in the save's operation manifest and performs the
+//! demo rename last. If any step after the original was replaced fails, the
+//! original contents are put back and the log entry is dropped again.
+
+use crate::backup::BackupStore;
+use crate::demo::{self, DemoTransfer};
+use crate::error::{Error, Result};
+use crate::files;
+use crate::gvas::SaveGame;
+use crate::manifest::{self, Change};
+use std::path::{Path, PathBuf};
+
+/// What to do around writing the new contents.
+#[derive(Debug, Clone, Default)]
+pub struct Plan<'a> {
+    /// Back up the original here first.
+    pub backup: Option<&'a BackupStore>,
+    /// Move the file here once written, e.g. for a demo transfer.
+    pub rename_to: Option<PathBuf>,
+    /// Let `rename_to` replace an existing file, which is backed up first.
+    pub overwrite_target: bool,
+    /// Write the new contents straight to `rename_to` and leave the file
+    /// untouched, e.g. for an anonymized copy. The file itself is not backed up.
+    pub keep_source: bool,
+    /// Log the change in the save's operation manifest.
+    pub record: Option<Change>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Committed {
+    pub backup_path: Option<PathBuf>,
+    /// Backup of the file that `rename_to` replaced.
+    pub replaced_backup: Option<PathBuf>,
+    pub final_path: PathBuf,
+    /// The manifest the change was logged in.
+    pub manifest_path: Option<PathBuf>,
+}
+
+/// Replaces `original` (the current contents of `path`) with `data`.
+pub fn commit(path: &Path, original: &[u8], data: &[u8], plan: &Plan) -> Result<Committed> {
+    validate(original, data)?;
+
+    let replaces_existing = match &plan.rename_to {
+        Some(target) if target == path => return Err(Error::InvalidTransferTarget(target.clone())),
+        Some(target) if target.exists() && !plan.overwrite_target => {
+            return Err(Error::TransferTargetExists(target.clone()));
+        }
+        Some(target) => target.exists(),
+        None => false,
+    };
+
+    let backup_path = match plan.backup {
+        Some(store) if !plan.keep_source => Some(store.create(path)?),
+        _ => None,
+    };
+    // The file being replaced is always backed up, even when the source is not.
+    let replaced_backup = match &plan.rename_to {
+        Some(target) if replaces_existing => Some(plan.backup.cloned().unwrap_or_default().create(target)?),
+        _ => None,
+    };
+
+    // The file written now and what to put back there if a later step fails;
+    // `None` means it did not exist.
+    let (written, previous) = match &plan.rename_to {
+        Some(target) if plan.keep_source => {
+            let previous = if replaces_existing { Some(files::read_save(target)?) } else { None };
+            (target.as_path(), previous)
+        }
+        _ => (path, Some(original.to_vec())),
+    };
+    files::write_save(written, data)?;
+
+    let mut committed = Committed {
+        backup_path,
+        replaced_backup,
+        final_path: plan.rename_to.clone().unwrap_or_else(|| path.to_path_buf()),
+        manifest_path: None,
+    };
+    let restore_backup = || committed.backup_path.clone().or_else(|| committed.replaced_backup.clone());
+    // Logged before the rename so that a save is never left changed without its entry.
+    if let Some(change) = &plan.record {
+        let store = plan.backup.cloned().unwrap_or_default();
+        match manifest::record(&store, change, path, original, data, &committed) {
+            Ok(manifest_path) => committed.manifest_path = Some(manifest_path),
+            Err(cause) => return Err(roll_back(written, previous.as_deref(), cause, restore_backup())),
+        }
+    }
+
+    if let Some(target) = &plan.rename_to
+        && !plan.keep_source
+        && let Err(e) = std::fs::rename(path, target)
+    {
+        let cause = Error::io("Failed to transfer demo save")(e);
+        let cause = match committed.manifest_path.as_deref().map(manifest::unrecord) {
+            Some(Err(rollback)) => Error::RollbackFailed {
+                cause: Box::new(cause),
+                rollback: Box::new(rollback),
+                backup_path: committed.backup_path.clone(),
+            },
+            _ => cause,
+        };
+        return Err(roll_back(path, Some(original), cause, committed.backup_path));
+    }
+    Ok(committed)
+}
+
+/// Where a requested demo transfer goes: the chosen slot, or the default one
+/// derived from the file name. Fails if the file is not a demo save.
+pub fn demo_transfer_target(
+    path: &Path,
+    data: &[u8],
+    transfer: Option<&DemoTransfer>,
+    remove_demo: bool,
+) -> Result<Option<PathBuf>> {
+    let Some(transfer) = transfer else {
+        return Ok(None);
+    };
+    if !demo::is_demo_save(path, data) {
+        return Err(Error::NotDemoSave);
+    }
+
+    match &transfer.target {
+        Some(target) => Ok(Some(target.clone())),
+        None => demo::default_target(path, remove_demo).map(Some).ok_or(Error::NoTransferTarget),
+    }
+}
+
+/// A save that parsed before the edit must still parse after it.
+fn validate(original: &[u8], data: &[u8]) -> Result<()> {
+    if SaveGame::parse(original).is_ok()
+        && let Err(e) = SaveGame::parse(data)
+    {
+        return Err(match e {
+            Error::InvalidSave { offset, reason } => Error::InvalidSave {
+                offset,
+                reason: format!("edited save no longer parses: {}", reason),
+            },
+            other => other,
+        });
+    }
+    Ok(())
+}
+
+/// Puts `previous` back at `path`, or deletes the file if it did not exist.
+fn roll_back(path: &Path, previous: Option<&[u8]>, cause: Error, backup_path: Option<PathBuf>) -> Error {
+    let restored = match previous {
+        Some(previous) => files::write_save(path, previous),
+        None => std::fs::remove_file(path).map_err(Error::io("Failed to remove partial copy")),
+    };
+    match restored {
+        Ok(()) => cause,
+        Err(rollback) => Error::RollbackFailed {
+            cause: Box::new(cause),
+            rollback: Box::new(rollback),
+            backup_path,
+        },
+    }
+}
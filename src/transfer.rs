@@ -0,0 +1,55 @@
+use std::path::Path;
+use steamid_replacer::demo::{self, DemoEvidence, DemoTransfer, TransferTarget};
+
+/// Demo transfer settings shared by the Replacer and Remover tabs.
+#[derive(Default)]
+pub struct TransferChoice {
+    pub enabled: bool,
+    /// Why the loaded file counts as a demo save; `None` if it does not.
+    pub evidence: Option<DemoEvidence>,
+    pub targets: Vec<TransferTarget>,
+    /// Index into `targets`.
+    pub selected: usize,
+    pub overwrite_existing: bool,
+}
+
+impl TransferChoice {
+    /// Re-detects the loaded save and lists the slots it can move to.
+    pub fn load(&mut self, path: &Path, data: Option<&[u8]>, remove_demo: bool) {
+        self.evidence = data.and_then(|data| demo::detect(path, data));
+        self.targets = match self.evidence {
+            Some(_) => demo::target_candidates(path, remove_demo),
+            None => Vec::new(),
+        };
+        self.selected = 0;
+        self.overwrite_existing = false;
+    }
+
+    pub fn is_demo(&self) -> bool {
+        self.evidence.is_some()
+    }
+
+    pub fn target(&self) -> Option<&TransferTarget> {
+        self.targets.get(self.selected)
+    }
+
+    /// Whether an edit may start: no transfer requested, or a target is chosen
+    /// and replacing an existing file has been confirmed.
+    pub fn is_ready(&self) -> bool {
+        !self.enabled
+            || (self.is_demo()
+                && self
+                    .target()
+                    .is_some_and(|target| !target.exists || self.overwrite_existing))
+    }
+
+    pub fn to_options(&self) -> Option<DemoTransfer> {
+        if !self.enabled {
+            return None;
+        }
+        Some(DemoTransfer {
+            target: self.target().map(|target| target.path.clone()),
+            overwrite_existing: self.overwrite_existing,
+        })
+    }
+}
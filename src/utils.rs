@@ -1,5 +1,8 @@
 use eframe::egui;
-use std::path::PathBuf;
+use std::path::Path;
+use steamid_replacer::cloud::{self, CloudStatus};
+use steamid_replacer::occurrences::{self, SteamIdGroup};
+use steamid_replacer::{slots, steam};
 
 pub fn load_icon() -> egui::IconData {
     use ico::IconDir;
@@ -17,95 +20,50 @@ pub fn load_icon() -> egui::IconData {
 }
 
 pub fn browse_file() -> Option<(String, String)> {
-    #[cfg(target_os = "windows")]
-    {
-        if let Ok(userprofile) = std::env::var("USERPROFILE") {
-            let path = PathBuf::from(userprofile)
-                .join("AppData")
-                .join("Local")
-                .join("SB")
-                .join("Saved")
-                .join("SaveGames");
-            if path.exists() {
-                if let Some(selected_path) = rfd::FileDialog::new()
-                    .set_directory(&path)
-                    .add_filter("Save Files", &["sav"])
-                    .add_filter("All Files", &["*"])
-                    .pick_file()
-                {
-                    return Some((
-                        selected_path.to_string_lossy().to_string(),
-                        "File has been selected. Ready!".to_string()
-                    ));
-                }
-            } else {
-                if let Some(selected_path) = rfd::FileDialog::new()
-                    .add_filter("Save Files", &["sav"])
-                    .add_filter("All Files", &["*"])
-                    .pick_file()
-                {
-                    return Some((
-                        selected_path.to_string_lossy().to_string(),
-                        "File has been selected. Ready!".to_string()
-                    ));
-                }
-            }
-        }
+    let mut dialog = rfd::FileDialog::new()
+        .add_filter("Save Files", &["sav"])
+        .add_filter("All Files", &["*"]);
+    if let Some(dir) = slots::find_save_dir() {
+        dialog = dialog.set_directory(dir);
     }
-    #[cfg(not(target_os = "windows"))]
-    {
-        if let Some(selected_path) = rfd::FileDialog::new()
-            .add_filter("Save Files", &["sav"])
-            .add_filter("All Files", &["*"])
-            .pick_file()
-        {
-            return Some((
-                selected_path.to_string_lossy().to_string(),
-                "File has been selected. Ready!".to_string()
-            ));
-        }
-    }
-    None
+
+    dialog.pick_file().map(|selected_path| {
+        (
+            selected_path.to_string_lossy().to_string(),
+            "File has been selected. Ready!".to_string(),
+        )
+    })
 }
 
-pub fn create_backup(file_path: &str) -> Result<String, String> {
-    let backup_path = format!("{}.bak", file_path);
-    
-    std::fs::copy(file_path, &backup_path)
-        .map_err(|e| format!("Failed to create backup: {}", e))?;
-        
-    Ok(backup_path)
+pub fn browse_folder() -> Option<String> {
+    let mut dialog = rfd::FileDialog::new();
+    if let Some(dir) = slots::find_save_dir() {
+        dialog = dialog.set_directory(dir);
+    }
+    dialog.pick_folder().map(|dir| dir.to_string_lossy().to_string())
 }
 
-pub fn handle_demo_transfer(file_path: &str, remove_demo: bool) -> Result<(), String> {
-    let path = std::path::Path::new(file_path);
-    
-    if let Some(filename) = path.file_name() {
-        let filename_str = filename.to_string_lossy();
-        
-        if filename_str.contains("Demo00") {
-            let new_filename = if remove_demo {
-                filename_str.replace("Demo00", "00")  // For remover
-            } else {
-                filename_str.replace("Demo00", "")    // For replacer
-            };
-            
-            if let Some(parent) = path.parent() {
-                let new_path = parent.join(&new_filename);
-                
-                std::fs::rename(file_path, &new_path)
-                    .map_err(|e| format!("Failed to transfer demo save: {}", e))?;
-            }
-        }
+/// Status line shown when a save holds more than one distinct SteamID.
+pub fn inconsistency_warning(groups: &[SteamIdGroup]) -> Option<String> {
+    if occurrences::is_consistent(groups) {
+        return None;
     }
-    
-    Ok(())
+    let ids: Vec<String> = groups
+        .iter()
+        .map(|g| format!("{} ({}x)", g.steamid, g.offsets.len()))
+        .collect();
+    Some(format!(
+        "(!) Warning: save contains {} different SteamIDs: {}",
+        groups.len(),
+        ids.join(", ")
+    ))
 }
 
-pub fn get_backup_filename(backup_path: &str) -> String {
-    std::path::Path::new(backup_path)
-        .file_name()
-        .unwrap_or_else(|| std::ffi::OsStr::new("backup.bak"))
-        .to_string_lossy()
-        .to_string()
-}
\ No newline at end of file
+/// Status line shown after an edit if Steam Cloud still records the old save.
+pub fn cloud_warning(save: &Path) -> Option<String> {
+    let caches = cloud::caches_tracking(save, &steam::installed_steam_dirs(), steam::APP_ID);
+    caches
+        .iter()
+        .any(|cache| matches!(cache.check(save), Ok(CloudStatus::Changed(_))))
+        .then(|| "(!) Steam Cloud still records the old save; update it in the 💾 Slots tab or Steam may restore it".to_string())
+}
@@ -0,0 +1,253 @@
+//! Reader and writer for Valve's text KeyValues format (`.vdf`), as used by
+//! Steam's `loginusers.vdf`, `libraryfolders.vdf` and friends.
+
+use crate::error::{Error, Result};
+use std::path::Path;
+
+/// A KeyValues node: either a string or an ordered list of children.
+/// Keys are compared case-insensitively, like Steam does.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Vdf {
+    Value(String),
+    Object(Vec<(String, Vdf)>),
+}
+
+impl Vdf {
+    pub fn get(&self, key: &str) -> Option<&Vdf> {
+        self.entries()
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, value)| value)
+    }
+
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.get(key).and_then(Vdf::as_str)
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Vdf::Value(value) => Some(value),
+            Vdf::Object(_) => None,
+        }
+    }
+
+    /// Children of an object; empty for a plain value.
+    pub fn entries(&self) -> &[(String, Vdf)] {
+        match self {
+            Vdf::Object(entries) => entries,
+            Vdf::Value(_) => &[],
+        }
+    }
+
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut Vdf> {
+        match self {
+            Vdf::Object(entries) => entries
+                .iter_mut()
+                .find(|(k, _)| k.eq_ignore_ascii_case(key))
+                .map(|(_, value)| value),
+            Vdf::Value(_) => None,
+        }
+    }
+
+    /// Sets `key` to a string, keeping its position if it exists and
+    /// appending it otherwise. Does nothing on a plain value.
+    pub fn set(&mut self, key: &str, value: impl Into<String>) {
+        let value = Vdf::Value(value.into());
+        match self.get_mut(key) {
+            Some(existing) => *existing = value,
+            None => {
+                if let Vdf::Object(entries) = self {
+                    entries.push((key.to_string(), value));
+                }
+            }
+        }
+    }
+}
+
+/// Formats a document produced by [`parse`] the way Steam writes it: tab
+/// indentation, braces on their own lines.
+pub fn to_text(root: &Vdf) -> String {
+    let mut text = String::new();
+    write_entries(&mut text, root.entries(), 0);
+    text
+}
+
+fn write_entries(text: &mut String, entries: &[(String, Vdf)], depth: usize) {
+    let indent = "\t".repeat(depth);
+    for (key, value) in entries {
+        match value {
+            Vdf::Value(value) => {
+                text.push_str(&format!("{}\"{}\"\t\t\"{}\"\n", indent, escape(key), escape(value)));
+            }
+            Vdf::Object(children) => {
+                text.push_str(&format!("{}\"{}\"\n{}{{\n", indent, escape(key), indent));
+                write_entries(text, children, depth + 1);
+                text.push_str(&format!("{}}}\n", indent));
+            }
+        }
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Parses a whole document into an object holding its top-level keys.
+pub fn parse(text: &str) -> Result<Vdf> {
+    let mut parser = Parser {
+        chars: text.chars().collect(),
+        pos: 0,
+        line: 1,
+    };
+    let entries = parser.entries(false)?;
+    Ok(Vdf::Object(entries))
+}
+
+pub fn read(path: &Path) -> Result<Vdf> {
+    let text = std::fs::read_to_string(path).map_err(Error::io("Failed to read VDF file"))?;
+    parse(&text)
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    line: usize,
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Str(String),
+    Open,
+    Close,
+}
+
+impl Parser {
+    fn entries(&mut self, nested: bool) -> Result<Vec<(String, Vdf)>> {
+        let mut entries = Vec::new();
+
+        loop {
+            let key = match self.token()? {
+                Some(Token::Str(key)) => key,
+                Some(Token::Close) if nested => return Ok(entries),
+                None if !nested => return Ok(entries),
+                None => return Err(self.error("unexpected end of file, missing '}'")),
+                Some(_) => return Err(self.error("expected a key")),
+            };
+
+            let value = match self.token()? {
+                Some(Token::Str(value)) => Vdf::Value(value),
+                Some(Token::Open) => Vdf::Object(self.entries(true)?),
+                _ => return Err(self.error(&format!("expected a value for '{}'", key))),
+            };
+            self.skip_condition();
+            entries.push((key, value));
+        }
+    }
+
+    fn token(&mut self) -> Result<Option<Token>> {
+        self.skip_trivia();
+        let Some(&c) = self.chars.get(self.pos) else {
+            return Ok(None);
+        };
+
+        match c {
+            '{' => {
+                self.pos += 1;
+                Ok(Some(Token::Open))
+            }
+            '}' => {
+                self.pos += 1;
+                Ok(Some(Token::Close))
+            }
+            '"' => self.quoted().map(|s| Some(Token::Str(s))),
+            _ => Ok(Some(Token::Str(self.unquoted()))),
+        }
+    }
+
+    fn quoted(&mut self) -> Result<String> {
+        self.pos += 1;
+        let mut value = String::new();
+
+        while let Some(&c) = self.chars.get(self.pos) {
+            self.pos += 1;
+            match c {
+                '"' => return Ok(value),
+                '\\' => {
+                    let escaped = self.chars.get(self.pos).copied();
+                    self.pos += 1;
+                    match escaped {
+                        Some('n') => value.push('\n'),
+                        Some('t') => value.push('\t'),
+                        Some('\\') => value.push('\\'),
+                        Some('"') => value.push('"'),
+                        // Windows paths in older files are not escaped.
+                        Some(other) => {
+                            value.push('\\');
+                            value.push(other);
+                        }
+                        None => break,
+                    }
+                }
+                '\n' => {
+                    self.line += 1;
+                    value.push(c);
+                }
+                _ => value.push(c),
+            }
+        }
+
+        Err(self.error("unterminated string"))
+    }
+
+    fn unquoted(&mut self) -> String {
+        let start = self.pos;
+        while let Some(&c) = self.chars.get(self.pos) {
+            if c.is_whitespace() || matches!(c, '{' | '}' | '"') {
+                break;
+            }
+            self.pos += 1;
+        }
+        self.chars[start..self.pos].iter().collect()
+    }
+
+    /// Skips a platform conditional such as `[$WIN32]` after a value.
+    fn skip_condition(&mut self) {
+        let mut pos = self.pos;
+        while self.chars.get(pos).is_some_and(|c| *c == ' ' || *c == '\t') {
+            pos += 1;
+        }
+        if self.chars.get(pos) == Some(&'[') {
+            while let Some(&c) = self.chars.get(pos) {
+                pos += 1;
+                if c == ']' || c == '\n' {
+                    break;
+                }
+            }
+            self.pos = pos;
+        }
+    }
+
+    fn skip_trivia(&mut self) {
+        while let Some(&c) = self.chars.get(self.pos) {
+            if c == '\n' {
+                self.line += 1;
+                self.pos += 1;
+            } else if c.is_whitespace() {
+                self.pos += 1;
+            } else if c == '/' && self.chars.get(self.pos + 1) == Some(&'/') {
+                while self.chars.get(self.pos).is_some_and(|c| *c != '\n') {
+                    self.pos += 1;
+                }
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn error(&self, reason: &str) -> Error {
+        Error::InvalidVdf {
+            line: self.line,
+            reason: reason.to_string(),
+        }
+    }
+}
@@ -0,0 +1,116 @@
+mod common;
+
+use common::{copy_fixture, read_fixture, scratch_dir};
+use steamid_replacer::anonymize::{self, AnonymizeOptions, IdentifierKind, Identifiers};
+use steamid_replacer::backup::BackupStore;
+use steamid_replacer::gvas::SaveGame;
+use steamid_replacer::manifest::{self, Operation};
+use steamid_replacer::steamid::{self, SteamId};
+
+fn identifiers() -> Identifiers {
+    let mut identifiers = Identifiers {
+        steamids: vec![SteamId::from_steamid64(76561198000000001).unwrap()],
+        ..Default::default()
+    };
+    identifiers.add(IdentifierKind::AccountName, "deck");
+    identifiers.add(IdentifierKind::PersonaName, "Zoë");
+    identifiers
+}
+
+fn string(save: &SaveGame, path: &str) -> String {
+    save.get(path).unwrap().as_str().unwrap().to_string()
+}
+
+#[test]
+fn names_are_only_scrubbed_inside_string_values() {
+    let mut data = read_fixture("named.sav");
+    let scrubbed = anonymize::anonymize(&mut data, &identifiers());
+    assert!(!scrubbed.is_empty());
+
+    let save = SaveGame::parse(&data).unwrap();
+    assert!(save.get("Deck").is_some());
+    assert_eq!(string(&save, "PlayerName"), "XXX");
+    assert_eq!(string(&save, "Note"), "XXXX played as [U:1:10000001] in /home/XXXX");
+    assert_eq!(string(&save, "WideNote"), "XXX ★ STEAM_0:1:10000000");
+    assert_eq!(string(&save, "OwnerSteamId"), "76561197960265729");
+    assert_eq!(data.len(), read_fixture("named.sav").len());
+}
+
+#[test]
+fn steamid_text_placeholders_are_valid() {
+    let mut data = read_fixture("named.sav");
+    let scrubbed = anonymize::anonymize(&mut data, &identifiers());
+
+    let texts: Vec<_> = scrubbed.iter().filter(|s| s.kind == IdentifierKind::SteamIdText).collect();
+    assert_eq!(texts.len(), 2);
+    for text in texts {
+        assert_eq!(text.placeholder.len(), text.original.len());
+        let placeholder = steamid::parse(&text.placeholder).unwrap();
+        assert_ne!(placeholder, steamid::parse(&text.original).unwrap());
+    }
+}
+
+#[test]
+fn copies_are_validated_and_recorded() {
+    let dir = scratch_dir("anonymize_copy");
+    let save = copy_fixture("named.sav", &dir);
+    let output = dir.join("shared.sav");
+    let options = AnonymizeOptions {
+        identifiers: identifiers(),
+        output: Some(output.clone()),
+        ..Default::default()
+    };
+
+    let report = anonymize::anonymize_file(&save, &options).unwrap();
+    assert_eq!(report.final_path, output);
+    assert_eq!(report.backup_path, None);
+    assert_eq!(std::fs::read(&save).unwrap(), read_fixture("named.sav"));
+    assert!(SaveGame::parse(&std::fs::read(&output).unwrap()).is_ok());
+
+    let history = manifest::history(&BackupStore::default(), &output).unwrap();
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].operation, Operation::Anonymize);
+    assert_eq!(history[0].file, save);
+    assert_eq!(history[0].transferred_to.as_ref(), Some(&output));
+
+    // Overwriting the copy backs up the one it replaces.
+    let options = AnonymizeOptions { overwrite: true, ..options };
+    let report = anonymize::anonymize_file(&save, &options).unwrap();
+    assert!(report.backup_path.is_some_and(|backup| backup.exists()));
+}
+
+fn count(data: &[u8], needle: &[u8]) -> usize {
+    data.windows(needle.len()).filter(|window| window.eq_ignore_ascii_case(needle)).count()
+}
+
+fn wide(text: &str) -> Vec<u8> {
+    text.encode_utf16().flat_map(u16::to_le_bytes).collect()
+}
+
+#[test]
+fn container_elements_are_scrubbed() {
+    let original = read_fixture("named.sav");
+    assert_eq!(count(&original, b"deck"), 5);
+    let mut data = original.clone();
+    anonymize::anonymize(&mut data, &identifiers());
+
+    // Only the `Deck` property name is left.
+    assert_eq!(count(&data, b"deck"), 1);
+    assert_eq!(count(&data, &wide("Zoë")), 0);
+    assert!(SaveGame::parse_lossless(&data).is_ok());
+}
+
+#[test]
+fn wide_matches_start_on_a_character() {
+    // Without its magic the save does not parse and the whole file is searched.
+    let mut data = read_fixture("named.sav");
+    data[..4].copy_from_slice(b"XXXX");
+    let cjk = wide("\u{6400}\u{6500}\u{6300}\u{6B00}");
+    // Its UTF-16 bytes read `deck` from the second byte on.
+    assert_eq!(count(&data, &wide("deck")), 1);
+
+    let scrubbed = anonymize::anonymize(&mut data, &identifiers());
+    assert_eq!(count(&data, &cjk), 1);
+    assert!(!scrubbed.iter().any(|s| s.wide && s.original.eq_ignore_ascii_case("deck")));
+    assert_eq!(count(&data, &wide("Zoë")), 0);
+}
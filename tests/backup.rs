@@ -0,0 +1,62 @@
+mod common;
+
+use common::{copy_fixture, scratch_dir};
+use steamid_replacer::backup::{BackupStore, Retention};
+
+#[test]
+fn identical_backups_keep_their_name() {
+    let dir = scratch_dir("backup_identical");
+    let save = copy_fixture("owned.sav", &dir);
+    let store = BackupStore::default();
+
+    let first = store.create(&save).unwrap();
+    let second = store.create(&save).unwrap();
+    assert_eq!(first, second);
+    assert!(first.exists());
+    assert_eq!(store.list(&save).unwrap().len(), 1);
+}
+
+#[test]
+fn shared_dir_keeps_same_named_saves_apart() {
+    let dir = scratch_dir("backup_shared_dir");
+    let (a, b) = (dir.join("a"), dir.join("b"));
+    std::fs::create_dir_all(&a).unwrap();
+    std::fs::create_dir_all(&b).unwrap();
+    let save_a = a.join("SBSave0001.sav");
+    let save_b = b.join("SBSave0001.sav");
+    std::fs::copy(common::fixture("owned.sav"), &save_a).unwrap();
+    std::fs::copy(common::fixture("two_owners.sav"), &save_b).unwrap();
+    let store = BackupStore {
+        dir: Some(dir.join("backups")),
+        retention: Retention {
+            keep_last: Some(1),
+            max_age_days: None,
+        },
+    };
+    assert_ne!(store.source_name(&save_a), store.source_name(&save_b));
+
+    let backup_b = store.create(&save_b).unwrap();
+    store.create(&save_a).unwrap();
+    std::fs::copy(common::fixture("universal.sav"), &save_a).unwrap();
+    let backup_a = store.create(&save_a).unwrap();
+
+    assert!(backup_b.exists());
+    let listed_a: Vec<_> = store.list(&save_a).unwrap().into_iter().map(|entry| entry.path).collect();
+    let listed_b: Vec<_> = store.list(&save_b).unwrap().into_iter().map(|entry| entry.path).collect();
+    assert_eq!(listed_a, [backup_a]);
+    assert_eq!(listed_b, [backup_b]);
+}
+
+#[test]
+fn default_dir_names_have_no_folder_id() {
+    let dir = scratch_dir("backup_default_dir");
+    let save = copy_fixture("owned.sav", &dir);
+    let store = BackupStore::default();
+
+    assert_eq!(store.source_name(&save), "owned.sav");
+    let entry = store.list(&save).unwrap().into_iter().next();
+    assert!(entry.is_none());
+    let backup = store.create(&save).unwrap();
+    assert_eq!(backup.parent().unwrap(), dir.join("SteamIDBackups"));
+    assert!(backup.file_name().unwrap().to_string_lossy().starts_with("owned.sav."));
+}
@@ -0,0 +1,42 @@
+mod common;
+
+use common::{copy_fixture, scratch_dir};
+use std::sync::Mutex;
+use steamid_replacer::batch::{self, BatchOperation, BatchOptions, BatchSummary};
+
+#[test]
+fn progress_counts_every_finished_save() {
+    let dir = scratch_dir("batch_progress");
+    let saves: Vec<_> = ["owned.sav", "two_owners.sav", "universal.sav"]
+        .iter()
+        .map(|name| copy_fixture(name, &dir))
+        .collect();
+    let options = BatchOptions {
+        operation: BatchOperation::Remove,
+        backup: None,
+        dry_run: true,
+        jobs: Some(2),
+    };
+
+    let seen = Mutex::new(Vec::new());
+    let results = batch::run_with_progress(&saves, &options, |done| seen.lock().unwrap().push(done));
+    let mut seen = seen.into_inner().unwrap();
+    seen.sort();
+    assert_eq!(seen, [1, 2, 3]);
+
+    let summary = BatchSummary::of(&results);
+    assert_eq!(summary.total(), 3);
+    assert_eq!(summary.already_universal, 1);
+    assert_eq!(results[2].path, saves[2]);
+}
+
+#[test]
+fn each_save_is_collected_once() {
+    let dir = scratch_dir("batch_collect");
+    let owned = copy_fixture("owned.sav", &dir);
+    let other = copy_fixture("two_owners.sav", &dir);
+    let dotted = dir.join(".").join("owned.sav");
+
+    let saves = batch::collect_saves(&[owned.clone(), other.clone(), dotted, dir.clone(), owned.clone()]).unwrap();
+    assert_eq!(saves, [owned.canonicalize().unwrap(), other.canonicalize().unwrap()]);
+}
@@ -0,0 +1,81 @@
+mod common;
+
+use common::{copy_fixture, fixture, scratch_dir};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, UNIX_EPOCH};
+use steamid_replacer::backup::BackupStore;
+use steamid_replacer::cloud::{self, CloudStatus, Mismatch, RemoteCache};
+use steamid_replacer::steam;
+
+const CACHE: &str = "steam/userdata/39734273/3489700/remotecache.vdf";
+
+/// A Steam folder holding a copy of the fixture cache, and a save folder.
+fn setup(test: &str) -> (PathBuf, PathBuf) {
+    let dir = scratch_dir(test);
+    let cache = dir.join(CACHE);
+    std::fs::create_dir_all(cache.parent().unwrap()).unwrap();
+    std::fs::copy(fixture(CACHE), &cache).unwrap();
+    let saves = dir.join("SaveGames");
+    std::fs::create_dir_all(&saves).unwrap();
+    (dir.join("steam"), saves)
+}
+
+fn set_time(path: &Path, secs: u64) {
+    let file = std::fs::File::options().write(true).open(path).unwrap();
+    file.set_modified(UNIX_EPOCH + Duration::from_secs(secs)).unwrap();
+}
+
+#[test]
+fn check_compares_size_hash_and_time() {
+    let (steam_dir, saves) = setup("cloud_check");
+    let owned = copy_fixture("owned.sav", &saves);
+    let two_owners = copy_fixture("two_owners.sav", &saves);
+    let untracked = copy_fixture("universal.sav", &saves);
+    set_time(&owned, 1760000000);
+    set_time(&two_owners, 1760000000);
+
+    let caches = cloud::caches_tracking(&owned, &[steam_dir], steam::APP_ID);
+    assert_eq!(caches.len(), 1);
+    let cache = &caches[0];
+    assert_eq!(cache.account_id, Some(39734273));
+    assert_eq!(cache.entries().len(), 2);
+
+    assert_eq!(cache.check(&owned).unwrap(), CloudStatus::InSync);
+    assert_eq!(cache.check(&untracked).unwrap(), CloudStatus::Untracked);
+    let CloudStatus::Changed(mismatches) = cache.check(&two_owners).unwrap() else {
+        panic!("two_owners.sav should differ from its record");
+    };
+    assert_eq!(mismatches.len(), 2);
+    assert_eq!(mismatches[0], Mismatch::Size { recorded: 3155, actual: 3216 });
+    assert!(matches!(mismatches[1], Mismatch::Hash { .. }));
+
+    set_time(&owned, 1760000100);
+    assert_eq!(
+        cache.check(&owned).unwrap(),
+        CloudStatus::Changed(vec![Mismatch::Time { recorded: 1760000000, actual: 1760000100 }])
+    );
+}
+
+#[test]
+fn update_records_the_save_and_backs_up_outside_userdata() {
+    let (steam_dir, saves) = setup("cloud_update");
+    let save = copy_fixture("two_owners.sav", &saves);
+    let cache_path = steam_dir.join("userdata/39734273/3489700/remotecache.vdf");
+    let original = std::fs::read(&cache_path).unwrap();
+
+    let mut cache = RemoteCache::load(&cache_path).unwrap();
+    assert!(cache.update(&save).unwrap());
+    assert!(!cache.update(&saves.join("missing.sav")).unwrap());
+    let backup = cache.save(&save, Some(&BackupStore::default())).unwrap().unwrap();
+
+    assert_eq!(backup.parent().unwrap(), saves.join("SteamIDBackups"));
+    assert_eq!(std::fs::read(&backup).unwrap(), original);
+    assert!(!steam_dir.join("userdata/39734273/3489700/SteamIDBackups").exists());
+
+    let reloaded = RemoteCache::load(&cache_path).unwrap();
+    assert_eq!(reloaded.check(&save).unwrap(), CloudStatus::InSync);
+    let entry = reloaded.entry_for(&save).unwrap();
+    assert_eq!(entry.size, Some(3216));
+    assert_eq!(entry.sha.as_deref(), Some(cloud::sha1_hex(&std::fs::read(&save).unwrap()).as_str()));
+    assert_eq!(reloaded.entry_for(&saves.join("owned.sav")).unwrap().size, Some(3155));
+}
@@ -0,0 +1,41 @@
+//! Helpers shared by the integration tests.
+
+#![allow(dead_code)]
+
+use std::path::{Path, PathBuf};
+
+/// Saves built to cover every property kind the parser knows, plus the
+/// shapes the remover produces.
+pub const PARSEABLE_SAVES: &[&str] = &[
+    "owned.sav",
+    "two_owners.sav",
+    "universal.sav",
+    "raw_enum.sav",
+    "demo.sav",
+    "demo_progress.sav",
+    "named.sav",
+    "static_owner.sav",
+];
+
+pub fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(name)
+}
+
+pub fn read_fixture(name: &str) -> Vec<u8> {
+    std::fs::read(fixture(name)).unwrap()
+}
+
+/// An empty directory for one test to write into.
+pub fn scratch_dir(test: &str) -> PathBuf {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join(test);
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Copies the fixture `name` into `dir` and returns the copy's path.
+pub fn copy_fixture(name: &str, dir: &Path) -> PathBuf {
+    let path = dir.join(name);
+    std::fs::copy(fixture(name), &path).unwrap();
+    path
+}
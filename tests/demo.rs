@@ -0,0 +1,26 @@
+mod common;
+
+use common::{fixture, read_fixture};
+use std::path::Path;
+use steamid_replacer::demo::{self, DemoEvidence};
+
+#[test]
+fn demo_save_class_is_detected() {
+    let evidence = demo::detect(&fixture("demo.sav"), &read_fixture("demo.sav"));
+    assert_eq!(evidence, Some(DemoEvidence::SaveClass("/Script/SB.SBDemoSaveGame".to_string())));
+}
+
+#[test]
+fn full_game_saves_are_not_demos() {
+    for name in ["owned.sav", "demo_progress.sav"] {
+        assert_eq!(demo::detect(&fixture(name), &read_fixture(name)), None, "{}", name);
+    }
+}
+
+#[test]
+fn file_name_is_the_fallback() {
+    let data = read_fixture("owned.sav");
+    assert_eq!(demo::detect(Path::new("SBSaveDemo00.sav"), &data), Some(DemoEvidence::FileName));
+    assert_eq!(demo::detect(Path::new("SBSaveDemo00.sav"), b"not a save"), Some(DemoEvidence::FileName));
+    assert_eq!(demo::detect(Path::new("SBSave0001.sav"), b"not a save"), None);
+}
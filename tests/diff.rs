@@ -0,0 +1,63 @@
+use steamid_replacer::diff::{self, DiffSummary, Hunk};
+
+fn hunk(old_offset: usize, old_len: usize, new_offset: usize, new_len: usize) -> Hunk {
+    Hunk {
+        old_offset,
+        old_len,
+        new_offset,
+        new_len,
+    }
+}
+
+#[test]
+fn equal_inputs_have_no_hunks() {
+    let data = b"GVAS and some save data";
+    let result = diff::diff(data, data);
+    assert!(result.is_identical());
+    assert_eq!(result.summary(), DiffSummary::default());
+    assert!(diff::diff(b"", b"").is_identical());
+}
+
+#[test]
+fn insertion_is_one_hunk() {
+    let result = diff::diff(b"owner: ;", b"owner: 76561198000000001;");
+    assert_eq!(result.hunks, vec![hunk(7, 0, 7, 17)]);
+    let summary = result.summary();
+    assert_eq!((summary.hunks, summary.changed, summary.added, summary.removed), (1, 0, 17, 0));
+}
+
+#[test]
+fn deletion_is_one_hunk() {
+    let result = diff::diff(b"owner: 76561198000000001;", b"owner: ;");
+    assert_eq!(result.hunks, vec![hunk(7, 17, 7, 0)]);
+    let summary = result.summary();
+    assert_eq!((summary.hunks, summary.changed, summary.added, summary.removed), (1, 0, 0, 17));
+}
+
+#[test]
+fn replacement_of_the_same_length_is_changed_in_place() {
+    let result = diff::diff(b"owner: 76561198000000001;", b"owner: 76561198999999999;");
+    assert_eq!(result.hunks, vec![hunk(15, 9, 15, 9)]);
+    let summary = result.summary();
+    assert_eq!((summary.hunks, summary.changed, summary.added, summary.removed), (1, 9, 0, 0));
+}
+
+#[test]
+fn separate_edits_are_separate_hunks() {
+    let result = diff::diff(b"a-bcdefg-h", b"abcdXefgYYh");
+    assert_eq!(result.hunks, vec![hunk(1, 1, 1, 0), hunk(5, 0, 4, 1), hunk(8, 1, 8, 2)]);
+}
+
+#[test]
+fn edits_beyond_the_distance_limit_are_one_hunk() {
+    let mut old = b"head".to_vec();
+    old.extend(std::iter::repeat_n(0x00, 3000));
+    old.extend(b"tail");
+    let mut new = b"head".to_vec();
+    new.extend(std::iter::repeat_n(0xFF, 2500));
+    new.extend(b"tail");
+
+    let result = diff::diff(&old, &new);
+    assert_eq!(result.hunks, vec![hunk(4, 3000, 4, 2500)]);
+    assert_eq!((result.old_len, result.new_len), (3008, 2508));
+}
@@ -0,0 +1,41 @@
+mod common;
+
+use common::read_fixture;
+use steamid_replacer::Error;
+use steamid_replacer::edit::{self, ValueEdit};
+use steamid_replacer::gvas::SaveGame;
+
+fn set(path: &str, value: &str) -> Result<Vec<u8>, Error> {
+    let edits = [ValueEdit {
+        path: path.to_string(),
+        value: value.to_string(),
+    }];
+    edit::apply(&read_fixture("owned.sav"), &edits).map(|(data, _)| data)
+}
+
+fn reason(result: Result<Vec<u8>, Error>) -> String {
+    match result {
+        Err(Error::InvalidValue { reason, .. }) => reason,
+        other => panic!("expected an invalid value, got {:?}", other.map(|data| data.len())),
+    }
+}
+
+#[test]
+fn floats_must_be_finite() {
+    for text in ["NaN", "inf", "-inf", "infinity"] {
+        assert_eq!(reason(set("Health", text)), "expected a finite number");
+    }
+    let data = set("Health", "12.25").unwrap();
+    let save = SaveGame::parse_lossless(&data).unwrap();
+    assert_eq!(edit::scalar_text(save.get("Health").unwrap()).as_deref(), Some("12.25"));
+}
+
+#[test]
+fn enum_labels_need_their_enum_prefix() {
+    for text in ["", "Easy", "EOther::Easy", "EDiff::", "EDiff::Very Easy"] {
+        assert_eq!(reason(set("Diff", text)), "expected a label of the form EDiff::Name");
+    }
+    let data = set("Diff", "EDiff::Easy").unwrap();
+    let save = SaveGame::parse_lossless(&data).unwrap();
+    assert_eq!(edit::scalar_text(save.get("Diff").unwrap()).as_deref(), Some("EDiff::Easy"));
+}
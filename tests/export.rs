@@ -0,0 +1,58 @@
+mod common;
+
+use common::{PARSEABLE_SAVES, read_fixture};
+use serde_json::{Value, json};
+use steamid_replacer::Error;
+use steamid_replacer::edit;
+use steamid_replacer::export;
+use steamid_replacer::gvas::SaveGame;
+
+/// The exported `owned.sav` with the value of the top-level property `name`
+/// replaced, as a text editor would leave it.
+fn edited(name: &str, value: Value) -> String {
+    let mut document: Value = serde_json::from_str(&export::export(&read_fixture("owned.sav")).unwrap()).unwrap();
+    let property = document["properties"]
+        .as_array_mut()
+        .unwrap()
+        .iter_mut()
+        .find(|property| property["name"] == name)
+        .unwrap();
+    property["value"] = value;
+    serde_json::to_string_pretty(&document).unwrap()
+}
+
+#[test]
+fn exports_import_to_the_same_bytes() {
+    for name in PARSEABLE_SAVES {
+        let data = read_fixture(name);
+        let text = export::export(&data).unwrap_or_else(|e| panic!("{}: {}", name, e));
+        assert!(export::import(&text).unwrap() == data, "{} changed through JSON", name);
+    }
+}
+
+#[test]
+fn edited_values_are_imported() {
+    let data = export::import(&edited("Level", json!(7))).unwrap();
+    let original = read_fixture("owned.sav");
+    assert_eq!(data.len(), original.len());
+    let save = SaveGame::parse_lossless(&data).unwrap();
+    assert_eq!(edit::scalar_text(save.get("Level").unwrap()).as_deref(), Some("7"));
+    assert_eq!(edit::scalar_text(save.get("Health").unwrap()).as_deref(), Some("99.5"));
+
+    // A longer string changes the sizes, which are recomputed.
+    let data = export::import(&edited("PlayerName", json!("Someone Else"))).unwrap();
+    assert_eq!(data.len(), original.len() + "Someone Else".len() - "Persona".len());
+    let save = SaveGame::parse_lossless(&data).unwrap();
+    assert_eq!(edit::scalar_text(save.get("PlayerName").unwrap()).as_deref(), Some("Someone Else"));
+    assert_eq!(edit::scalar_text(save.get("Level").unwrap()).as_deref(), Some("42"));
+}
+
+#[test]
+fn values_of_the_wrong_type_are_rejected() {
+    for value in [json!("seven"), json!(1.5), json!(i64::MAX)] {
+        assert!(matches!(
+            export::import(&edited("Level", value)),
+            Err(Error::InvalidSaveJson(_))
+        ));
+    }
+}
@@ -0,0 +1,75 @@
+mod common;
+
+use common::{PARSEABLE_SAVES, read_fixture};
+use steamid_replacer::gvas::{FString, PropertyValue, SaveGame, Value};
+use steamid_replacer::remove;
+
+#[test]
+fn unedited_saves_round_trip_byte_for_byte() {
+    for name in PARSEABLE_SAVES {
+        let data = read_fixture(name);
+        let save = SaveGame::parse(&data).unwrap_or_else(|e| panic!("{}: {}", name, e));
+        assert!(save.to_bytes() == data, "{} does not round-trip", name);
+        assert!(SaveGame::parse_lossless(&data).is_ok(), "{}", name);
+    }
+}
+
+#[test]
+fn unparsed_enum_bodies_keep_their_tag() {
+    let data = read_fixture("raw_enum.sav");
+    let save = SaveGame::parse_lossless(&data).unwrap();
+
+    for (name, expected) in [("RawByte", "EFoo"), ("RawEnum", "EBar")] {
+        match &save.get(name).unwrap().value {
+            PropertyValue::RawEnum { enum_name, .. } => assert_eq!(enum_name.as_str(), expected),
+            other => panic!("{}: {:?}", name, other),
+        }
+    }
+}
+
+#[test]
+fn edits_recompute_enclosing_sizes() {
+    let data = read_fixture("owned.sav");
+    let mut save = SaveGame::parse(&data).unwrap();
+    let name = "A much longer player name than before";
+    save.get_mut("Info.Name").unwrap().value = PropertyValue::Simple(Value::Str(FString::new(name)));
+    save.get_mut("Items[1].Label").unwrap().value = PropertyValue::Simple(Value::Str(FString::new("Ünïcode ✓")));
+
+    let written = save.to_bytes();
+    let reparsed = SaveGame::parse_lossless(&written).unwrap();
+    assert_eq!(reparsed.get("Info.Name").unwrap().as_str(), Some(name));
+    assert_eq!(reparsed.get("Items[1].Label").unwrap().as_str(), Some("Ünïcode ✓"));
+    assert_eq!(reparsed.get("Level").unwrap().body_offset(), save.get("Level").unwrap().body_offset());
+    assert_eq!(reparsed.walk().len(), save.walk().len());
+}
+
+#[test]
+fn wide_strings_are_sized_as_written() {
+    let narrow_flag_but_wide_text = FString {
+        value: Some("✓".to_string()),
+        wide: false,
+    };
+    assert_eq!(narrow_flag_but_wide_text.serialized_len(), 4 + 2 * 2);
+    assert_eq!(FString::new("abc").serialized_len(), 4 + 4);
+    assert_eq!(FString::default().serialized_len(), 4);
+}
+
+#[test]
+fn universal_saves_parse_again() {
+    for name in ["owned.sav", "two_owners.sav"] {
+        let mut data = read_fixture(name);
+        let removed = remove::make_universal(&mut data, None).unwrap();
+        assert!(!removed.is_empty(), "{}", name);
+
+        let save = SaveGame::parse_lossless(&data).unwrap_or_else(|e| panic!("{}: {}", name, e));
+        for owner in &removed {
+            let property = save.get(owner.path.as_deref().unwrap()).unwrap();
+            assert!(matches!(&property.value, PropertyValue::Simple(Value::Str(value)) if value.is_null()));
+        }
+        assert!(remove::is_universal(&data), "{}", name);
+    }
+
+    let mut data = read_fixture("owned.sav");
+    remove::make_universal(&mut data, None).unwrap();
+    assert!(data == read_fixture("universal.sav"));
+}
@@ -0,0 +1,144 @@
+mod common;
+
+use common::{copy_fixture, read_fixture, scratch_dir};
+use steamid_replacer::Error;
+use steamid_replacer::backup::BackupStore;
+use steamid_replacer::manifest::{self, Change, Operation};
+use steamid_replacer::transaction::{self, Plan};
+
+fn change() -> Change {
+    Change {
+        operation: Operation::Remove,
+        new_steamid: None,
+        edits: Vec::new(),
+    }
+}
+
+#[test]
+fn shared_dir_keeps_same_named_histories_apart() {
+    let dir = scratch_dir("manifest_shared");
+    let store = BackupStore {
+        dir: Some(dir.join("backups")),
+        ..Default::default()
+    };
+    for folder in ["a", "b"] {
+        std::fs::create_dir_all(dir.join(folder)).unwrap();
+    }
+    let first = copy_fixture("owned.sav", &dir.join("a"));
+    let second = copy_fixture("owned.sav", &dir.join("b"));
+    assert_ne!(manifest::manifest_path(&store, &first), manifest::manifest_path(&store, &second));
+
+    let original = read_fixture("owned.sav");
+    let edited = read_fixture("universal.sav");
+    let plan = Plan {
+        backup: Some(&store),
+        record: Some(change()),
+        ..Default::default()
+    };
+    std::thread::scope(|scope| {
+        for save in [&first, &second] {
+            scope.spawn(|| transaction::commit(save, &original, &edited, &plan).unwrap());
+        }
+    });
+
+    for save in [&first, &second] {
+        let history = manifest::history(&store, save).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(&history[0].file, save);
+    }
+}
+
+#[test]
+fn failed_validation_writes_nothing() {
+    let dir = scratch_dir("manifest_validation_fails");
+    let save = copy_fixture("owned.sav", &dir);
+    let store = BackupStore {
+        dir: Some(dir.join("backups")),
+        ..Default::default()
+    };
+    let original = read_fixture("owned.sav");
+    let truncated = &original[..original.len() / 2];
+    let copy = dir.join("copy.sav");
+
+    for rename_to in [None, Some(copy.clone())] {
+        let plan = Plan {
+            backup: Some(&store),
+            keep_source: rename_to.is_some(),
+            rename_to,
+            record: Some(change()),
+            ..Default::default()
+        };
+        match transaction::commit(&save, &original, truncated, &plan) {
+            Err(Error::InvalidSave { reason, .. }) => assert!(reason.starts_with("edited save no longer parses")),
+            other => panic!("expected an invalid save, got {:?}", other.map(|committed| committed.final_path)),
+        }
+    }
+    assert_eq!(std::fs::read(&save).unwrap(), original);
+    assert!(!copy.exists());
+    assert!(!dir.join("backups").exists());
+    assert!(manifest::history(&store, &save).unwrap().is_empty());
+    assert!(!manifest::manifest_path(&store, &save).exists());
+}
+
+#[test]
+fn failed_record_leaves_the_save_unchanged() {
+    let dir = scratch_dir("manifest_record_fails");
+    let save = copy_fixture("owned.sav", &dir);
+    let store = BackupStore::default();
+    std::fs::create_dir_all(manifest::manifest_path(&store, &save)).unwrap();
+
+    let original = read_fixture("owned.sav");
+    let plan = Plan {
+        record: Some(change()),
+        ..Default::default()
+    };
+    assert!(transaction::commit(&save, &original, &read_fixture("universal.sav"), &plan).is_err());
+    assert_eq!(std::fs::read(&save).unwrap(), original);
+}
+
+#[test]
+fn failed_rename_drops_the_recorded_entry() {
+    let dir = scratch_dir("manifest_rename_fails");
+    let save = copy_fixture("owned.sav", &dir);
+    let store = BackupStore {
+        dir: Some(dir.join("backups")),
+        ..Default::default()
+    };
+    // A target inside a file, which cannot be created.
+    std::fs::write(dir.join("file"), b"").unwrap();
+    let target = dir.join("file/SBSave0001.sav");
+
+    let original = read_fixture("owned.sav");
+    let plan = Plan {
+        backup: Some(&store),
+        rename_to: Some(target.clone()),
+        record: Some(change()),
+        ..Default::default()
+    };
+    assert!(transaction::commit(&save, &original, &read_fixture("universal.sav"), &plan).is_err());
+    assert_eq!(std::fs::read(&save).unwrap(), original);
+    assert!(manifest::manifest_path(&store, &target).exists());
+    assert!(manifest::history(&store, &target).unwrap().is_empty());
+}
+
+#[test]
+fn history_is_replaced_without_leftover_files() {
+    let dir = scratch_dir("manifest_atomic");
+    let save = copy_fixture("owned.sav", &dir);
+    let store = BackupStore::default();
+    let plan = Plan {
+        record: Some(change()),
+        ..Default::default()
+    };
+    let original = read_fixture("owned.sav");
+    let edited = read_fixture("universal.sav");
+    transaction::commit(&save, &original, &edited, &plan).unwrap();
+    transaction::commit(&save, &edited, &original, &plan).unwrap();
+
+    assert_eq!(manifest::history(&store, &save).unwrap().len(), 2);
+    let names: Vec<_> = std::fs::read_dir(store.dir_for(&save))
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+        .collect();
+    assert_eq!(names, ["owned.sav.history.json"]);
+}
@@ -0,0 +1,39 @@
+mod common;
+
+use common::read_fixture;
+use steamid_replacer::Error;
+use steamid_replacer::patch::{self, Patch, PatchEdit};
+
+#[test]
+fn edits_past_the_end_are_rejected() {
+    let data = read_fixture("owned.sav");
+    assert!(PatchEdit::at(&data, data.len() - 1, vec![0, 0]).is_err());
+    assert!(matches!(PatchEdit::at(&data, usize::MAX, vec![0]), Err(Error::InvalidPatch(_))));
+
+    let patch = Patch {
+        description: None,
+        edits: vec![PatchEdit {
+            offset: usize::MAX,
+            old: vec![0],
+            new: vec![1],
+        }],
+    };
+    assert!(matches!(patch::apply(&data, &patch), Err(Error::InvalidPatch(_))));
+}
+
+#[test]
+fn edits_apply_in_order() {
+    let data = read_fixture("owned.sav");
+    let first = PatchEdit::at(&data, 0, b"XXXX".to_vec()).unwrap();
+    assert_eq!(first.old, b"GVAS");
+    let second = PatchEdit {
+        offset: 1,
+        old: b"XX".to_vec(),
+        new: b"VA".to_vec(),
+    };
+    let patch = Patch {
+        description: None,
+        edits: vec![first, second],
+    };
+    assert_eq!(&patch::apply(&data, &patch).unwrap()[..4], b"XVAX");
+}
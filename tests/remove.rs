@@ -0,0 +1,102 @@
+mod common;
+
+use common::{copy_fixture, read_fixture, scratch_dir};
+use steamid_replacer::gvas::SaveGame;
+use steamid_replacer::{manifest, personalize, remove};
+
+#[test]
+fn only_owner_properties_are_emptied() {
+    let mut data = read_fixture("two_owners.sav");
+    let removed = remove::make_universal(&mut data, None).unwrap();
+
+    let paths: Vec<_> = removed.iter().map(|owner| owner.path.as_deref().unwrap()).collect();
+    assert_eq!(paths, ["OwnerSteamId"]);
+    let save = SaveGame::parse_lossless(&data).unwrap();
+    assert_eq!(save.get("LastOwner").unwrap().as_str(), Some("76561198555555555"));
+}
+
+#[test]
+fn unparsed_saves_get_the_legacy_pattern() {
+    // Without its magic the save no longer parses, so only the byte pattern
+    // search can find the owner.
+    let mut original = read_fixture("owned.sav");
+    original[..4].copy_from_slice(b"XXXX");
+    let mut data = original.clone();
+
+    let removed = remove::make_universal(&mut data, None).unwrap();
+    assert_eq!(removed.len(), 1);
+    assert!(removed[0].path.is_none());
+    assert!(remove::is_universal(&data));
+    assert_eq!(data.len(), original.len() - 12);
+
+    personalize::personalize(&mut data, "76561198000000001", None).unwrap();
+    assert!(data == original);
+}
+
+#[test]
+fn legacy_universal_saves_parse_once_personalized() {
+    let legacy = read_fixture("legacy_universal.sav");
+    assert!(SaveGame::parse(&legacy).is_err());
+    assert!(remove::is_universal(&legacy));
+
+    let mut data = legacy.clone();
+    personalize::personalize(&mut data, "76561198000000001", None).unwrap();
+    let save = SaveGame::parse_lossless(&data).unwrap();
+    assert_eq!(save.get("OwnerSteamId").unwrap().as_str(), Some("76561198000000001"));
+
+    remove::make_universal(&mut data, None).unwrap();
+    assert!(SaveGame::parse_lossless(&data).is_ok());
+    assert_eq!(data.len(), legacy.len() - 6);
+}
+
+/// Static array elements share a path; the value at `array_index`.
+fn element(save: &SaveGame, name: &str, array_index: i32) -> String {
+    let (_, property) = save
+        .walk()
+        .into_iter()
+        .find(|(path, property)| path == name && property.array_index == array_index)
+        .unwrap();
+    property.as_str().unwrap().to_string()
+}
+
+#[test]
+fn owners_sharing_a_path_are_emptied_by_offset() {
+    let mut data = read_fixture("static_owner.sav");
+    let removed = remove::make_universal(&mut data, None).unwrap();
+    assert_eq!(removed.len(), 1);
+    assert_eq!(removed[0].steamid, "76561198000000001");
+
+    let save = SaveGame::parse_lossless(&data).unwrap();
+    assert_eq!(element(&save, "OwnerSteamId", 0), "76561198333333333");
+    assert_eq!(element(&save, "OwnerSteamId", 1), "");
+}
+
+#[test]
+fn personalize_and_restore_fill_the_emptied_element() {
+    let original = read_fixture("static_owner.sav");
+    let mut data = original.clone();
+    remove::make_universal(&mut data, None).unwrap();
+    let universal = data.clone();
+
+    personalize::personalize(&mut data, "76561198000000002", None).unwrap();
+    let save = SaveGame::parse_lossless(&data).unwrap();
+    assert_eq!(element(&save, "OwnerSteamId", 0), "76561198333333333");
+    assert_eq!(element(&save, "OwnerSteamId", 1), "76561198000000002");
+
+    // Without a backup, undo writes the removed SteamID back by path.
+    let dir = scratch_dir("remove_static_undo");
+    let save = copy_fixture("static_owner.sav", &dir);
+    let options = remove::RemoveOptions {
+        backup: None,
+        ..Default::default()
+    };
+    remove::remove_steamid_in_file(&save, &options).unwrap();
+    assert!(std::fs::read(&save).unwrap() == universal);
+    let options = manifest::UndoOptions {
+        backup: None,
+        dry_run: false,
+    };
+    let report = manifest::undo(&save, &options).unwrap();
+    assert!(!report.from_backup);
+    assert!(std::fs::read(&save).unwrap() == original);
+}
@@ -0,0 +1,42 @@
+mod common;
+
+use common::{copy_fixture, scratch_dir};
+use steamid_replacer::error::Error;
+use steamid_replacer::replace::{self, ReplaceOptions};
+
+const NEW_STEAMID: &str = "76561198000000002";
+
+#[test]
+fn unknown_selected_offsets_are_reported() {
+    let dir = scratch_dir("replace_unknown_offsets");
+    let path = copy_fixture("two_owners.sav", &dir);
+    let options = ReplaceOptions {
+        backup: None,
+        dry_run: true,
+        only_offsets: Some(vec![0x10, 0xC71, 0xC58]),
+        ..Default::default()
+    };
+
+    match replace::replace_steamid_in_file(&path, NEW_STEAMID, &options) {
+        Err(Error::UnknownOffsets(offsets)) => assert_eq!(offsets, [0x10, 0xC58]),
+        other => panic!("{:?}", other.map(|report| report.offsets)),
+    }
+}
+
+#[test]
+fn previous_steamid_comes_from_a_property() {
+    let dir = scratch_dir("replace_previous_steamid");
+    let path = copy_fixture("two_owners.sav", &dir);
+    let lookalike = 0x4A3;
+    let owner = 0xC71;
+    let options = ReplaceOptions {
+        backup: None,
+        dry_run: true,
+        only_offsets: Some(vec![lookalike, owner]),
+        ..Default::default()
+    };
+
+    let report = replace::replace_steamid_in_file(&path, NEW_STEAMID, &options).unwrap();
+    assert_eq!(report.offsets, [lookalike, owner]);
+    assert_eq!(report.previous_steamid.as_deref(), Some("76561198000000001"));
+}
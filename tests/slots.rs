@@ -0,0 +1,93 @@
+mod common;
+
+use common::{copy_fixture, read_fixture, scratch_dir};
+use std::path::Path;
+use steamid_replacer::Error;
+use steamid_replacer::backup::BackupStore;
+use steamid_replacer::slots;
+
+/// Copies the fixture `name` into `dir` as the slot `slot`.
+fn slot(dir: &Path, slot: &str, name: &str) -> std::path::PathBuf {
+    std::fs::create_dir_all(dir).unwrap();
+    let path = dir.join(slot);
+    std::fs::copy(common::fixture(name), &path).unwrap();
+    path
+}
+
+#[test]
+fn swap_exchanges_contents_and_backs_up_both() {
+    let dir = scratch_dir("slots_swap");
+    let a = slot(&dir, "SBSave0001.sav", "owned.sav");
+    let b = slot(&dir, "SBSave0002.sav", "universal.sav");
+    let store = BackupStore::default();
+
+    let backups = slots::swap(&a, &b, &store).unwrap();
+    assert_eq!(std::fs::read(&a).unwrap(), read_fixture("universal.sav"));
+    assert_eq!(std::fs::read(&b).unwrap(), read_fixture("owned.sav"));
+    assert_eq!(std::fs::read(&backups[0]).unwrap(), read_fixture("owned.sav"));
+    assert_eq!(std::fs::read(&backups[1]).unwrap(), read_fixture("universal.sav"));
+
+    assert!(matches!(slots::swap(&a, &a, &store), Err(Error::SameSlot(_))));
+}
+
+#[test]
+fn failed_swap_puts_the_first_slot_back() {
+    let dir = scratch_dir("slots_swap_fails");
+    let a = slot(&dir.join("a"), "SBSave0001.sav", "owned.sav");
+    let b = slot(&dir.join("b"), "SBSave0002.sav", "universal.sav");
+    // Folders where the second slot's temp files would go, so writing it fails.
+    for id in 0..256 {
+        let name = format!(".SBSave0002.sav.{}.{}.tmp", std::process::id(), id);
+        std::fs::create_dir_all(dir.join("b").join(name)).unwrap();
+    }
+
+    assert!(slots::swap(&a, &b, &BackupStore::default()).is_err());
+    assert_eq!(std::fs::read(&a).unwrap(), read_fixture("owned.sav"));
+    assert_eq!(std::fs::read(&b).unwrap(), read_fixture("universal.sav"));
+}
+
+#[test]
+fn rename_takes_plain_names_only() {
+    let dir = scratch_dir("slots_rename");
+    let save = copy_fixture("owned.sav", &dir);
+
+    for name in ["", "  ", ".", "..", "../SBSave0002", "saves/SBSave0002", "saves\\SBSave0002", "C:SBSave0002"] {
+        assert!(
+            matches!(slots::rename(&save, name), Err(Error::InvalidSlotName(_))),
+            "'{}' was accepted",
+            name
+        );
+    }
+    assert!(save.exists());
+
+    let renamed = slots::rename(&save, " SBSave0002 ").unwrap();
+    assert_eq!(renamed, dir.join("SBSave0002.sav"));
+    let renamed = slots::rename(&renamed, "SBSave0003.SAV").unwrap();
+    assert_eq!(renamed, dir.join("SBSave0003.SAV"));
+    assert!(!save.exists());
+    assert_eq!(std::fs::read(&renamed).unwrap(), read_fixture("owned.sav"));
+
+    let other = copy_fixture("universal.sav", &dir);
+    assert!(matches!(slots::rename(&other, "SBSave0003.SAV"), Err(Error::SlotExists(_))));
+    assert_eq!(std::fs::read(&renamed).unwrap(), read_fixture("owned.sav"));
+}
+
+#[test]
+fn delete_moves_the_slot_into_the_backups() {
+    let dir = scratch_dir("slots_delete");
+    let save = copy_fixture("owned.sav", &dir);
+    let store = BackupStore {
+        dir: Some(dir.join("backups")),
+        ..Default::default()
+    };
+
+    let backup = slots::delete(&save, &store).unwrap();
+    assert!(!save.exists());
+    assert_eq!(std::fs::read(&backup).unwrap(), read_fixture("owned.sav"));
+    let backups = store.list(&save).unwrap();
+    assert_eq!(backups.len(), 1);
+    assert_eq!(backups[0].path, backup);
+
+    store.restore(&backup, &save).unwrap();
+    assert_eq!(std::fs::read(&save).unwrap(), read_fixture("owned.sav"));
+}
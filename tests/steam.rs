@@ -0,0 +1,54 @@
+mod common;
+
+use common::fixture;
+use std::path::PathBuf;
+use steamid_replacer::steam;
+
+#[test]
+fn login_users_most_recent_first() {
+    let accounts = steam::load_accounts(&fixture("steam")).unwrap();
+
+    let steamids: Vec<_> = accounts.iter().map(|account| account.steamid.to_string()).collect();
+    assert_eq!(steamids, ["76561198000000002", "76561198000000003", "76561198000000001"]);
+    assert!(accounts[0].most_recent);
+    assert_eq!(accounts[0].account_name, "lily");
+    assert_eq!(accounts[0].label(), "lily");
+    assert_eq!(accounts[2].persona_name, "Eve \"The\" Hunter");
+    assert_eq!(accounts[2].label(), "Eve \"The\" Hunter (eve_hunter)");
+    assert_eq!(accounts[2].timestamp, 1760000000);
+    assert_eq!(accounts[1].persona_name, "C:\\Users\\adam");
+}
+
+#[test]
+fn login_users_needs_a_users_section() {
+    assert!(steam::parse_login_users("\"other\" { }").is_err());
+    assert!(steam::parse_login_users("\"users\" {").is_err());
+}
+
+#[test]
+fn library_folders_current_layout() {
+    let steam_dir = fixture("steam");
+    let folders = steam::library_folders(&steam_dir);
+
+    assert_eq!(
+        folders,
+        [
+            steam_dir,
+            PathBuf::from("/home/deck/.local/share/Steam"),
+            PathBuf::from("/run/media/mmcblk0p1"),
+        ]
+    );
+}
+
+#[test]
+fn library_folders_old_layout() {
+    let text = std::fs::read_to_string(fixture("libraryfolders_old.vdf")).unwrap();
+    let folders = steam::parse_library_folders(&text).unwrap();
+
+    assert_eq!(folders, [PathBuf::from("D:\\SteamLibrary"), PathBuf::from("E:\\Games\\Steam")]);
+}
+
+#[test]
+fn library_folders_needs_its_section() {
+    assert!(steam::parse_library_folders("\"users\" { }").is_err());
+}
@@ -0,0 +1,79 @@
+use steamid_replacer::Error;
+use steamid_replacer::steamid::{self, SteamId};
+
+const STEAMID64: u64 = 76561198000000001;
+const ACCOUNT_ID: u32 = 39734273;
+
+fn reason(input: &str) -> &'static str {
+    match steamid::parse(input) {
+        Err(Error::SteamIdNotation { reason, .. }) => reason,
+        other => panic!("{} should be rejected, got {:?}", input, other.map(SteamId::steamid64)),
+    }
+}
+
+#[test]
+fn every_notation_reads_the_same_account() {
+    for input in [
+        "76561198000000001",
+        " 76561198000000001 ",
+        "STEAM_0:1:19867136",
+        "STEAM_1:1:19867136",
+        "[U:1:39734273]",
+        "U:1:39734273",
+        "39734273",
+        "https://steamcommunity.com/profiles/76561198000000001",
+        "http://steamcommunity.com/profiles/76561198000000001/",
+        "steamcommunity.com/profiles/76561198000000001",
+    ] {
+        let id = steamid::parse(input).unwrap_or_else(|e| panic!("{}: {}", input, e));
+        assert_eq!(id.steamid64(), STEAMID64, "{}", input);
+    }
+}
+
+#[test]
+fn notations_are_written_back() {
+    let id = SteamId::from_account_id(ACCOUNT_ID);
+    assert_eq!(id.to_string(), STEAMID64.to_string());
+    assert_eq!(id.account_id(), ACCOUNT_ID);
+    assert_eq!(id.steam2(), "STEAM_0:1:19867136");
+    assert_eq!(id.steam3(), "[U:1:39734273]");
+    assert_eq!(id.profile_url(), "https://steamcommunity.com/profiles/76561198000000001");
+    assert_eq!(steamid::parse(&id.steam2()).unwrap(), id);
+    assert_eq!(steamid::parse(&id.steam3()).unwrap(), id);
+}
+
+#[test]
+fn malformed_notations_are_rejected() {
+    assert_eq!(reason(""), "empty");
+    assert_eq!(reason("abc"), "unrecognised format");
+    assert_eq!(reason("0"), "account ID is zero");
+    assert_eq!(reason("99999999999999999999"), "number is too large");
+    assert_eq!(reason("STEAM_0:1"), "expected STEAM_X:Y:Z");
+    assert_eq!(reason("STEAM_2:1:19867136"), "not in the public universe");
+    assert_eq!(reason("STEAM_0:2:19867136"), "Y must be 0 or 1");
+    assert_eq!(reason("STEAM_0:0:x"), "Z is not a valid account number");
+    assert_eq!(reason("STEAM_0:0:0"), "account ID is zero");
+    assert_eq!(reason("[U:1]"), "expected [U:1:N]");
+    assert_eq!(reason("[G:1:39734273]"), "not an individual account");
+    assert_eq!(reason("[U:0:39734273]"), "not in the public universe");
+    assert_eq!(reason("[U:1:0]"), "account ID is zero");
+    assert_eq!(reason("[U:1:4294967296]"), "N is not a valid account ID");
+}
+
+#[test]
+fn vanity_urls_and_bad_profile_urls_are_rejected() {
+    assert!(reason("https://steamcommunity.com/id/eve_hunter").starts_with("custom profile URLs"));
+    assert_eq!(
+        reason("https://steamcommunity.com/profiles/eve_hunter"),
+        "profile URL does not end in a SteamID"
+    );
+}
+
+#[test]
+fn steamid64_bits_are_checked() {
+    assert_eq!(reason("148618791998193669"), "not in the public universe");
+    assert_eq!(reason("103582795724488709"), "not an individual account");
+    assert_eq!(reason("76561193665298437"), "not a user instance");
+    assert_eq!(reason("76561197960265728"), "account ID is zero");
+    assert!(SteamId::from_steamid64(STEAMID64).is_ok());
+}
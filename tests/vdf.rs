@@ -0,0 +1,65 @@
+mod common;
+
+use steamid_replacer::error::Error;
+use steamid_replacer::vdf::{self, Vdf};
+
+#[test]
+fn nested_blocks_keep_their_order() {
+    let root = vdf::parse("\"a\" { \"b\" { \"c\" \"1\" } \"d\" \"2\" }\n\"e\" \"3\"").unwrap();
+
+    let a = root.get("a").unwrap();
+    assert_eq!(a.get("b").unwrap().get_str("c"), Some("1"));
+    assert_eq!(a.get_str("d"), Some("2"));
+    assert_eq!(root.get_str("e"), Some("3"));
+    let keys: Vec<_> = a.entries().iter().map(|(key, _)| key.as_str()).collect();
+    assert_eq!(keys, ["b", "d"]);
+}
+
+#[test]
+fn keys_are_case_insensitive() {
+    let root = vdf::parse("\"Users\" { \"MostRecent\" \"1\" }").unwrap();
+    assert_eq!(root.get("users").unwrap().get_str("mostrecent"), Some("1"));
+}
+
+#[test]
+fn quoted_strings_unescape() {
+    let root = vdf::parse(r#""quote" "say \"hi\"" "tab" "a\tb" "slash" "a\\b" "path" "C:\Games""#).unwrap();
+
+    assert_eq!(root.get_str("quote"), Some("say \"hi\""));
+    assert_eq!(root.get_str("tab"), Some("a\tb"));
+    assert_eq!(root.get_str("slash"), Some("a\\b"));
+    // Older files do not escape backslashes in Windows paths.
+    assert_eq!(root.get_str("path"), Some("C:\\Games"));
+}
+
+#[test]
+fn unquoted_tokens_comments_and_conditions() {
+    let text = "// header comment\nroot\n{\n\tkey value [$WIN32]\n\t\"empty\" \"\"\n\tnested { x y }\n}\n";
+    let root = vdf::parse(text).unwrap();
+    let inner = root.get("root").unwrap();
+
+    assert_eq!(inner.get_str("key"), Some("value"));
+    assert_eq!(inner.get_str("empty"), Some(""));
+    assert_eq!(inner.get("nested").unwrap().get_str("x"), Some("y"));
+}
+
+#[test]
+fn errors_name_the_line() {
+    for (text, line) in [("\"a\"\n{\n\"b\" \"c\"\n", 4), ("\"a\" \"b\n\nc", 3), ("\"a\"\n}", 2)] {
+        match vdf::parse(text) {
+            Err(Error::InvalidVdf { line: found, .. }) => assert_eq!(found, line, "{:?}", text),
+            other => panic!("{:?}: {:?}", text, other),
+        }
+    }
+}
+
+#[test]
+fn written_text_parses_back() {
+    let text = std::fs::read_to_string(common::fixture("steam/config/loginusers.vdf")).unwrap();
+    let mut root = vdf::parse(&text).unwrap();
+    root.get_mut("users").unwrap().set("Added", "with \"quotes\" and \\");
+
+    let written = vdf::to_text(&root);
+    assert_eq!(vdf::parse(&written).unwrap(), root);
+    assert!(matches!(root.get("users"), Some(Vdf::Object(_))));
+}